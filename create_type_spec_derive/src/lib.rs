@@ -24,7 +24,7 @@ use uuid::Uuid;
 /// is due to to some inconsistencies in the ABI generation. If you forget to do this, your
 /// contract ABI might become corrupted, making it difficult for the browser and other blockchain
 /// explorers to display and interact with your contract.
-#[proc_macro_derive(CreateTypeSpec, attributes(discriminant))]
+#[proc_macro_derive(CreateTypeSpec, attributes(discriminant, pbc))]
 pub fn create_type_spec(input: TokenStream) -> TokenStream {
     // Parse the AST
     let ast = syn::parse(input).unwrap();
@@ -39,7 +39,7 @@ pub fn create_type_spec(input: TokenStream) -> TokenStream {
 /// Derive the `CreateTypeSpec` trait for structs and enum, with an internal view; for use in
 /// `pbc_contract_common`.
 #[doc(hidden)]
-#[proc_macro_derive(CreateTypeSpecInternal)]
+#[proc_macro_derive(CreateTypeSpecInternal, attributes(pbc))]
 pub fn create_type_spec_internal(input: TokenStream) -> TokenStream {
     // Parse the AST
     let ast = syn::parse(input).unwrap();
@@ -90,6 +90,8 @@ fn impl_create_type_spec(
     let type_name = &ast.ident;
     let trait_name: syn::Path = syn::parse_quote! { pbc_traits::CreateTypeSpec };
 
+    let transparent_field_type = transparent_field_type(&ast);
+
     // Generics
     let mut generics = ast.generics;
     derive_commons::extend_generic_bounds_with_trait(&mut generics, &trait_name);
@@ -106,18 +108,59 @@ fn impl_create_type_spec(
         quote! {}
     };
 
-    let create_type_spec_impl = create_type_spec_impl(type_name, type_id, &trait_name, &generics);
+    let create_type_spec_impl = match &transparent_field_type {
+        Some(field_type) => {
+            create_transparent_type_spec_impl(type_name, field_type, &trait_name, &generics)
+        }
+        None => create_type_spec_impl(type_name, type_id, &trait_name, &generics),
+    };
+
+    let tuple_types = find_tuple_types_in_data(&ast.data, &generics);
+    let tuple_abi_exporters = derive_commons::make_tuple_abi_exporters(
+        &tuple_types,
+        &uuid.simple().to_string(),
+        &abi_module_prefix,
+    );
+
+    if transparent_field_type.is_some() {
+        // Transparent types are described by their inner type, and define no named type.
+        return quote! {
+            #create_type_spec_impl
+
+            #[cfg(feature = "abi")]
+            #[automatically_derived]
+            #[doc = "PBC ABI gen internal method. Ensures that [`"]
+            #[doc = stringify!(#type_name #ty_generics)]
+            #[doc = "`] is visible to the ABI generator."]
+            #[doc = "This is a method used by the PBC ABI-gen system for generating ABI (machine-readable descriptions of smart contracts.)"]
+            pub fn #abi_for_type_function_name #impl_generics (_named_types: &std::collections::BTreeMap<String, u8>) -> Vec<#abi_module_prefix::NamedTypeSpec> #where_clause {
+                vec![]
+            }
+
+            #create_type_spec_extern_c
+
+            #tuple_abi_exporters
+        };
+    }
 
+    let type_doc = doc_tokens(derive_commons::doc_comment(&ast.attrs));
     let abi_for_type_function_name_body = match ast.data {
         Data::Struct(ref data_struct) => {
-            let (field_names, field_types) = data_to_field_types(data_struct);
-            let field_names_ts = field_names.iter().map(|x| x.to_token_stream()).collect();
-            create_struct_type_spec_body(&field_names_ts, &field_types, &abi_module_prefix)
+            let (field_names, field_types, field_docs) = data_to_field_types(data_struct);
+            create_struct_type_spec_body(
+                &field_names,
+                &field_types,
+                &field_docs,
+                &type_doc,
+                &abi_module_prefix,
+            )
+        }
+        Data::Enum(ref data_enum) => {
+            create_enum_type_spec_body(data_enum, &type_doc, &abi_module_prefix)
         }
-        Data::Enum(ref data_enum) => create_enum_type_spec_body(data_enum, &abi_module_prefix),
         _ => panic!(
-            "CreateTypeSpec derive does not support Union, currently only structs with named \
-        fields and explicitly discriminated enums consisting of struct variants"
+            "CreateTypeSpec derive does not support Union, currently only structs and explicitly \
+        discriminated enums"
         ),
     };
 
@@ -142,14 +185,36 @@ fn impl_create_type_spec(
         }
 
         #create_type_spec_extern_c
+
+        #tuple_abi_exporters
     }
 }
 
+/// Finds the tuple types occurring in the fields of the given struct or enum.
+fn find_tuple_types_in_data(data: &Data, generics: &syn::Generics) -> Vec<syn::Type> {
+    let fields: Vec<&syn::Field> = match data {
+        Data::Struct(data_struct) => data_struct.fields.iter().collect(),
+        Data::Enum(data_enum) => data_enum
+            .variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter())
+            .collect(),
+        Data::Union(_) => vec![],
+    };
+    let mut tuple_types = vec![];
+    for field in fields {
+        derive_commons::find_tuple_types(&field.ty, generics, &mut tuple_types);
+    }
+    tuple_types
+}
+
 fn create_enum_type_spec_body(
     data_enum: &DataEnum,
+    type_doc: &TokenStream2,
     abi_module_prefix: &syn::Path,
 ) -> proc_macro2::TokenStream {
-    let (variant_discriminants, variant_names, variant_fields) = data_to_variants(data_enum);
+    let (variant_discriminants, variant_names, variant_fields, variant_docs) =
+        data_to_variants(data_enum);
     let variant_names_string: Vec<String> = variant_names.iter().map(|n| n.to_string()).collect();
     let variant_names_lowercase: Vec<Ident> = variant_names_string
         .iter()
@@ -183,20 +248,25 @@ fn create_enum_type_spec_body(
         )*
     };
 
-    let add_fields_to_variants: Vec<TokenStream2> = variant_fields.iter().zip(struct_variant_names.iter()).map(|(variant_field, struct_name)| {
-        let variant_field_names = &variant_field.1;
-        let variant_field_string_names: Vec<String> = variant_field_names
-            .iter().map(|name| name.to_string().to_lowercase()).collect();
-        let variant_field_types = &variant_field.0;
-        quote! {
-            #(
-                let #variant_field_names = #abi_module_prefix::NamedEntityAbi::new::<#variant_field_types>(
-                    #variant_field_string_names.to_string(), named_types,
-                );
-                #struct_name.add_field(#variant_field_names);
-            )*
-        }
-    }).collect();
+    let add_fields_to_variants: Vec<TokenStream2> = variant_fields
+        .iter()
+        .zip(struct_variant_names.iter())
+        .map(|(variant_field, struct_name)| {
+            let variant_field_string_names = &variant_field.1;
+            let variant_field_types = &variant_field.0;
+            let variant_field_docs = &variant_field.2;
+            quote! {
+                #(
+                    #struct_name.add_field(#abi_module_prefix::NamedEntityAbi {
+                        doc: #variant_field_docs,
+                        ..#abi_module_prefix::NamedEntityAbi::new::<#variant_field_types>(
+                            #variant_field_string_names.to_string(), named_types,
+                        )
+                    });
+                )*
+            }
+        })
+        .collect();
 
     quote! {
 
@@ -206,6 +276,7 @@ fn create_enum_type_spec_body(
             type_name,
             type_id, type_spec,
         );
+        type_spec.doc = #type_doc;
 
         #(
             let #variant_names_lowercase = #abi_module_prefix::EnumVariant::new(
@@ -221,6 +292,7 @@ fn create_enum_type_spec_body(
                 #variant_type_ids,
                 #variant_type_specs,
             );
+            #struct_variant_names.doc = #variant_docs;
             #add_fields_to_variants
             named_types_in_fn.push(#struct_variant_names);
         )*
@@ -228,15 +300,12 @@ fn create_enum_type_spec_body(
 }
 
 fn create_struct_type_spec_body(
-    field_names: &Vec<TokenStream2>,
-    field_types: &Vec<TokenStream2>,
+    field_names: &[String],
+    field_types: &[TokenStream2],
+    field_docs: &[TokenStream2],
+    type_doc: &TokenStream2,
     abi_module_prefix: &syn::Path,
 ) -> proc_macro2::TokenStream {
-    let fields_string_names: Vec<String> = field_names
-        .iter()
-        .map(|name| -> String { name.to_string().to_lowercase() })
-        .collect();
-
     quote! {
 
         let mut type_abi = #abi_module_prefix::NamedTypeSpec::new_struct(
@@ -244,48 +313,119 @@ fn create_struct_type_spec_body(
             type_id,
             type_spec,
         );
+        type_abi.doc = #type_doc;
         #(
-            let #field_names = #abi_module_prefix::NamedEntityAbi::new::<#field_types>(
-                #fields_string_names.to_string(),
-                named_types,
-            );
-            type_abi.add_field(#field_names);
+            type_abi.add_field(#abi_module_prefix::NamedEntityAbi {
+                doc: #field_docs,
+                ..#abi_module_prefix::NamedEntityAbi::new::<#field_types>(
+                    #field_names.to_string(),
+                    named_types,
+                )
+            });
         )*
 
         named_types_in_fn.push(type_abi);
     }
 }
 
+/// The types, names and documentation of fields in the ABI.
+type AbiFields = (Vec<String>, Vec<TokenStream2>, Vec<TokenStream2>);
+
 type VariantInfo<'a> = (
     Vec<Literal>,
     Vec<&'a Ident>,
-    Vec<(Vec<TokenStream2>, Vec<TokenStream2>)>,
+    Vec<(Vec<TokenStream2>, Vec<String>, Vec<TokenStream2>)>,
+    Vec<TokenStream2>,
 );
 
-fn data_to_variants(data: &DataEnum) -> VariantInfo {
+/// Determines the discriminants, names, fields and documentation of the variants of the given
+/// enum.
+///
+/// Each variant is described as a struct; tuple variants use the positional field names `"0"`,
+/// `"1"`, etc., like tuple structs, and unit variants have no fields.
+fn data_to_variants(data: &DataEnum) -> VariantInfo<'_> {
     let mut discriminants: Vec<Literal> = vec![];
     let mut idents: Vec<&Ident> = vec![];
-    let mut variant_fields: Vec<(Vec<TokenStream2>, Vec<TokenStream2>)> = vec![];
+    let mut variant_fields: Vec<(Vec<TokenStream2>, Vec<String>, Vec<TokenStream2>)> = vec![];
+    let mut variant_docs: Vec<TokenStream2> = vec![];
 
     for variant in &data.variants {
-        match variant.fields {
-            Fields::Named(ref fields) => {
-                let (variant_discriminator, variant_fields_types, variant_fields_names) =
-                    extract_enum_variant_data(fields, &variant.attrs);
-                discriminants.push(variant_discriminator);
-                idents.push(&variant.ident);
-                variant_fields.push((variant_fields_types, variant_fields_names));
-            }
-            _ => panic!("Derive CreateTypeSpec only supports explicitly discriminated enums consisting of struct variants"),
+        if variant.discriminant.is_some() {
+            panic!("Derive CreateTypeSpec only supports explicitly discriminated enums where each variant has a discriminant attribute");
         }
+        let (variant_discriminator, variant_fields_types, variant_fields_members, variant_options) =
+            extract_enum_variant_data(&variant.fields, &variant.attrs);
+        let (variant_fields_names, variant_fields_types, variant_fields_docs) = abi_fields(
+            &variant_fields_members,
+            variant_fields_types,
+            &variant_options,
+            &variant.fields,
+        );
+        discriminants.push(variant_discriminator);
+        idents.push(&variant.ident);
+        variant_fields.push((
+            variant_fields_types,
+            variant_fields_names,
+            variant_fields_docs,
+        ));
+        variant_docs.push(doc_tokens(derive_commons::doc_comment(&variant.attrs)));
     }
     if has_unique_elements(discriminants.iter().map(|d| d.to_string())) {
-        (discriminants, idents, variant_fields)
+        (discriminants, idents, variant_fields, variant_docs)
     } else {
         panic!("Duplicate discriminant values")
     }
 }
 
+/// Determines the names, types and documentation of the fields present in the ABI, from the
+/// members, types, `#[pbc(...)]` options and doc comments of all fields.
+///
+/// Skipped fields are left out, renamed fields use their new name, and fields with custom
+/// serialization are described by the `AbiType` of their serialization module.
+fn abi_fields(
+    members: &[syn::Member],
+    types: Vec<TokenStream2>,
+    options: &[derive_commons::FieldOptions],
+    fields: &Fields,
+) -> AbiFields {
+    let mut abi_fields: AbiFields = (vec![], vec![], vec![]);
+    for (((member, ty), options), field) in members.iter().zip(types).zip(options).zip(fields) {
+        if options.skip {
+            continue;
+        }
+        let name = match &options.rename {
+            Some(rename) => rename.clone(),
+            None => member_to_name(member),
+        };
+        let ty = match &options.with {
+            Some(module) => quote! { #module::AbiType },
+            None => ty,
+        };
+        abi_fields.0.push(name);
+        abi_fields.1.push(ty);
+        abi_fields
+            .2
+            .push(doc_tokens(derive_commons::doc_comment(&field.attrs)));
+    }
+    abi_fields
+}
+
+/// The expression for the given documentation in the ABI.
+fn doc_tokens(doc: Option<String>) -> TokenStream2 {
+    match doc {
+        Some(doc) => quote! { ::core::option::Option::Some(#doc.to_string()) },
+        None => quote! { ::core::option::Option::None },
+    }
+}
+
+/// Name of the given field member in the ABI.
+fn member_to_name(member: &syn::Member) -> String {
+    match member {
+        syn::Member::Named(ident) => ident.to_string().to_lowercase(),
+        syn::Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
 fn identifier_creator(
     type_id: &str,
     called_method: syn::Ident,
@@ -302,6 +442,68 @@ fn identifier_creator(
     quote! { #type_id.to_owned() #generics_str }
 }
 
+/// Implements `CreateTypeSpec` for a `#[repr(transparent)]` type by delegating to the type of its
+/// only field, such that the ABI shows the inner type directly.
+fn create_transparent_type_spec_impl(
+    type_name: &Ident,
+    field_type: &TokenStream2,
+    trait_name: &syn::Path,
+    generics: &syn::Generics,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[cfg(feature = "abi")]
+        #[automatically_derived]
+        impl #impl_generics #trait_name for #type_name #ty_generics #where_clause {
+            fn __ty_name() -> String {
+                <#field_type as #trait_name>::__ty_name()
+            }
+
+            fn __ty_identifier() -> String {
+                <#field_type as #trait_name>::__ty_identifier()
+            }
+
+            fn __ty_spec_write(w: &mut Vec<u8>, named_types: &std::collections::BTreeMap<String, u8>) {
+                <#field_type as #trait_name>::__ty_spec_write(w, named_types)
+            }
+        }
+    }
+}
+
+/// Determines the type of the only field of a `#[repr(transparent)]` struct, or `None` if the
+/// type is not annotated with `#[repr(transparent)]`.
+///
+/// Panics if a `#[repr(transparent)]` type does not have exactly one field.
+fn transparent_field_type(ast: &syn::DeriveInput) -> Option<TokenStream2> {
+    let is_transparent = ast.attrs.iter().any(is_repr_transparent);
+    if !is_transparent {
+        return None;
+    }
+    match &ast.data {
+        Data::Struct(data_struct) if data_struct.fields.len() == 1 => data_struct
+            .fields
+            .iter()
+            .next()
+            .map(derive_commons::field_to_type),
+        _ => panic!("Derive CreateTypeSpec only supports #[repr(transparent)] for structs with exactly one field"),
+    }
+}
+
+/// Whether the given attribute is `#[repr(transparent)]`.
+fn is_repr_transparent(attr: &syn::Attribute) -> bool {
+    if !attr.path.is_ident("repr") {
+        return false;
+    }
+    match attr.parse_meta() {
+        Ok(syn::Meta::List(list)) => list.nested.iter().any(|nested| match nested {
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) => path.is_ident("transparent"),
+            _ => false,
+        }),
+        _ => false,
+    }
+}
+
 fn create_type_spec_impl(
     type_name: &Ident,
     type_id: String,
@@ -381,23 +583,28 @@ fn create_type_spec_extern_c(ast_type: &syn::TypePath) -> proc_macro2::TokenStre
     }
 }
 
-fn data_to_field_types(data: &DataStruct) -> (Vec<Ident>, Vec<TokenStream2>) {
-    match data.fields {
-        Fields::Named(ref fields) => {
-            let names: Vec<Ident> = fields
-                .named
-                .iter()
-                .map(derive_commons::field_to_name)
-                .collect();
-            let types: Vec<TokenStream2> = fields
-                .named
-                .iter()
-                .map(derive_commons::field_to_type)
-                .collect();
-            (names, types)
-        }
-        _ => {
-            panic!("Derive CreateTypeSpec only supports named fields for structs")
-        }
+/// Determines the ABI names, types and documentation of the fields of the given struct.
+///
+/// Tuple structs use the positional names `"0"`, `"1"`, etc., like tuples, unless renamed.
+fn data_to_field_types(data: &DataStruct) -> AbiFields {
+    if let Fields::Unit = data.fields {
+        panic!("Derive CreateTypeSpec does not support unit structs")
     }
+    let members: Vec<syn::Member> = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| derive_commons::field_to_member(index, field))
+        .collect();
+    let types: Vec<TokenStream2> = data
+        .fields
+        .iter()
+        .map(derive_commons::field_to_type)
+        .collect();
+    let options: Vec<_> = data
+        .fields
+        .iter()
+        .map(derive_commons::field_options)
+        .collect();
+    abi_fields(&members, types, &options, &data.fields)
 }
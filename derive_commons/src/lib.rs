@@ -13,7 +13,7 @@ use std::hash::Hash;
 use proc_macro2::{Literal, TokenStream};
 use quote::ToTokens;
 use syn::parse_quote::parse;
-use syn::{Attribute, Data, Fields, FieldsNamed, Ident, Type};
+use syn::{Attribute, Data, Fields, Ident, Type};
 
 /// Extracts a fields identifier
 ///
@@ -23,14 +23,16 @@ pub fn field_to_name(field: &syn::Field) -> Ident {
     field.ident.clone().unwrap()
 }
 
-/// Type for creating a new SERIALIZABLE_BY_COPY const field for the [`ReadWriteState`] trait.
+/// Type for creating the items only present in the [`ReadWriteState`] trait; the
+/// SERIALIZABLE_BY_COPY const field and the `state_skip` method.
 ///
 /// Can assume that `Self` will refer to the implementing struct.
 ///
 /// # Arguments
-/// * First - Enum differentiating the kind of the derive type.
-/// * Second - The trait name
-type SerializableByCopyConstFieldCreator = fn(&SupportedKind, &Ident) -> TokenStream;
+/// * First - Name of the derive type.
+/// * Second - Enum differentiating the kind of the derive type.
+/// * Third - The trait name
+type StateItemsCreator = fn(&Ident, &SupportedKind, &Ident) -> TokenStream;
 
 /// Which methods to generate: read, write or both at the same time.
 pub struct ReadWriteGenType {
@@ -82,18 +84,25 @@ pub fn extend_generic_bounds_with_trait(generics: &mut syn::Generics, trait_name
 /// Implement a named trait that has read and a write methods.
 /// The signature matches `ReadRPC`, `WriteRPC` and `ReadWriteState`.
 ///
+/// When read methods are generated, the fallible `try_`-prefixed variant of `read_method` is
+/// generated alongside it.
+///
 /// # Arguments
 /// * `ast` - A Abstract Syntax Tree of the struct calling the procedural macro.
 /// * `trait_name` - Identifier of the trait to derive for
 /// * `read_method` - Identifier of the trait read method
 /// * `write_method` - Identifier of the trait write method
-/// * `serializable_by_copy_creator` - If `Some` it must create a [`TokenStream`] for creating a new const field.
+/// * `size_method` - Identifier of the trait method computing the serialized size; generated
+///   alongside the write method.
+/// * `state_items_creator` - If `Some` it must create a [`TokenStream`] with the items only
+///   present in the [`ReadWriteState`] trait.
 pub fn impl_read_write(
     ast: &syn::DeriveInput,
     trait_name: Ident,
     read_method: Ident,
     write_method: Ident,
-    serializable_by_copy_creator: Option<SerializableByCopyConstFieldCreator>,
+    size_method: Ident,
+    state_items_creator: Option<StateItemsCreator>,
     generation_type: ReadWriteGenType,
 ) -> TokenStream {
     // Extract basic data
@@ -130,23 +139,110 @@ pub fn impl_read_write(
 
     // Compute method logic
     let supported_kind = get_kind_data(&ast.data);
-    let (read_logic, write_logic) = match &supported_kind {
-        SupportedKind::StructWithNamedFields { fields } => {
-            let field_names: Vec<_> = fields
+    let try_read_method = format_ident!("try_{}", read_method);
+    let (read_logic, write_logic) = make_read_and_write_logic(
+        name,
+        &supported_kind,
+        &ReadCall::infallible(read_method.clone()),
+        &write_method,
+    );
+    let (try_read_logic, _) = make_read_and_write_logic(
+        name,
+        &supported_kind,
+        &ReadCall::fallible(try_read_method.clone()),
+        &write_method,
+    );
+
+    let size_logic = make_size_logic(&supported_kind, &write_method, &size_method);
+
+    // Compute state items if proper arguments were given. Sizes of SERIALIZABLE_BY_COPY values
+    // are known at compile time.
+    let (joined_const_field, size_logic) = match state_items_creator {
+        Some(state_items_creator_fn) => (
+            state_items_creator_fn(name, &supported_kind, &trait_name),
+            quote! {
+                if <Self as pbc_traits::#trait_name>::SERIALIZABLE_BY_COPY {
+                    return ::core::mem::size_of::<Self>();
+                }
+                #size_logic
+            },
+        ),
+        None => (quote! {}, size_logic),
+    };
+
+    let read_block = if generation_type.read {
+        quote! {
+            #joined_const_field
+            fn #read_method<#type_read: pbc_traits::PbcRead>(reader: &mut #type_read) -> Self {
+                #read_logic
+            }
+
+            fn #try_read_method<#type_read: pbc_traits::PbcRead>(reader: &mut #type_read) -> ::core::result::Result<Self, pbc_traits::DecodeError> {
+                #try_read_logic
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let write_block = if generation_type.write {
+        quote! {
+            fn #write_method<#type_write: pbc_traits::PbcWrite>(&self, writer: &mut #type_write) -> pbc_traits::io::Result<()> {
+                #write_logic
+            }
+
+            fn #size_method(&self) -> usize {
+                #size_logic
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+            #[automatically_derived]
+            impl #impl_generics pbc_traits::#trait_name for #name #ty_generics #where_clause {
+                #read_block
+                #write_block
+            }
+    }
+}
+
+/// Compute the read and write logic for the given kind of type.
+///
+/// * `type_name` - Name of the type being derived for.
+/// * `supported_kind` - The kind of the type.
+/// * `read_call` - How fields are read, and whether the read is fallible.
+/// * `write_method` - Identifier of the trait write method.
+fn make_read_and_write_logic(
+    type_name: &Ident,
+    supported_kind: &SupportedKind,
+    read_call: &ReadCall,
+    write_method: &Ident,
+) -> (TokenStream, TokenStream) {
+    match supported_kind {
+        SupportedKind::StructWithNamedFields { fields }
+        | SupportedKind::StructWithUnnamedFields { fields } => {
+            let members: Vec<syn::Member> = fields
                 .iter()
-                .map(|x| field_to_name(x).to_token_stream())
+                .enumerate()
+                .map(|(index, field)| field_to_member(index, field))
                 .collect();
-            let write_field_names: Vec<_> =
-                field_names.iter().map(|x| quote! { &self.#x }).collect();
+            let field_inits: Vec<_> = members.iter().map(member_init).collect();
+            let field_names: Vec<_> = members.iter().map(member_binding).collect();
+            let write_field_names: Vec<_> = members.iter().map(|x| quote! { &self.#x }).collect();
             let field_types: Vec<_> = fields.iter().map(field_to_type).collect();
+            let options: Vec<_> = fields.iter().map(field_options).collect();
 
             make_read_and_write_logic_struct(
                 quote! { Self },
+                &field_inits,
                 &field_names,
                 &write_field_names,
                 &field_types,
-                &read_method,
-                &write_method,
+                &options,
+                read_call,
+                write_method,
             )
         }
         SupportedKind::DiscriminatedCstyleEnum {
@@ -157,86 +253,304 @@ pub fn impl_read_write(
             let variant_exprs: Vec<_> = variants.iter().map(|x| x.1).collect();
 
             make_read_and_write_logic_cstyle_enum(
+                type_name,
                 &variant_names,
                 &variant_exprs,
                 discriminant_type,
-                &read_method,
-                &write_method,
+                read_call,
+                write_method,
             )
         }
         SupportedKind::ItemStructEnum {
             variant_discriminants,
             variant_names,
             variant_fields_types,
-            variant_fields_names,
+            variant_fields_members,
+            variant_fields_options,
         } => {
             let mut read_variants: Vec<TokenStream> = vec![];
             let mut write_variants: Vec<TokenStream> = vec![];
             let mut variant_params: Vec<TokenStream> = vec![];
             for i in 0..variant_names.len() {
                 let name: &syn::Ident = variant_names.get(i).unwrap();
-                let field_names: &Vec<TokenStream> = variant_fields_names.get(i).unwrap();
+                let members: &Vec<syn::Member> = variant_fields_members.get(i).unwrap();
                 let field_types: &Vec<TokenStream> = variant_fields_types.get(i).unwrap();
+                let options: &Vec<FieldOptions> = variant_fields_options.get(i).unwrap();
+                let field_inits: Vec<_> = members.iter().map(member_init).collect();
+                let field_names: Vec<_> = members.iter().map(member_binding).collect();
                 let read_write = make_read_and_write_logic_struct(
                     quote! { Self::#name },
-                    field_names,
-                    field_names,
+                    &field_inits,
+                    &field_names,
+                    &field_names,
                     field_types,
-                    &read_method,
-                    &write_method,
+                    options,
+                    read_call,
+                    write_method,
                 );
                 read_variants.push(read_write.0);
                 write_variants.push(read_write.1);
-                variant_params.push(quote! { #(#field_names),* });
+
+                variant_params.push(make_variant_pattern(&field_inits, options));
             }
 
             make_read_and_write_item_struct_enum(
+                type_name,
                 variant_discriminants,
                 variant_names,
                 &variant_params,
                 &read_variants,
                 &write_variants,
-                &read_method,
-                &write_method,
+                read_call,
+                write_method,
             )
         }
-    };
+    }
+}
 
-    // Compute const field if proper arguments were given.
-    let joined_const_field = match serializable_by_copy_creator {
-        Some(serializable_by_copy_creator_fn) => {
-            serializable_by_copy_creator_fn(&supported_kind, &trait_name)
+/// Compute the logic of the method determining the serialized size, for the given kind of type.
+///
+/// * `supported_kind` - The kind of the type.
+/// * `write_method` - Identifier of the trait write method.
+/// * `size_method` - Identifier of the trait size method.
+fn make_size_logic(
+    supported_kind: &SupportedKind,
+    write_method: &Ident,
+    size_method: &Ident,
+) -> TokenStream {
+    match supported_kind {
+        SupportedKind::StructWithNamedFields { fields }
+        | SupportedKind::StructWithUnnamedFields { fields } => {
+            let sizes: Vec<_> = fields
+                .iter()
+                .enumerate()
+                .filter_map(|(index, field)| {
+                    let member = field_to_member(index, field);
+                    make_field_size(
+                        quote! { &self.#member },
+                        &field_to_type(field),
+                        &field_options(field),
+                        write_method,
+                        size_method,
+                    )
+                })
+                .collect();
+            quote! { 0 #(+ #sizes)* }
         }
-        None => quote! {},
-    };
-
-    let read_block = if generation_type.read {
-        quote! {
-            #joined_const_field
-            fn #read_method<#type_read: std::io::Read>(reader: &mut #type_read) -> Self {
-                #read_logic
+        SupportedKind::DiscriminatedCstyleEnum {
+            discriminant_type, ..
+        } => {
+            quote! { ::core::mem::size_of::<#discriminant_type>() }
+        }
+        SupportedKind::ItemStructEnum {
+            variant_names,
+            variant_fields_types,
+            variant_fields_members,
+            variant_fields_options,
+            ..
+        } => {
+            let mut variant_params: Vec<TokenStream> = vec![];
+            let mut variant_sizes: Vec<TokenStream> = vec![];
+            for i in 0..variant_names.len() {
+                let members: &Vec<syn::Member> = variant_fields_members.get(i).unwrap();
+                let field_types: &Vec<TokenStream> = variant_fields_types.get(i).unwrap();
+                let options: &Vec<FieldOptions> = variant_fields_options.get(i).unwrap();
+                let field_inits: Vec<_> = members.iter().map(member_init).collect();
+                let sizes: Vec<_> = members
+                    .iter()
+                    .zip(field_types.iter())
+                    .zip(options.iter())
+                    .filter_map(|((member, ty), options)| {
+                        make_field_size(
+                            member_binding(member),
+                            ty,
+                            options,
+                            write_method,
+                            size_method,
+                        )
+                    })
+                    .collect();
+                variant_params.push(make_variant_pattern(&field_inits, options));
+                variant_sizes.push(quote! { ::core::mem::size_of::<u8>() #(+ #sizes)* });
+            }
+            quote! {
+                match self {
+                    #(Self::#variant_names { #variant_params } => #variant_sizes,)*
+                }
             }
         }
+    }
+}
+
+/// Expression computing the serialized size of a field, or nothing if the field is skipped.
+///
+/// * `value` - Expression referencing the value of the field.
+/// * `ty` - The type of the field.
+/// * `options` - The serialization options of the field.
+/// * `write_method` - Identifier of the trait write method.
+/// * `size_method` - Identifier of the trait size method.
+fn make_field_size(
+    value: TokenStream,
+    ty: &TokenStream,
+    options: &FieldOptions,
+    write_method: &Ident,
+    size_method: &Ident,
+) -> Option<TokenStream> {
+    match (options.skip, &options.with) {
+        (true, _) => None,
+        (false, Some(module)) => Some(quote! {
+            pbc_traits::ByteCounter::count_written(|writer| #module::#write_method(#value, writer))
+        }),
+        (false, None) => Some(quote! { <#ty>::#size_method(#value) }),
+    }
+}
+
+/// Pattern matching the fields of an enum variant. Skipped fields are not bound, as they are not
+/// serialized, and would otherwise be unused.
+fn make_variant_pattern(field_inits: &[TokenStream], options: &[FieldOptions]) -> TokenStream {
+    let written_inits: Vec<_> = field_inits
+        .iter()
+        .zip(options.iter())
+        .filter(|(_, options)| !options.skip)
+        .map(|(init, _)| init)
+        .collect();
+    let rest = if written_inits.len() < field_inits.len() {
+        quote! { .. }
     } else {
         quote! {}
     };
+    quote! { #(#written_inits,)* #rest }
+}
 
-    let write_block = if generation_type.write {
-        quote! {
-            fn #write_method<#type_write: std::io::Write>(&self, writer: &mut #type_write) -> std::io::Result<()> {
-                #write_logic
+/// Serialization options for a field, given by `#[pbc(...)]` attributes.
+///
+/// Supported attributes are:
+///
+/// - `#[pbc(skip)]`: The field is not serialized, nor present in the ABI. It is reconstructed
+///   using [`Default`] when deserializing.
+/// - `#[pbc(with = "module")]`: The field is serialized by functions in `module`, named like the
+///   methods of the derived trait; e.g. `state_read_from`, `try_state_read_from` and
+///   `state_write_to` for `ReadWriteState`. The ABI describes the field by the type alias
+///   `module::AbiType`.
+/// - `#[pbc(rename = "name")]`: The field is named `name` in the ABI.
+#[derive(Default)]
+pub struct FieldOptions {
+    /// Whether the field is skipped during serialization.
+    pub skip: bool,
+    /// Module with custom serialization functions for the field.
+    pub with: Option<syn::Path>,
+    /// Name of the field in the ABI.
+    pub rename: Option<String>,
+}
+
+impl FieldOptions {
+    /// Whether the field is serialized using its own implementation of the derived trait.
+    pub fn is_verbatim(&self) -> bool {
+        !self.skip && self.with.is_none()
+    }
+}
+
+/// Extracts the serialization options of a field from its `#[pbc(...)]` attributes.
+///
+/// May panic if the attributes are malformed.
+///
+/// * `field` - Field to extract options from.
+pub fn field_options(field: &syn::Field) -> FieldOptions {
+    let mut options = FieldOptions::default();
+    for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("pbc")) {
+        let nested = match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => list.nested,
+            _ => panic!("Attribute 'pbc' must be of the form #[pbc(...)]"),
+        };
+        for meta in nested {
+            match meta {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("skip") => {
+                    options.skip = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(value),
+                    ..
+                })) if path.is_ident("with") => {
+                    let module = value
+                        .parse()
+                        .unwrap_or_else(|_| panic!("Attribute 'pbc(with)' must name a module"));
+                    options.with = Some(module);
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(value),
+                    ..
+                })) if path.is_ident("rename") => {
+                    options.rename = Some(value.value());
+                }
+                _ => panic!(
+                    "Unsupported 'pbc' attribute; expected skip, with = \"module\" or rename = \"name\""
+                ),
             }
         }
-    } else {
-        quote! {}
-    };
+    }
+    if options.skip && (options.with.is_some() || options.rename.is_some()) {
+        panic!("Attribute 'pbc(skip)' cannot be combined with other options");
+    }
+    options
+}
 
-    quote! {
-            #[automatically_derived]
-            impl #impl_generics pbc_traits::#trait_name for #name #ty_generics #where_clause {
-                #read_block
-                #write_block
+/// Extracts the documentation of an item from its `#[doc = "..."]` attributes, which `///`
+/// comments desugar to. Returns `None` if the item is undocumented.
+///
+/// The lines are joined with newlines, with the space following `///` removed.
+///
+/// * `attrs` - Attributes of the documented item.
+pub fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let mut lines: Vec<String> = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("doc")) {
+        if let Ok(syn::Meta::NameValue(syn::MetaNameValue {
+            lit: syn::Lit::Str(value),
+            ..
+        })) = attr.parse_meta()
+        {
+            for line in value.value().split('\n') {
+                let line = line.strip_prefix(' ').unwrap_or(line);
+                lines.push(line.trim_end().to_string());
             }
+        }
+    }
+    let doc = lines.join("\n");
+    let doc = doc.trim_matches('\n');
+    if doc.is_empty() {
+        None
+    } else {
+        Some(doc.to_string())
+    }
+}
+
+/// Determines the member used to access a field; its identifier for named fields, and its
+/// position for unnamed fields.
+///
+/// * `index` - Position of the field.
+/// * `field` - Field to determine member for.
+pub fn field_to_member(index: usize, field: &syn::Field) -> syn::Member {
+    match &field.ident {
+        Some(ident) => syn::Member::Named(ident.clone()),
+        None => syn::Member::Unnamed(syn::Index::from(index)),
+    }
+}
+
+/// Name of the local variable holding the value of the given member in generated code.
+pub fn member_binding(member: &syn::Member) -> TokenStream {
+    match member {
+        syn::Member::Named(ident) => ident.to_token_stream(),
+        syn::Member::Unnamed(index) => format_ident!("field_{}", index.index).to_token_stream(),
+    }
+}
+
+/// Field initializer, or field pattern, binding the given member to its local variable.
+pub fn member_init(member: &syn::Member) -> TokenStream {
+    let binding = member_binding(member);
+    match member {
+        syn::Member::Named(_) => binding,
+        syn::Member::Unnamed(_) => quote! { #member: #binding },
     }
 }
 
@@ -247,14 +561,9 @@ pub fn field_to_type(field: &syn::Field) -> TokenStream {
     let ty: TokenStream = match &field.ty {
         Type::Path(path) => path.to_token_stream(),
         Type::Array(arr) => {
-            let ident = match arr.elem.as_ref() {
-                Type::Path(type_path) => type_path
-                    .path
-                    .segments
-                    .last()
-                    .unwrap()
-                    .ident
-                    .to_token_stream(),
+            let elem = match arr.elem.as_ref() {
+                Type::Path(type_path) => type_path.to_token_stream(),
+                Type::Array(_) | Type::Tuple(_) => arr.elem.to_token_stream(),
                 _ => panic!("Unknown array element type"),
             };
 
@@ -263,40 +572,127 @@ pub fn field_to_type(field: &syn::Field) -> TokenStream {
                 _ => panic!("The length of an array must be a literal"),
             };
 
-            parse(quote!([#ident; #len]))
+            parse(quote!([#elem; #len]))
         }
+        Type::Tuple(tuple) => tuple.to_token_stream(),
         ty => panic!("Unsupported type: {:}", quote! { #ty }),
     };
     ty.to_token_stream()
 }
 
+/// Finds the tuple types occurring in the given type, including tuples nested within other types,
+/// and adds those not already present to `found`.
+///
+/// Tuples mentioning any of the type parameters in `generics` are skipped, as they cannot be
+/// described without knowing the instantiation of the parameters.
+///
+/// * `ty` - Type to search.
+/// * `generics` - Generics in scope for the type.
+/// * `found` - Tuple types found so far.
+pub fn find_tuple_types(ty: &Type, generics: &syn::Generics, found: &mut Vec<Type>) {
+    match ty {
+        Type::Tuple(tuple) => {
+            for elem in &tuple.elems {
+                find_tuple_types(elem, generics, found);
+            }
+            let type_params: Vec<&Ident> = generics.type_params().map(|x| &x.ident).collect();
+            let type_string = ty.to_token_stream().to_string();
+            let is_new = !found
+                .iter()
+                .any(|other| other.to_token_stream().to_string() == type_string);
+            if is_new && !mentions_any(ty.to_token_stream(), &type_params) {
+                found.push(ty.clone());
+            }
+        }
+        Type::Path(path) => {
+            for segment in &path.path.segments {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    for arg in &args.args {
+                        if let syn::GenericArgument::Type(arg) = arg {
+                            find_tuple_types(arg, generics, found);
+                        }
+                    }
+                }
+            }
+        }
+        Type::Array(array) => find_tuple_types(&array.elem, generics, found),
+        Type::Group(group) => find_tuple_types(&group.elem, generics, found),
+        Type::Paren(paren) => find_tuple_types(&paren.elem, generics, found),
+        _ => {}
+    }
+}
+
+/// Whether the given tokens contain any of the given identifiers.
+fn mentions_any(tokens: TokenStream, idents: &[&Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => idents.contains(&&ident),
+        proc_macro2::TokenTree::Group(group) => mentions_any(group.stream(), idents),
+        _ => false,
+    })
+}
+
+/// Generates exported functions making the given tuple types visible to the ABI generator, which
+/// describes them as anonymous structs.
+///
+/// * `tuple_types` - Tuple types to export, as found by [`find_tuple_types`].
+/// * `symbol_prefix` - Prefix making the names of the exported functions unique.
+/// * `abi_module_prefix` - Path to the module containing `abi_for_tuple`.
+pub fn make_tuple_abi_exporters(
+    tuple_types: &[Type],
+    symbol_prefix: &str,
+    abi_module_prefix: &syn::Path,
+) -> TokenStream {
+    let function_names: Vec<Ident> = (0..tuple_types.len())
+        .map(|index| format_ident!("__abi_type_as_fn_ptr_tuple_{}_{}", symbol_prefix, index))
+        .collect();
+
+    quote! {
+        #(
+            #[cfg(feature = "abi")]
+            #[no_mangle]
+            #[doc = "PBC ABI gen internal method. Ensures that [`"]
+            #[doc = stringify!(#tuple_types)]
+            #[doc = "`] is visible to the ABI generator."]
+            #[doc = "This is a method used by the PBC ABI-gen system for generating ABI (machine-readable descriptions of smart contracts.)"]
+            #[automatically_derived]
+            pub unsafe extern "C" fn #function_names () -> u32 {
+                let function_pointer = #abi_module_prefix::abi_for_tuple::<#tuple_types> as *const ();
+                function_pointer as u32
+            }
+        )*
+    }
+}
+
 /// Attempts to convert the given AST element to [`SupportedKind`], an enum detailing the kind of
 /// the annotated type.
 ///
 /// May panic if the kind is unsupported.
-pub fn get_kind_data(data: &Data) -> SupportedKind {
+pub fn get_kind_data(data: &Data) -> SupportedKind<'_> {
     match *data {
         Data::Struct(ref data) => match data.fields {
             Fields::Named(ref fields) => SupportedKind::StructWithNamedFields { fields: &fields.named },
-            _ => panic!("PBC serialization derives currently only supports named fields for structs"),
+            Fields::Unnamed(ref fields) => SupportedKind::StructWithUnnamedFields { fields: &fields.unnamed },
+            Fields::Unit => panic!("PBC serialization derives do not support unit structs"),
         },
         Data::Enum(ref data) => {
             let mut cstyle_variants = vec![];
             let mut struct_variants_discriminants: Vec<Literal> = vec![];
             let mut struct_variants_idents: Vec<&syn::Ident> = vec![];
             let mut struct_variants_field_types: Vec<Vec<TokenStream>> = vec![];
-            let mut struct_variants_field_names: Vec<Vec<TokenStream>> = vec![];
+            let mut struct_variants_field_members: Vec<Vec<syn::Member>> = vec![];
+            let mut struct_variants_field_options: Vec<Vec<FieldOptions>> = vec![];
             for x in &data.variants {
                 match (&x.fields, &x.discriminant, &x.attrs, &x.ident) {
                     (Fields::Unit, Some((_, expr)), _, _) => cstyle_variants.push((&x.ident, expr)),
-                    (Fields::Named(name), _, attrs, ident) => {
-                        let (variant_discriminator, variant_fields_types, variant_fields_names) = extract_enum_variant_data(name, attrs);
+                    (fields, None, attrs, ident) if !matches!(fields, Fields::Unit) || has_discriminant_attribute(attrs) => {
+                        let (variant_discriminator, variant_fields_types, variant_fields_members, variant_fields_options) = extract_enum_variant_data(fields, attrs);
                         struct_variants_discriminants.push(variant_discriminator);
                         struct_variants_idents.push(ident);
                         struct_variants_field_types.push(variant_fields_types);
-                        struct_variants_field_names.push(variant_fields_names);
+                        struct_variants_field_members.push(variant_fields_members);
+                        struct_variants_field_options.push(variant_fields_options);
                     }
-                    (_, _, _, _) => panic!("PBC serialization derives only supports explicitly discriminated C-style enums and enums consisting of struct variants"),
+                    (_, _, _, _) => panic!("PBC serialization derives only supports explicitly discriminated C-style enums and enums where each variant has a discriminant attribute"),
                 }
             }
             if cstyle_variants.is_empty() {
@@ -305,7 +701,8 @@ pub fn get_kind_data(data: &Data) -> SupportedKind {
                         variant_discriminants: struct_variants_discriminants,
                         variant_names: struct_variants_idents,
                         variant_fields_types: struct_variants_field_types,
-                        variant_fields_names: struct_variants_field_names,
+                        variant_fields_members: struct_variants_field_members,
+                        variant_fields_options: struct_variants_field_options,
                     }
                 } else {
                     panic!("Duplicate discriminant values")
@@ -328,28 +725,33 @@ where
     iter.into_iter().all(move |x| uniq.insert(x))
 }
 
+/// Whether the given attributes contain a `discriminant` attribute.
+fn has_discriminant_attribute(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| attr.path.is_ident("discriminant"))
+}
+
+/// Data describing an enum variant; see [`extract_enum_variant_data`].
+pub type EnumVariantData = (
+    Literal,
+    Vec<TokenStream>,
+    Vec<syn::Member>,
+    Vec<FieldOptions>,
+);
+
 /// Extract variant data.
-/// Returns a tuple consisting of the discriminator, variant_field_types, and variant_field_names.
+/// Returns a tuple consisting of the discriminator, variant_field_types, variant_field_members and
+/// variant_field_options.
+///
+/// Variants may have named fields, unnamed fields or no fields at all.
 ///
 /// May panic if the variant does not have a discriminant attribute.
-pub fn extract_enum_variant_data(
-    name: &FieldsNamed,
-    attrs: &[Attribute],
-) -> (Literal, Vec<TokenStream>, Vec<TokenStream>) {
+pub fn extract_enum_variant_data(fields: &Fields, attrs: &[Attribute]) -> EnumVariantData {
     let discriminator_ident = format_ident!("discriminant");
-    let variant_fields_types: Vec<TokenStream> = name.named.iter().map(field_to_type).collect();
-    let variant_fields_names: Vec<&syn::Ident> = name
-        .named
-        .iter()
-        .map(|x| {
-            x.ident
-                .as_ref()
-                .expect("EnumItemStruct must have a name Identifier")
-        })
-        .collect();
-    let variant_fields_names_ts: Vec<TokenStream> = variant_fields_names
+    let variant_fields_types: Vec<TokenStream> = fields.iter().map(field_to_type).collect();
+    let variant_fields_members: Vec<syn::Member> = fields
         .iter()
-        .map(|&x| x.to_token_stream())
+        .enumerate()
+        .map(|(index, field)| field_to_member(index, field))
         .collect();
     let variant_discriminator_stream: TokenStream = attrs
         .iter()
@@ -362,10 +764,12 @@ pub fn extract_enum_variant_data(
         });
     let variant_discriminator: Literal = syn::parse2(discriminator_with_paren.stream())
         .unwrap_or_else(|_| panic!("Discriminant is required to be a literal"));
+    let variant_fields_options: Vec<FieldOptions> = fields.iter().map(field_options).collect();
     (
         variant_discriminator,
         variant_fields_types,
-        variant_fields_names_ts,
+        variant_fields_members,
+        variant_fields_options,
     )
 }
 
@@ -376,6 +780,11 @@ pub enum SupportedKind<'a> {
         /// The fields for the struct
         fields: &'a syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
     },
+    /// Tuple struct, including newtypes
+    StructWithUnnamedFields {
+        /// The fields for the struct, in positional order
+        fields: &'a syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+    },
     /// C-style enum where all variants are annotated with explicit expressions.
     DiscriminatedCstyleEnum {
         /// The discriminant type
@@ -383,7 +792,8 @@ pub enum SupportedKind<'a> {
         /// The list of variants
         variants: Vec<(&'a syn::Ident, &'a syn::Expr)>,
     },
-    /// Enum where all variants are EnumItemStructs annotated with a discriminant attribute.
+    /// Enum where all variants are annotated with a discriminant attribute, and may carry data in
+    /// named or unnamed fields.
     ItemStructEnum {
         /// The discriminant of each variant
         variant_discriminants: Vec<Literal>,
@@ -391,36 +801,138 @@ pub enum SupportedKind<'a> {
         variant_names: Vec<&'a syn::Ident>,
         /// The field types for each variant
         variant_fields_types: Vec<Vec<TokenStream>>,
-        /// The field names for each variant
-        variant_fields_names: Vec<Vec<TokenStream>>,
+        /// The field members for each variant; names for named fields, and positions for unnamed
+        /// fields
+        variant_fields_members: Vec<Vec<syn::Member>>,
+        /// The field serialization options for each variant
+        variant_fields_options: Vec<Vec<FieldOptions>>,
     },
 }
 
-/// Implement read/write logic for a struct, specifically one with named fields.
+/// Describes how generated code reads values: the read method to call, and whether that method
+/// is fallible.
+///
+/// Fallible reads propagate errors with `?`, wrap the result in `Ok`, and return a
+/// `pbc_traits::DecodeError` where infallible reads would panic.
+struct ReadCall {
+    /// Identifier of the read method.
+    method: Ident,
+    /// Whether the read method returns a `Result`.
+    fallible: bool,
+}
+
+impl ReadCall {
+    /// Reads using a method returning the value itself.
+    fn infallible(method: Ident) -> Self {
+        ReadCall {
+            method,
+            fallible: false,
+        }
+    }
+
+    /// Reads using a method returning `Result<_, pbc_traits::DecodeError>`.
+    fn fallible(method: Ident) -> Self {
+        ReadCall {
+            method,
+            fallible: true,
+        }
+    }
+
+    /// Expression reading a value of the given type from `reader`.
+    fn read(&self, ty: &TokenStream) -> TokenStream {
+        let method = &self.method;
+        if self.fallible {
+            quote! { <#ty>::#method(reader)? }
+        } else {
+            quote! { <#ty>::#method(reader) }
+        }
+    }
+
+    /// Expression reading a value using the read method of the given module.
+    fn read_with(&self, module: &syn::Path) -> TokenStream {
+        let method = &self.method;
+        if self.fallible {
+            quote! { #module::#method(reader)? }
+        } else {
+            quote! { #module::#method(reader) }
+        }
+    }
+
+    /// Expression producing the result of the read method from the read value.
+    fn output(&self, value: TokenStream) -> TokenStream {
+        if self.fallible {
+            quote! { ::core::result::Result::Ok(#value) }
+        } else {
+            value
+        }
+    }
+
+    /// Expression diverging on the unknown discriminant `__unknown` for the given enum.
+    fn unknown_discriminant(&self, type_name: &Ident) -> TokenStream {
+        if self.fallible {
+            let type_name = type_name.to_string();
+            quote! {
+                return ::core::result::Result::Err(pbc_traits::DecodeError::InvalidDiscriminant {
+                    type_name: #type_name,
+                    discriminant: __unknown,
+                })
+            }
+        } else {
+            quote! { panic!("No known enum value with discriminant {}", __unknown) }
+        }
+    }
+}
+
+/// Implement read/write logic for a struct, or a struct-like enum variant.
+///
+/// Fields are read into the bindings `names`, and the value is constructed with `field_inits`,
+/// which allows positional fields to be initialized as `0: field_0`. Each field is read and
+/// written according to its [`FieldOptions`].
 ///
 /// This code is shared between `ReadRPC`, `WriteRPC` and `ReadWriteState`.
+#[allow(clippy::too_many_arguments)]
 fn make_read_and_write_logic_struct(
     variant_constructor: TokenStream,
+    field_inits: &[TokenStream],
     names: &[TokenStream],
     write_names: &[TokenStream],
     types: &[TokenStream],
-    read_method: &Ident,
+    options: &[FieldOptions],
+    read_call: &ReadCall,
     write_method: &Ident,
 ) -> (TokenStream, TokenStream) {
     // For all (names, types) write `let name_n = type_n::read_method(reader)`.
+    let reads: Vec<_> = types
+        .iter()
+        .zip(options.iter())
+        .map(|(ty, options)| match (options.skip, &options.with) {
+            (true, _) => quote! { <#ty as ::core::default::Default>::default() },
+            (false, Some(module)) => read_call.read_with(module),
+            (false, None) => read_call.read(ty),
+        })
+        .collect();
+    let constructed = read_call.output(quote! { #variant_constructor { #(#field_inits),* } });
     let read_lines = quote! {
         #(
-            let #names =  <#types>::#read_method(reader);
+            let #names = #reads;
         )*
-        #variant_constructor { #(#names),* }
+        #constructed
     };
 
-    // For all (names, types) write `self.field_n::write_method(reader)?`.
+    // For all (names, types) write `self.field_n::write_method(reader)?`, except skipped fields.
+    let writes: Vec<_> = write_names
+        .iter()
+        .zip(types.iter())
+        .zip(options.iter())
+        .filter(|(_, options)| !options.skip)
+        .map(|((name, ty), options)| match &options.with {
+            Some(module) => quote! { #module::#write_method(#name, writer)?; },
+            None => quote! { <#ty>::#write_method(#name, writer)?; },
+        })
+        .collect();
     let write_lines = quote! {
-        #(
-            <#types>::#write_method(#write_names, writer)?;
-        )*
-        Ok(())
+        #(#writes)*
+        ::core::result::Result::Ok(())
     };
 
     (read_lines, write_lines)
@@ -430,20 +942,24 @@ fn make_read_and_write_logic_struct(
 ///
 /// This code is shared between `ReadRPC`, `WriteRPC` and `ReadWriteState`.
 fn make_read_and_write_logic_cstyle_enum(
+    type_name: &Ident,
     variant_names: &[&syn::Ident],
     variant_expressions: &[&syn::Expr],
     discriminant_type: &Ident,
-    read_method: &Ident,
+    read_call: &ReadCall,
     write_method: &Ident,
 ) -> (TokenStream, TokenStream) {
+    let read_discriminant = read_call.read(&discriminant_type.to_token_stream());
+    let unknown_discriminant = read_call.unknown_discriminant(type_name);
+    let matched_value = read_call.output(quote! { __matched_value });
     let read_lines = quote! {
         // Read, then match value.
-        let __discriminant = #discriminant_type::#read_method(reader);
+        let __discriminant = #read_discriminant;
         let __matched_value = match __discriminant {
             #(
                 #variant_expressions => Self::#variant_names,
             )*
-            __unknown => panic!("No known enum value with discriminant {}", __unknown),
+            __unknown => #unknown_discriminant,
         };
 
         // This magic line ensures that discriminant type have been determined correctly at
@@ -452,8 +968,8 @@ fn make_read_and_write_logic_cstyle_enum(
         //
         // One unfortunate effect of this line is that the enum value must be PartialEq. Not a huge
         // problem, except for some mysterious error messages.
-        assert!(__matched_value == unsafe { std::mem::transmute::<#discriminant_type,Self>(__discriminant) });
-        __matched_value
+        assert!(__matched_value == unsafe { ::core::mem::transmute::<#discriminant_type,Self>(__discriminant) });
+        #matched_value
     };
 
     // Generate match on each enum value, with write.
@@ -472,24 +988,29 @@ fn make_read_and_write_logic_cstyle_enum(
 /// Implement read/write logic for a enum, specifically one with itemStruct variants.
 ///
 /// This code is shared between `ReadRPC`, `WriteRPC` and `ReadWriteState`.
+#[allow(clippy::too_many_arguments)]
 fn make_read_and_write_item_struct_enum(
+    type_name: &Ident,
     variant_discriminators: &[Literal],
     variant_names: &[&Ident],
     variant_params: &[TokenStream],
     read_variants: &[TokenStream],
     write_variants: &[TokenStream],
-    read_method: &Ident,
+    read_call: &ReadCall,
     write_method: &Ident,
 ) -> (TokenStream, TokenStream) {
+    // Each of the read_variants already produce the output of the read method.
+    let read_discriminant = read_call.read(&quote! { u8 });
+    let unknown_discriminant = read_call.unknown_discriminant(type_name);
     let read_lines = quote! {
-        let __discriminant = <u8>::#read_method(reader);
+        let __discriminant = #read_discriminant;
         let __matched_value = match __discriminant {
             #(
                 #variant_discriminators => {
                     #read_variants
                 },
             )*
-            (__unknown) => panic!("No known enum value with discriminant {}", __unknown),
+            (__unknown) => #unknown_discriminant,
         };
         __matched_value
     };
@@ -12,9 +12,9 @@ use syn::__private::TokenStream2;
 use syn::{parse_macro_input, AttributeArgs};
 
 use pbc_contract_codegen_internal::{
-    action_macro, callback_macro, init_macro, parse_attributes, parse_secret_type_input,
-    parse_shortname_override, parse_zk_argument, state_macro, zk_macro, SecretInput,
-    WrappedFunctionKind,
+    action_macro, callback_macro, init_macro, parse_attributes, parse_return_type,
+    parse_secret_type_input, parse_shortname_override, parse_zk_argument, state_macro, zk_macro,
+    SecretInput, WrappedFunctionKind,
 };
 use pbc_contract_common::FunctionKind;
 
@@ -179,17 +179,49 @@ pub fn init(attrs: TokenStream, input: TokenStream) -> TokenStream {
 ///   (state, vec![])
 /// }
 /// ```
+///
+/// # Return data
+///
+/// Actions can return data to the callbacks of their callers, using
+/// [`EventGroupBuilder::return_data`](pbc_contract_common::events::EventGroupBuilder::return_data).
+/// The type of the returned data can be declared in the ABI using the `returns = "<type>"`
+/// attribute, such that callers can decode the data with
+/// [`ExecutionResult::get_return_data`](pbc_contract_common::context::ExecutionResult::get_return_data).
+/// The type must implement [`pbc_traits::CreateTypeSpec`].
+///
+/// For example:
+///
+/// ```ignore
+/// # use pbc_contract_codegen::action;
+/// # use pbc_contract_common::context::*;
+/// # use pbc_contract_common::events::*;
+/// # type ContractState = u128;
+/// #[action(returns = "u128")]
+/// pub fn get_balance(
+///     context: ContractContext,
+///     state: ContractState,
+/// ) -> (ContractState, Vec<EventGroup>) {
+///   let mut event_group = EventGroup::builder();
+///   event_group.return_data(state);
+///   (state, vec![event_group.build()])
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn action(attrs: TokenStream, input: TokenStream) -> TokenStream {
     let args: AttributeArgs = parse_macro_input!(attrs as AttributeArgs);
     let attributes = parse_attributes(
         args,
-        vec!["shortname".to_string(), "zk".to_string()],
+        vec![
+            "shortname".to_string(),
+            "zk".to_string(),
+            "returns".to_string(),
+        ],
         vec![],
     );
     let shortname_override = parse_shortname_override(&attributes);
     let zk = parse_zk_argument(&attributes);
-    action_macro::handle_action_macro(input, shortname_override, zk)
+    let return_type = parse_return_type(&attributes);
+    action_macro::handle_action_macro(input, shortname_override, zk, return_type)
 }
 
 /// Public callback contract annotation
@@ -15,6 +15,7 @@ pub fn handle_action_macro(
     input: TokenStream,
     shortname_override: Option<Shortname>,
     zk_argument: bool,
+    return_type: Option<String>,
 ) -> TokenStream {
     let fn_ast: syn::ItemFn = syn::parse(input.clone()).unwrap();
     let names = determine_names(shortname_override, &fn_ast, "action", true);
@@ -53,6 +54,7 @@ pub fn handle_action_macro(
             rpc_pos,
             shortname_ident,
             SecretInput::None,
+            return_type,
         )
     };
 
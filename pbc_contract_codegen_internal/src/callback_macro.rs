@@ -54,6 +54,7 @@ pub fn handle_callback_macro(
             rpc_pos,
             shortname_ident,
             SecretInput::None,
+            None,
         )
     };
 
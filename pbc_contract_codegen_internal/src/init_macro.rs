@@ -47,6 +47,7 @@ pub fn handle_init_macro(input: TokenStream, zk_argument: bool) -> TokenStream {
             rpc_pos,
             shortname_ident,
             SecretInput::None,
+            None,
         )
     };
 
@@ -12,8 +12,7 @@ use std::collections::HashMap;
 use quote::ToTokens;
 use syn::__private::TokenStream2;
 use syn::{
-    AttributeArgs, FnArg, Ident, Lit, Meta, NestedMeta, PatType, ReturnType, Type, TypeArray,
-    TypePath,
+    AttributeArgs, FnArg, Ident, Lit, Meta, NestedMeta, PatType, ReturnType, Type, TypePath,
 };
 
 use crate::tokenized::{ArgumentList, InstantiableArgument, TokenizedInvocation};
@@ -37,10 +36,10 @@ pub mod zk_macro;
 /// * `args`: [`AttributeArgs`] - the args to be parsed.
 ///
 /// * `valid_names`: [`Vec<String>`] - valid names of attributes. Panics if an attribute name not
-/// in valid_names is present in args.
+///   in valid_names is present in args.
 ///
 /// * `required_names`: [`Vec<String>`] - required names. Panics if any of the attribute names is
-/// not present in args.
+///   not present in args.
 ///
 /// ### Returns
 ///
@@ -80,7 +79,7 @@ pub fn parse_attributes(
 
     for required_name in required_names {
         assert!(
-            result.get(&required_name).is_some(),
+            result.contains_key(&required_name),
             "Required attribute '{required_name}' is missing",
         );
     }
@@ -153,6 +152,25 @@ pub fn parse_secret_type_input(args: HashMap<String, Lit>) -> SecretInput {
     }
 }
 
+/// Gets the returns attribute of the arguments, naming the type of the data returned by an action.
+/// Panics if the attribute is not a string literal.
+///
+/// ### Parameters:
+///
+/// * `args`: &[HashMap<String, Lit>] - parsed attributes of a macro.
+///
+/// ### Returns
+/// Some of the return type if present in args, None if it is not present.
+pub fn parse_return_type(args: &HashMap<String, Lit>) -> Option<String> {
+    args.get("returns").map(|lit: &Lit| match lit {
+        Lit::Str(lit_str) => lit_str.value(),
+        _ => panic!(
+            "Invalid return type, expecting a string literal, but got: {}",
+            lit.to_token_stream()
+        ),
+    })
+}
+
 /// Describes the kind of secret input argument have been defined.
 pub enum SecretInput {
     /// The action does not require a secret input.
@@ -176,8 +194,7 @@ impl SecretInput {
 /// Whether the given integer literal is hex formatted or not.
 fn is_hex_literal(lit: &syn::LitInt) -> bool {
     let token_text = format!("{}", lit.token());
-    return token_text.starts_with("0x")
-        && token_text.chars().skip(2).all(|c| c.is_ascii_hexdigit());
+    token_text.starts_with("0x") && token_text.chars().skip(2).all(|c| c.is_ascii_hexdigit())
 }
 
 fn select_leb_bytes<const N: usize>(bytes: [u8; N]) -> Result<Shortname, String> {
@@ -264,7 +281,7 @@ impl WrappedFunctionKind {
 /// * `arguments`: [TokenizedInvocation], The arguments for the wrapped function.
 /// * `function_kind`: &[WrappedFunctionKind], The function kind, e.g. action or callback.
 /// * `check_zk_contract`: Option<[bool]>, If `Some(true)` asserts that the contract is a zk-contract.
-///     if `Some(false)` asserts that the contract is a public contract, otherwise no check is performed.
+///   if `Some(false)` asserts that the contract is a public contract, otherwise no check is performed.
 ///
 /// ### Returns:
 /// The [TokenStream2] for the wrapper function.
@@ -363,7 +380,7 @@ fn wrap_function_for_export(
             #[cfg(all(not(feature = "abi"), any(target_arch = "wasm32", doc)))]
             pbc_lib::exit::override_panic();
             #zk_check_stream
-            let mut #reader = unsafe { std::slice::from_raw_parts(input_buf_ptr, input_buf_len) };
+            let mut #reader = unsafe { ::core::slice::from_raw_parts(input_buf_ptr, input_buf_len) };
             let context = #ctx_expression;
             #(let #invoke_vars = #invoke_read_expr;)*
             #rpc_read
@@ -529,7 +546,7 @@ fn read_arguments_names_and_types(item: &syn::ItemFn, skip: usize) -> ArgumentLi
 }
 
 /// Determines the parameter type for the given function argument.
-fn determine_parameter_type(token: &FnArg) -> &PatType {
+pub(crate) fn determine_parameter_type(token: &FnArg) -> &PatType {
     match token {
         FnArg::Receiver(_) => {
             panic!("Contract functions must be bare functions.")
@@ -552,12 +569,13 @@ fn read_arguments_for_instantiation(token: &FnArg, is_state: bool) -> Instantiab
             InstantiableArgument::new(&var_name, expr)
         }
 
-        Type::Tuple(_) => {
-            panic!("Unsupported tuple type");
+        Type::Tuple(tuple) => {
+            let expr = generate_read_from_type_expression(tuple, is_state);
+            InstantiableArgument::new(&var_name, expr)
         }
 
         Type::Array(array) => {
-            let expr = generate_read_from_array_expression(array, is_state);
+            let expr = generate_read_from_type_expression(array, is_state);
             InstantiableArgument::new(&var_name, expr)
         }
 
@@ -582,7 +600,7 @@ fn read_arguments_for_instantiation(token: &FnArg, is_state: bool) -> Instantiab
 /// Generate instantiating expressions for the given type.
 ///
 /// This is a part of a macro and assumes that `input_buf` is in scope where the macro is called
-/// and that said ident represents an instance of std::io::Read.
+/// and that said ident represents an instance of [`pbc_traits::PbcRead`].
 ///
 /// * `path` - the AST type to generate an instantiating expression for
 /// * `is_state` - whether we are using [`pbc_traits::ReadWriteState`] or [`pbc_traits::ReadRPC`]
@@ -599,23 +617,22 @@ fn generate_read_from_path_expression(path: TypePath, is_state: bool) -> TokenSt
     quote! {<#type_name as #trait_type>::#read_from(&mut input_reader);}
 }
 
-/// Generate instantiating expressions for the given array.
+/// Generate instantiating expressions for the given array or tuple type.
 ///
-/// This is a part of a macro and assumes that `reader_ident` is in scope where the macro is called
-/// and that said ident represents an instance of std::io::Read.
+/// This is a part of a macro and assumes that `input_reader` is in scope where the macro is called
+/// and that said ident represents an instance of [`pbc_traits::PbcRead`].
 ///
-/// * `reader_ident` - the reader variable/expression
-/// * `path` - the AST type to generate an instantiating expression for
+/// * `ty` - the AST type to generate an instantiating expression for
 /// * `is_state` - whether we are using `pbc_traits::ReadWriteState` or `pbc_traits::ReadRPC`
-fn generate_read_from_array_expression(array: TypeArray, is_state: bool) -> TokenStream2 {
+fn generate_read_from_type_expression<T: ToTokens>(ty: T, is_state: bool) -> TokenStream2 {
     let (trait_type, read_from) = if is_state {
         (quote!(pbc_traits::ReadWriteState), quote!(state_read_from))
     } else {
         (quote!(pbc_traits::ReadRPC), quote!(rpc_read_from))
     };
 
-    let array_tokens = array.to_token_stream();
-    quote! { <#array_tokens as #trait_type>::#read_from(&mut input_reader); }
+    let type_tokens = ty.to_token_stream();
+    quote! { <#type_tokens as #trait_type>::#read_from(&mut input_reader); }
 }
 
 fn check_valid_zk_contract(zk_argument: bool, error_message: String) -> TokenStream2 {
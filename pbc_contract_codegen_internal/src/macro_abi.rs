@@ -1,6 +1,6 @@
 use syn::{Ident, ItemFn};
 
-use crate::{read_arguments_names_and_types, SecretInput, TokenStream2};
+use crate::{determine_parameter_type, read_arguments_names_and_types, SecretInput, TokenStream2};
 use pbc_contract_common::FunctionKind;
 
 fn fn_kind_snippet(fn_kind: FunctionKind) -> TokenStream2 {
@@ -17,6 +17,7 @@ pub(crate) fn make_hook_abi_fn(
     rpc_pos: usize,
     shortname_ident: TokenStream2,
     secret_type_input: SecretInput,
+    return_type: Option<String>,
 ) -> TokenStream2 {
     let fn_name = &fn_ast.sig.ident.to_string();
     let (params, types) = read_arguments_names_and_types(fn_ast, rpc_pos).convert_to_tuple();
@@ -29,7 +30,41 @@ pub(crate) fn make_hook_abi_fn(
             quote! { fn_abi.secret_argument::<#secret_type>(&lut); }
         }
     };
+    let mut tuple_types = vec![];
+    for token in fn_ast.sig.inputs.iter().skip(rpc_pos) {
+        let pat = determine_parameter_type(token);
+        derive_commons::find_tuple_types(&pat.ty, &fn_ast.sig.generics, &mut tuple_types);
+    }
+    let add_return_type = match return_type {
+        None => quote! {},
+        Some(return_type) => {
+            let return_type: syn::Type = syn::parse_str(&return_type).expect("Invalid return type");
+            derive_commons::find_tuple_types(&return_type, &fn_ast.sig.generics, &mut tuple_types);
+            quote! { fn_abi.return_type::<#return_type>(&lut); }
+        }
+    };
+    let add_docs = match derive_commons::doc_comment(&fn_ast.attrs) {
+        None => quote! {},
+        Some(doc) => {
+            let (documented_params, param_docs): (Vec<&String>, Vec<String>) = params
+                .iter()
+                .zip(parameter_docs(&doc, &params))
+                .filter_map(|(param, param_doc)| Some((param, param_doc?)))
+                .unzip();
+            quote! {
+                fn_abi.set_doc(#doc.to_string());
+                #(fn_abi.set_argument_doc(#documented_params, #param_docs.to_string());)*
+            }
+        }
+    };
+    let tuple_abi_exporters = derive_commons::make_tuple_abi_exporters(
+        &tuple_types,
+        fn_name,
+        &syn::parse_quote! { pbc_contract_common::abi },
+    );
     quote! {
+        #tuple_abi_exporters
+
         #[cfg(feature = "abi")]
         #[doc=concat!("ABI: Create ABI for [`", #fn_name, "`]")]
         #[automatically_derived]
@@ -37,11 +72,60 @@ pub(crate) fn make_hook_abi_fn(
             let mut fn_abi = pbc_contract_common::abi::FnAbi::new(#fn_name.to_string(), #shortname_ident, #fn_kind_snippet);
             #(fn_abi.argument::<#types>(#params.to_string(), &lut);)*
             #add_secret_argument
+            #add_return_type
+            #add_docs
             fn_abi
         }
     }
 }
 
+/// Extracts the documentation of the given parameters from the documentation of their function.
+///
+/// Parameters are documented by list items naming them in backticks, followed by a description
+/// after an optional `-` or `:`, as in ``* `amount` - The amount to transfer.``. Indented lines
+/// following an item continue its description.
+fn parameter_docs(doc: &str, params: &[String]) -> Vec<Option<String>> {
+    let mut docs: Vec<Option<String>> = vec![None; params.len()];
+    let mut current: Option<usize> = None;
+    for line in doc.lines() {
+        let trimmed = line.trim_start();
+        let item = trimmed
+            .strip_prefix("* ")
+            .or_else(|| trimmed.strip_prefix("- "));
+        if let Some(item) = item {
+            current = parameter_item(item).and_then(|(name, description)| {
+                let index = params.iter().position(|param| param == name)?;
+                docs[index] = Some(description.to_string());
+                Some(index)
+            });
+        } else if line.starts_with(char::is_whitespace) && !trimmed.is_empty() {
+            if let Some(param_doc) = current.and_then(|index| docs[index].as_mut()) {
+                param_doc.push(' ');
+                param_doc.push_str(trimmed.trim_end());
+            }
+        } else {
+            current = None;
+        }
+    }
+    docs
+}
+
+/// Splits a list item of the form ``` `name` - description``` into the name and the description.
+fn parameter_item(item: &str) -> Option<(&str, &str)> {
+    let (name, rest) = item.strip_prefix('`')?.split_once('`')?;
+    let rest = rest.trim_start();
+    let description = rest
+        .strip_prefix('-')
+        .or_else(|| rest.strip_prefix(':'))
+        .unwrap_or(rest)
+        .trim();
+    if description.is_empty() {
+        None
+    } else {
+        Some((name, description))
+    }
+}
+
 pub fn make_hook_abi_fn_delegator(delegated_function_to_call: &Ident) -> proc_macro2::TokenStream {
     let function_name = format_ident!("__abi_fn_as_fn_ptr_{}", delegated_function_to_call);
     let result = quote! {
@@ -1,5 +1,7 @@
 use proc_macro2::{Ident, TokenStream};
 
+/// Client version of the ABI format. The generated ABI is given a later version should the
+/// contract use encodings added since, as listed on `AbiHeader` in `pbc_contract_core`.
 static CLIENT_ABI_VERSION: [u8; 3] = [5, 4, 0];
 /// Binder version for private contracts.
 static BINDER_ABI_VERSION_ZK: [u8; 3] = [11, 0, 0];
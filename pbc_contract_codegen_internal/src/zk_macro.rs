@@ -55,6 +55,7 @@ pub fn handle_zk_macro(
             rpc_pos,
             shortname_ident,
             secret_type_input,
+            None,
         )
     };
 
@@ -8,6 +8,7 @@ use read_write_rpc_derive::WriteRPC;
 use read_write_state_derive::ReadWriteState;
 
 use std::fmt;
+use std::str::FromStr;
 
 /// An address identifier is a 20 byte array derived from the hash of the public key of
 /// an account.
@@ -88,8 +89,48 @@ impl AddressType {
             Self::GoveranceContract => 0x04,
         }
     }
+
+    /// The [`AddressType`] with the given discriminant, if any.
+    const fn from_discriminant(discriminant: u8) -> Option<Self> {
+        match discriminant {
+            0x00 => Some(Self::Account),
+            0x01 => Some(Self::SystemContract),
+            0x02 => Some(Self::PublicContract),
+            0x03 => Some(Self::ZkContract),
+            0x04 => Some(Self::GoveranceContract),
+            _ => None,
+        }
+    }
+}
+
+/// Converts the first byte of an [`Address`] to its [`AddressType`].
+impl TryFrom<u8> for AddressType {
+    type Error = InvalidAddressTypeError;
+
+    fn try_from(discriminant: u8) -> Result<Self, Self::Error> {
+        Self::from_discriminant(discriminant).ok_or(InvalidAddressTypeError { discriminant })
+    }
 }
 
+/// Error produced when converting a byte that is not the discriminant of any [`AddressType`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidAddressTypeError {
+    /// The invalid discriminant.
+    pub discriminant: u8,
+}
+
+impl fmt::Display for InvalidAddressTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "No address type with discriminant {:#04x}",
+            self.discriminant
+        )
+    }
+}
+
+impl std::error::Error for InvalidAddressTypeError {}
+
 /// A unique number that identifies accounts and contracts on Partisia BlockChain.
 ///
 /// [`Address`]es are used to for most blockchain interactions, including:
@@ -125,6 +166,154 @@ pub struct Address {
     pub identifier: Identifier,
 }
 
+/// Number of hexadecimal characters in the string form of an [`Address`].
+const ADDRESS_HEX_LENGTH: usize = 42;
+
+impl Address {
+    /// Creates the address of an [`Account`](AddressType::Account).
+    pub const fn account(identifier: Identifier) -> Self {
+        Self::new(AddressType::Account, identifier)
+    }
+
+    /// Creates the address of a [`SystemContract`](AddressType::SystemContract).
+    pub const fn system_contract(identifier: Identifier) -> Self {
+        Self::new(AddressType::SystemContract, identifier)
+    }
+
+    /// Creates the address of a [`PublicContract`](AddressType::PublicContract).
+    pub const fn public_contract(identifier: Identifier) -> Self {
+        Self::new(AddressType::PublicContract, identifier)
+    }
+
+    /// Creates the address of a [`ZkContract`](AddressType::ZkContract).
+    pub const fn zk_contract(identifier: Identifier) -> Self {
+        Self::new(AddressType::ZkContract, identifier)
+    }
+
+    /// Creates the address of a [`GoveranceContract`](AddressType::GoveranceContract).
+    pub const fn governance_contract(identifier: Identifier) -> Self {
+        Self::new(AddressType::GoveranceContract, identifier)
+    }
+
+    const fn new(address_type: AddressType, identifier: Identifier) -> Self {
+        Self {
+            address_type,
+            identifier,
+        }
+    }
+
+    /// Creates an address from its 42 character hexadecimal form, as produced by
+    /// [`Display`](fmt::Display). Usable in constants, where invalid input fails compilation.
+    ///
+    /// ```
+    /// # use pbc_contract_common::address::{Address, AddressType};
+    /// const TOKEN: Address = Address::from_hex("02000102030405060708090A0B0C0D0E0F55AADDFF");
+    /// assert_eq!(TOKEN.address_type, AddressType::PublicContract);
+    /// ```
+    ///
+    /// Panics if the string is not a valid address; use [`str::parse`] to handle invalid input.
+    pub const fn from_hex(hex: &str) -> Self {
+        match Self::try_from_hex(hex) {
+            Ok(address) => address,
+            Err(ParseAddressError::InvalidLength { .. }) => {
+                panic!("Address must consist of 42 hexadecimal characters")
+            }
+            Err(ParseAddressError::InvalidHexCharacter { .. }) => {
+                panic!("Address contains a character that is not hexadecimal")
+            }
+            Err(ParseAddressError::InvalidAddressType { .. }) => {
+                panic!("Address starts with an unknown address type")
+            }
+        }
+    }
+
+    /// Parses the 42 character hexadecimal form of an address.
+    const fn try_from_hex(hex: &str) -> Result<Self, ParseAddressError> {
+        let hex = hex.as_bytes();
+        if hex.len() != ADDRESS_HEX_LENGTH {
+            return Err(ParseAddressError::InvalidLength { length: hex.len() });
+        }
+        let mut bytes = [0u8; ADDRESS_HEX_LENGTH / 2];
+        let mut index = 0;
+        while index < hex.len() {
+            let nibble = match hex[index] {
+                character @ b'0'..=b'9' => character - b'0',
+                character @ b'a'..=b'f' => character - b'a' + 10,
+                character @ b'A'..=b'F' => character - b'A' + 10,
+                _ => return Err(ParseAddressError::InvalidHexCharacter { index }),
+            };
+            bytes[index / 2] = (bytes[index / 2] << 4) | nibble;
+            index += 1;
+        }
+
+        let Some(address_type) = AddressType::from_discriminant(bytes[0]) else {
+            return Err(ParseAddressError::InvalidAddressType {
+                discriminant: bytes[0],
+            });
+        };
+        let mut identifier = [0u8; 20];
+        let mut index = 0;
+        while index < identifier.len() {
+            identifier[index] = bytes[index + 1];
+            index += 1;
+        }
+        Ok(Self::new(address_type, identifier))
+    }
+}
+
+/// Parses the 42 character hexadecimal form of an address, as produced by
+/// [`Display`](fmt::Display), [`UpperHex`](fmt::UpperHex) and [`LowerHex`](fmt::LowerHex).
+impl FromStr for Address {
+    type Err = ParseAddressError;
+
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        Self::try_from_hex(hex)
+    }
+}
+
+/// Error produced when parsing an [`Address`] from a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseAddressError {
+    /// The string does not consist of exactly 42 characters.
+    InvalidLength {
+        /// Length of the string in bytes.
+        length: usize,
+    },
+    /// The string contains a character that is not a hexadecimal digit.
+    InvalidHexCharacter {
+        /// Byte index of the character.
+        index: usize,
+    },
+    /// The first byte is not the discriminant of any [`AddressType`].
+    InvalidAddressType {
+        /// The invalid discriminant.
+        discriminant: u8,
+    },
+}
+
+impl fmt::Display for ParseAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength { length } => write!(
+                f,
+                "Address must consist of {ADDRESS_HEX_LENGTH} hexadecimal characters, but was {length} long"
+            ),
+            Self::InvalidHexCharacter { index } => {
+                write!(f, "Invalid hexadecimal character at index {index}")
+            }
+            Self::InvalidAddressType { discriminant } => write!(
+                f,
+                "{}",
+                InvalidAddressTypeError {
+                    discriminant: *discriminant
+                }
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseAddressError {}
+
 impl fmt::UpperHex for Address {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:02X}", self.address_type.discriminant())?;
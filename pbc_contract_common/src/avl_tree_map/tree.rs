@@ -63,10 +63,10 @@
 //! A useful rule of thumb when working with [`AvlTreeMap`]s is to only initialize them in the `#[init]` invocation, and to never use them for internal computations.
 //! Using [`AvlTreeMap`]s in the values of other [`AvlTreeMap`] is technically possible, but discourage, due to the garbage collection issues.
 
+use pbc_traits::{PbcRead, PbcWrite};
 #[cfg(feature = "abi")]
 use std::collections::BTreeMap;
 use std::fmt::Debug;
-use std::io::{Read, Write};
 use std::marker::PhantomData;
 
 use crate::sorted_vec_map::entry::Entry;
@@ -75,7 +75,7 @@ use pbc_lib::wasm_avl::{
 };
 #[cfg(feature = "abi")]
 use pbc_traits::CreateTypeSpec;
-use pbc_traits::{ReadInt, ReadWriteState, WriteInt};
+use pbc_traits::{DecodeError, ReadInt, ReadWriteState, WriteInt};
 
 const U32_MAX: usize = u32::MAX as usize;
 
@@ -96,7 +96,7 @@ pub struct AvlTreeMap<K, V> {
 impl<K, V> ReadWriteState for AvlTreeMap<K, V> {
     const SERIALIZABLE_BY_COPY: bool = false;
 
-    fn state_read_from<T: Read>(reader: &mut T) -> Self {
+    fn state_read_from<T: PbcRead>(reader: &mut T) -> Self {
         let tree_id = reader.read_i32_le();
         AvlTreeMap {
             key_type: PhantomData,
@@ -105,7 +105,16 @@ impl<K, V> ReadWriteState for AvlTreeMap<K, V> {
         }
     }
 
-    fn state_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn try_state_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        let tree_id = reader.try_read_i32_le()?;
+        Ok(AvlTreeMap {
+            key_type: PhantomData,
+            value_type: PhantomData,
+            tree_id,
+        })
+    }
+
+    fn state_write_to<T: PbcWrite>(&self, writer: &mut T) -> pbc_traits::io::Result<()> {
         writer.write_i32_le(self.tree_id)
     }
 }
@@ -168,8 +177,7 @@ impl<K: ReadWriteState, V: ReadWriteState> AvlTreeMap<K, V> {
     ///
     /// * Option containing the value.
     pub fn get(&self, key: &K) -> Option<V> {
-        let mut key_bytes = Vec::new();
-        key.state_write_to(&mut key_bytes).unwrap();
+        let key_bytes = serialize_state(key);
         let value_size: usize = if AvlTreeMap::<K, V>::VALUE_SERIALIZABLE_BY_COPY {
             std::mem::size_of::<V>()
         } else {
@@ -197,8 +205,7 @@ impl<K: ReadWriteState, V: ReadWriteState> AvlTreeMap<K, V> {
     ///
     /// * boolean indicating the keys presence in the [`AvlTreeMap`].
     pub fn contains_key(&self, key: &K) -> bool {
-        let mut key_bytes = Vec::new();
-        key.state_write_to(&mut key_bytes).unwrap();
+        let key_bytes = serialize_state(key);
         U32_MAX != get_size(self.tree_id, &key_bytes)
     }
 
@@ -211,10 +218,8 @@ impl<K: ReadWriteState, V: ReadWriteState> AvlTreeMap<K, V> {
     /// * `key`: the key to insert
     /// * `value`: the corresponding value to insert
     pub fn insert(&mut self, key: K, value: V) {
-        let mut key_bytes = Vec::new();
-        key.state_write_to(&mut key_bytes).unwrap();
-        let mut value_bytes = Vec::new();
-        value.state_write_to(&mut value_bytes).unwrap();
+        let key_bytes = serialize_state(&key);
+        let value_bytes = serialize_state(&value);
         insert(self.tree_id, &key_bytes, &value_bytes);
     }
 
@@ -224,8 +229,7 @@ impl<K: ReadWriteState, V: ReadWriteState> AvlTreeMap<K, V> {
     ///
     /// * `key`: the key to remove from the map
     pub fn remove(&mut self, key: &K) {
-        let mut key_bytes = Vec::new();
-        key.state_write_to(&mut key_bytes).unwrap();
+        let key_bytes = serialize_state(key);
         remove(self.tree_id, &key_bytes);
     }
 
@@ -250,6 +254,13 @@ impl<K: ReadWriteState, V: ReadWriteState> AvlTreeMap<K, V> {
     }
 }
 
+/// Serializes a value to its state bytes, allocating exactly the needed space up front.
+fn serialize_state<T: ReadWriteState>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(value.state_serialized_size());
+    value.state_write_to(&mut bytes).unwrap();
+    bytes
+}
+
 pub struct AvlIterator<K, V> {
     prev_key: Option<Vec<u8>>,
     /// Ties the key type to the [`AvlTreeMap`]
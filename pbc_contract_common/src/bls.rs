@@ -0,0 +1,194 @@
+//! BLS signatures over the BLS12-381 curve, as used by the [`BlsSignature`]s and
+//! [`BlsPublicKey`]s of Partisia Blockchain.
+//!
+//! Signatures are points of G1 and public keys are points of G2, both in their 48 and 96 byte
+//! compressed encodings. A signature `s` of a message `m` by the secret key `k` is `k * H(m)`,
+//! where `H` hashes to G1 under the domain separation tag [`DST`], and it is valid for the public
+//! key `K = k * g2` if `e(s, g2) = e(H(m), K)`. Both sides are linear in the secret key, so
+//! summing signatures of a message gives a signature for the sum of the public keys.
+//!
+//! The curve arithmetic and pairing are provided by the [`bls12_381`] crate.
+//!
+//! [`BlsSignature`]: crate::BlsSignature
+//! [`BlsPublicKey`]: crate::BlsPublicKey
+
+use crate::BlsError;
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{
+    multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Gt,
+};
+use digest::generic_array::typenum::{U32, U64};
+use digest::generic_array::GenericArray;
+
+/// The domain separation tag messages are hashed to G1 under; that of the proof of possession
+/// scheme with signatures in G1 of the
+/// [BLS signature draft](https://datatracker.ietf.org/doc/draft-irtf-cfrg-bls-signature/).
+const DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Whether `signature` is a signature of `message` by `public_key`.
+///
+/// Encodings of points outside of G1 or G2, and the point at infinity, are never valid.
+pub(crate) fn verify(public_key: &[u8; 96], message: &[u8], signature: &[u8; 48]) -> bool {
+    match g2_from_compressed(public_key) {
+        Some(public_key) => verify_point(public_key.into(), message, signature),
+        None => false,
+    }
+}
+
+/// Whether `signature` is a signature of `message` by all of the `public_keys` together.
+pub(crate) fn verify_aggregate<'a>(
+    public_keys: impl IntoIterator<Item = &'a [u8; 96]>,
+    message: &[u8],
+    signature: &[u8; 48],
+) -> bool {
+    match sum_public_keys(public_keys) {
+        Ok(public_key) => verify_point(public_key, message, signature),
+        Err(_) => false,
+    }
+}
+
+fn verify_point(public_key: G2Projective, message: &[u8], signature: &[u8; 48]) -> bool {
+    let Some(signature) = g1_from_compressed(signature) else {
+        return false;
+    };
+    let public_key = G2Affine::from(public_key);
+    if bool::from(signature.is_identity() | public_key.is_identity()) {
+        return false;
+    }
+    let message = G1Affine::from(hash_to_g1(message, DST));
+    let generator = G2Prepared::from(-G2Affine::generator());
+    let product = multi_miller_loop(&[
+        (&signature, &generator),
+        (&message, &G2Prepared::from(public_key)),
+    ]);
+    product.final_exponentiation() == Gt::identity()
+}
+
+/// The sum of the given public keys.
+pub(crate) fn aggregate_public_keys<'a>(
+    public_keys: impl IntoIterator<Item = &'a [u8; 96]>,
+) -> Result<[u8; 96], BlsError> {
+    Ok(G2Affine::from(sum_public_keys(public_keys)?).to_compressed())
+}
+
+/// The sum of the given signatures.
+pub(crate) fn aggregate_signatures<'a>(
+    signatures: impl IntoIterator<Item = &'a [u8; 48]>,
+) -> Result<[u8; 48], BlsError> {
+    let mut sum = None::<G1Projective>;
+    for (index, signature) in signatures.into_iter().enumerate() {
+        let signature =
+            g1_from_compressed(signature).ok_or(BlsError::InvalidSignature { index })?;
+        sum = Some(sum.map_or(signature.into(), |sum| sum + signature));
+    }
+    Ok(G1Affine::from(sum.ok_or(BlsError::NothingToAggregate)?).to_compressed())
+}
+
+fn sum_public_keys<'a>(
+    public_keys: impl IntoIterator<Item = &'a [u8; 96]>,
+) -> Result<G2Projective, BlsError> {
+    let mut sum = None::<G2Projective>;
+    for (index, public_key) in public_keys.into_iter().enumerate() {
+        let public_key = g2_from_compressed(public_key)
+            .filter(|public_key| !bool::from(public_key.is_identity()))
+            .ok_or(BlsError::InvalidPublicKey { index })?;
+        sum = Some(sum.map_or(public_key.into(), |sum| sum + public_key));
+    }
+    sum.ok_or(BlsError::NothingToAggregate)
+}
+
+/// Decodes a compressed point, which must be in G1.
+fn g1_from_compressed(bytes: &[u8; 48]) -> Option<G1Affine> {
+    G1Affine::from_compressed(bytes).into()
+}
+
+/// Decodes a compressed point, which must be in G2.
+fn g2_from_compressed(bytes: &[u8; 96]) -> Option<G2Affine> {
+    G2Affine::from_compressed(bytes).into()
+}
+
+/// Hashes `message` to G1 by the suite `BLS12381G1_XMD:SHA-256_SSWU_RO_` of RFC 9380.
+fn hash_to_g1(message: &[u8], dst: &[u8]) -> G1Projective {
+    <G1Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(message, dst)
+}
+
+/// SHA-256 of [`sha2`], implementing the traits of the older version of [`digest`] that the hash
+/// to curve of [`bls12_381`] takes.
+#[derive(Clone, Default)]
+struct Sha256(sha2::Sha256);
+
+impl digest::Update for Sha256 {
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        sha2::Digest::update(&mut self.0, data);
+    }
+}
+
+impl digest::Reset for Sha256 {
+    fn reset(&mut self) {
+        sha2::Digest::reset(&mut self.0);
+    }
+}
+
+impl digest::BlockInput for Sha256 {
+    type BlockSize = U64;
+}
+
+impl digest::FixedOutputDirty for Sha256 {
+    type OutputSize = U32;
+
+    fn finalize_into_dirty(&mut self, out: &mut GenericArray<u8, U32>) {
+        out.copy_from_slice(&sha2::Digest::finalize_reset(&mut self.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls12_381::Scalar;
+
+    fn sign(secret_key: u64, message: &[u8]) -> [u8; 48] {
+        G1Affine::from(hash_to_g1(message, DST) * Scalar::from(secret_key)).to_compressed()
+    }
+
+    fn hex<const LEN: usize>(hex: &str) -> [u8; LEN] {
+        let mut bytes = [0; LEN];
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * index..2 * index + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    /// Test vectors of section J.9.1 of RFC 9380.
+    #[test]
+    fn hash_to_g1_vectors() {
+        const SUITE_DST: &[u8] = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        let vectors = [
+            (
+                &b""[..],
+                "052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1",
+                "08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265",
+            ),
+            (
+                &b"abc"[..],
+                "03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903",
+                "0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d",
+            ),
+        ];
+        for (message, x, y) in vectors {
+            let uncompressed: [u8; 96] = hex(&format!("{x}{y}"));
+            assert_eq!(
+                G1Affine::from(hash_to_g1(message, SUITE_DST)).to_uncompressed(),
+                uncompressed
+            );
+        }
+    }
+
+    #[test]
+    fn signatures_verify() {
+        let public_key = G2Affine::from(G2Affine::generator() * Scalar::from(42)).to_compressed();
+        let signature = sign(42, b"message");
+        assert!(verify(&public_key, b"message", &signature));
+        assert!(!verify(&public_key, b"other message", &signature));
+        assert!(!verify(&public_key, b"message", &sign(41, b"message")));
+    }
+}
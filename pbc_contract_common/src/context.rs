@@ -4,15 +4,17 @@
 //! executed.
 //!
 //! - [`ContractContext`] is used by all invocations to determine the what and why of the current
-//! invocation.
+//!   invocation.
 //! - [`CallbackContext`] is used by `#[callback]` invocations to indicate whether the invocation
-//! that triggered the callback succeeded, or if whether it resulted in an error. Also includes
-//! return data if applicable.
+//!   that triggered the callback succeeded, or if whether it resulted in an error. Also includes
+//!   return data if applicable.
 
-use std::io::{Read, Write};
+use pbc_traits::PbcRead;
+#[cfg(any(test, doc, feature = "test_examples"))]
+use pbc_traits::{PbcWrite, WriteRPC};
 
+use pbc_traits::DecodeError;
 use pbc_traits::ReadRPC;
-use pbc_traits::WriteRPC;
 use read_write_rpc_derive::ReadRPC;
 use read_write_rpc_derive::WriteRPC;
 
@@ -77,17 +79,23 @@ pub struct CallbackContext {
 
 /// Due to the implementation details of the code generation `rpc_read_from` is required for CallbackContext.
 impl ReadRPC for CallbackContext {
-    fn rpc_read_from<T: Read>(reader: &mut T) -> Self {
+    fn rpc_read_from<T: PbcRead>(reader: &mut T) -> Self {
         let success = bool::rpc_read_from(reader);
         let results = ReadRPC::rpc_read_from(reader);
         CallbackContext { success, results }
     }
+
+    fn try_rpc_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        let success = bool::try_rpc_read_from(reader)?;
+        let results = ReadRPC::try_rpc_read_from(reader)?;
+        Ok(CallbackContext { success, results })
+    }
 }
 
 /// Due to the implementation details of the code generation `rpc_read_from` is required for CallbackContext.
 #[cfg(any(test, doc, feature = "test_examples"))]
 impl WriteRPC for CallbackContext {
-    fn rpc_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn rpc_write_to<T: PbcWrite>(&self, writer: &mut T) -> pbc_traits::io::Result<()> {
         self.success.rpc_write_to(writer)?;
         self.results.rpc_write_to(writer)
     }
@@ -120,7 +128,7 @@ impl ExecutionResult {
 
 /// Needed since this struct is nested in [`CallbackContext`].
 impl ReadRPC for ExecutionResult {
-    fn rpc_read_from<T: Read>(reader: &mut T) -> Self {
+    fn rpc_read_from<T: PbcRead>(reader: &mut T) -> Self {
         let succeeded = bool::rpc_read_from::<T>(reader);
         let return_data = Vec::<u8>::rpc_read_from::<T>(reader);
 
@@ -129,12 +137,22 @@ impl ReadRPC for ExecutionResult {
             return_data,
         }
     }
+
+    fn try_rpc_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        let succeeded = bool::try_rpc_read_from::<T>(reader)?;
+        let return_data = Vec::<u8>::try_rpc_read_from::<T>(reader)?;
+
+        Ok(ExecutionResult {
+            succeeded,
+            return_data,
+        })
+    }
 }
 
 /// Needed since this struct is nested in [`CallbackContext`].
 #[cfg(any(test, doc, feature = "test_examples"))]
 impl WriteRPC for ExecutionResult {
-    fn rpc_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn rpc_write_to<T: PbcWrite>(&self, writer: &mut T) -> pbc_traits::io::Result<()> {
         self.succeeded.rpc_write_to(writer)?;
         self.return_data.rpc_write_to(writer)
     }
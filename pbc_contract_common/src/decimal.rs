@@ -0,0 +1,378 @@
+#[cfg(feature = "abi")]
+use crate::abi::{NamedEntityAbi, NamedTypeSpec};
+#[cfg(feature = "abi")]
+use pbc_traits::CreateTypeSpec;
+use pbc_traits::{DecodeError, PbcRead, PbcWrite, ReadRPC, ReadWriteState, WriteRPC};
+
+use crate::U256;
+use std::cmp::Ordering;
+#[cfg(feature = "abi")]
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A non-negative fixed-point decimal number with `SCALE` digits after the decimal point, such
+/// as an amount of tokens.
+///
+/// The value is stored as a `u128` holding the number multiplied by `10^SCALE`; for a token, the
+/// number of its smallest unit. `SCALE` must be at most 38, such that `10^SCALE` fits in a `u128`.
+///
+/// ## Arithmetic
+///
+/// Addition and subtraction are exact; the operators `+` and `-` panic on overflow, while the
+/// `checked_` methods return `None`. Multiplication and division take a [`Rounding`] mode, and
+/// compute the exact result before rounding it, such that intermediate results never overflow.
+///
+/// ```
+/// # use pbc_contract_common::{Decimal, Rounding};
+/// type Amount = Decimal<6>;
+/// let price: Amount = "2.5".parse().unwrap();
+/// let quantity: Amount = "0.333333".parse().unwrap();
+/// let cost = price.checked_mul(quantity, Rounding::Up).unwrap();
+/// assert_eq!(cost.to_string(), "0.833333");
+/// // Charge a fee of 0.3%.
+/// let fee = cost.checked_mul_div(3, 1000, Rounding::Up).unwrap();
+/// assert_eq!(fee, Amount::from_raw(2500));
+/// ```
+///
+/// ## Serialization
+///
+/// Both the State and the RPC format hold the `u128` value. The ABI describes it as a struct
+/// named after the scale, such as `Decimal<18>`, with the single field `raw: u128`, such that
+/// clients know the scale of the value.
+///
+/// Like other generic types, each scale used in a contract must be made visible to the ABI
+/// generator:
+///
+/// ```ignore
+/// use create_type_spec_derive::create_type_spec_for_generic;
+/// use pbc_contract_common::{Decimal, __abi_for_type_decimal};
+///
+/// create_type_spec_for_generic! { Decimal<18> }
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Decimal<const SCALE: u32> {
+    raw: u128,
+}
+
+/// How to round the result of an operation on [`Decimal`]s that it cannot hold exactly.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rounding {
+    /// Rounds towards zero, discarding the digits that do not fit.
+    Down,
+    /// Rounds away from zero.
+    Up,
+    /// Rounds to the nearest value, and away from zero when halfway between two values.
+    HalfUp,
+    /// Rounds to the nearest value, and to the even value when halfway between two values.
+    HalfEven,
+}
+
+impl Rounding {
+    /// Rounds the quotient of a division by a non-zero divisor, given the remainder.
+    fn round(self, quotient: u128, remainder: u128, divisor: u128) -> Option<u128> {
+        let round_up = match self {
+            Rounding::Down => false,
+            Rounding::Up => remainder != 0,
+            Rounding::HalfUp => remainder >= divisor - remainder,
+            Rounding::HalfEven => match remainder.cmp(&(divisor - remainder)) {
+                Ordering::Less => false,
+                Ordering::Equal => quotient % 2 == 1,
+                Ordering::Greater => true,
+            },
+        };
+        quotient.checked_add(u128::from(round_up))
+    }
+}
+
+impl<const SCALE: u32> Decimal<SCALE> {
+    /// `10^SCALE`, the raw value of one.
+    const UNIT: u128 = 10u128.pow(SCALE);
+
+    /// The value zero.
+    pub const ZERO: Self = Self { raw: 0 };
+
+    /// The value one.
+    pub const ONE: Self = Self { raw: Self::UNIT };
+
+    /// The largest value, with the raw value `u128::MAX`.
+    pub const MAX: Self = Self { raw: u128::MAX };
+
+    /// Creates a decimal from its raw value; the number multiplied by `10^SCALE`.
+    pub const fn from_raw(raw: u128) -> Self {
+        Self { raw }
+    }
+
+    /// The raw value; the number multiplied by `10^SCALE`.
+    pub const fn raw(self) -> u128 {
+        self.raw
+    }
+
+    /// Creates a decimal from an integer, or `None` if it is larger than [`Self::MAX`].
+    pub fn from_integer(value: u128) -> Option<Self> {
+        value.checked_mul(Self::UNIT).map(Self::from_raw)
+    }
+
+    /// The integer closest to the value in the direction given by `rounding`.
+    pub fn to_integer(self, rounding: Rounding) -> u128 {
+        // Rounding up cannot overflow, as the quotient is at most u128::MAX / 10.
+        let quotient = self.raw / Self::UNIT;
+        rounding
+            .round(quotient, self.raw % Self::UNIT, Self::UNIT)
+            .unwrap_or(quotient)
+    }
+
+    /// Whether the value is zero.
+    pub fn is_zero(self) -> bool {
+        self.raw == 0
+    }
+
+    /// Checked addition. Returns `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.raw.checked_add(other.raw).map(Self::from_raw)
+    }
+
+    /// Checked subtraction. Returns `None` if `other` is larger than `self`.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.raw.checked_sub(other.raw).map(Self::from_raw)
+    }
+
+    /// Saturating addition, returning [`Self::MAX`] on overflow.
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self::from_raw(self.raw.saturating_add(other.raw))
+    }
+
+    /// Saturating subtraction, returning zero if `other` is larger than `self`.
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self::from_raw(self.raw.saturating_sub(other.raw))
+    }
+
+    /// Multiplies two decimals, rounding the product to `SCALE` digits. Returns `None` if the
+    /// rounded product is larger than [`Self::MAX`].
+    pub fn checked_mul(self, other: Self, rounding: Rounding) -> Option<Self> {
+        mul_div(self.raw, other.raw, Self::UNIT, rounding).map(Self::from_raw)
+    }
+
+    /// Divides two decimals, rounding the quotient to `SCALE` digits. Returns `None` if `other`
+    /// is zero, or if the rounded quotient is larger than [`Self::MAX`].
+    pub fn checked_div(self, other: Self, rounding: Rounding) -> Option<Self> {
+        mul_div(self.raw, Self::UNIT, other.raw, rounding).map(Self::from_raw)
+    }
+
+    /// Computes `self * numerator / denominator`, rounding only the final result; for instance
+    /// to take a fraction of an amount, or to apply an exchange rate given as a ratio of
+    /// amounts. Returns `None` if `denominator` is zero, or if the rounded result is larger
+    /// than [`Self::MAX`].
+    pub fn checked_mul_div(
+        self,
+        numerator: u128,
+        denominator: u128,
+        rounding: Rounding,
+    ) -> Option<Self> {
+        mul_div(self.raw, numerator, denominator, rounding).map(Self::from_raw)
+    }
+
+    /// Converts to a decimal with a different number of digits after the decimal point,
+    /// rounding if there are fewer. Returns `None` if the value is larger than the maximum of
+    /// the new scale.
+    pub fn rescale<const NEW_SCALE: u32>(self, rounding: Rounding) -> Option<Decimal<NEW_SCALE>> {
+        let raw = if NEW_SCALE >= SCALE {
+            self.raw
+                .checked_mul(10u128.checked_pow(NEW_SCALE - SCALE)?)?
+        } else {
+            let divisor = 10u128.pow(SCALE - NEW_SCALE);
+            rounding.round(self.raw / divisor, self.raw % divisor, divisor)?
+        };
+        Some(Decimal::from_raw(raw))
+    }
+}
+
+/// `a * b / divisor`, rounded, without overflowing in the intermediate product.
+fn mul_div(a: u128, b: u128, divisor: u128, rounding: Rounding) -> Option<u128> {
+    if divisor == 0 {
+        return None;
+    }
+    let (quotient, remainder) = (U256::from(a) * U256::from(b)).div_rem(U256::from(divisor));
+    let quotient = u128::try_from(quotient).ok()?;
+    // The remainder is less than the divisor, which is a u128.
+    let remainder = u128::try_from(remainder).unwrap();
+    rounding.round(quotient, remainder, divisor)
+}
+
+impl<const SCALE: u32> Add for Decimal<SCALE> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other)
+            .expect("attempt to add with overflow")
+    }
+}
+
+impl<const SCALE: u32> AddAssign for Decimal<SCALE> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const SCALE: u32> Sub for Decimal<SCALE> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl<const SCALE: u32> SubAssign for Decimal<SCALE> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+/// Writes all `SCALE` digits after the decimal point, and no decimal point if `SCALE` is zero.
+impl<const SCALE: u32> fmt::Display for Decimal<SCALE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let integer = self.raw / Self::UNIT;
+        let digits = if SCALE == 0 {
+            integer.to_string()
+        } else {
+            let fraction = self.raw % Self::UNIT;
+            format!("{integer}.{fraction:0width$}", width = SCALE as usize)
+        };
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+/// Parses decimal digits with an optional decimal point followed by at most `SCALE` digits, such
+/// as `12`, `12.5` or `0.125`.
+impl<const SCALE: u32> FromStr for Decimal<SCALE> {
+    type Err = ParseDecimalError;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let (integer, fraction) = match src.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (src, None),
+        };
+        if integer.is_empty() && fraction.is_none() {
+            return Err(ParseDecimalError::Empty);
+        }
+        let fraction = fraction.unwrap_or("");
+        let all_digits = |digits: &str| digits.bytes().all(|byte| byte.is_ascii_digit());
+        if integer.is_empty() || !all_digits(integer) || !all_digits(fraction) {
+            return Err(ParseDecimalError::InvalidDigit);
+        }
+        if src.ends_with('.') {
+            return Err(ParseDecimalError::InvalidDigit);
+        }
+        if fraction.len() > SCALE as usize {
+            return Err(ParseDecimalError::TooManyDecimals);
+        }
+
+        let mut raw: u128 = 0;
+        let padding = std::iter::repeat_n(b'0', SCALE as usize - fraction.len());
+        for digit in integer.bytes().chain(fraction.bytes()).chain(padding) {
+            raw = raw
+                .checked_mul(10)
+                .and_then(|raw| raw.checked_add(u128::from(digit - b'0')))
+                .ok_or(ParseDecimalError::Overflow)?;
+        }
+        Ok(Self::from_raw(raw))
+    }
+}
+
+/// Error produced when parsing a [`Decimal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseDecimalError {
+    /// The string contained no digits.
+    Empty,
+    /// The string contained a character that is not a digit or a single decimal point between
+    /// digits.
+    InvalidDigit,
+    /// The string had more digits after the decimal point than the scale of the decimal.
+    TooManyDecimals,
+    /// The value is larger than [`Decimal::MAX`].
+    Overflow,
+}
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "cannot parse decimal from empty string"),
+            Self::InvalidDigit => write!(f, "invalid digit found in string"),
+            Self::TooManyDecimals => write!(f, "too many digits after the decimal point"),
+            Self::Overflow => write!(f, "number too large to fit in decimal"),
+        }
+    }
+}
+
+impl std::error::Error for ParseDecimalError {}
+
+impl<const SCALE: u32> ReadWriteState for Decimal<SCALE> {
+    // The representation is that of the raw u128.
+    const SERIALIZABLE_BY_COPY: bool = u128::SERIALIZABLE_BY_COPY;
+
+    fn state_read_from<T: PbcRead>(reader: &mut T) -> Self {
+        Self::from_raw(u128::state_read_from(reader))
+    }
+
+    fn try_state_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        u128::try_state_read_from(reader).map(Self::from_raw)
+    }
+
+    fn state_write_to<T: PbcWrite>(&self, writer: &mut T) -> pbc_traits::io::Result<()> {
+        self.raw.state_write_to(writer)
+    }
+}
+
+impl<const SCALE: u32> ReadRPC for Decimal<SCALE> {
+    fn rpc_read_from<T: PbcRead>(reader: &mut T) -> Self {
+        Self::from_raw(u128::rpc_read_from(reader))
+    }
+
+    fn try_rpc_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        u128::try_rpc_read_from(reader).map(Self::from_raw)
+    }
+}
+
+impl<const SCALE: u32> WriteRPC for Decimal<SCALE> {
+    fn rpc_write_to<T: PbcWrite>(&self, writer: &mut T) -> pbc_traits::io::Result<()> {
+        self.raw.rpc_write_to(writer)
+    }
+}
+
+#[cfg(feature = "abi")]
+impl<const SCALE: u32> CreateTypeSpec for Decimal<SCALE> {
+    fn __ty_name() -> String {
+        format!("Decimal<{SCALE}>")
+    }
+
+    fn __ty_identifier() -> String {
+        Self::__ty_name()
+    }
+
+    fn __ty_spec_write(w: &mut Vec<u8>, lut: &BTreeMap<String, u8>) {
+        w.push(0x00);
+        w.push(*lut.get(&Self::__ty_identifier()).unwrap_or(&0xFF));
+    }
+}
+
+/// PBC ABI gen internal method. Ensures that [`Decimal`] of the given scale is visible to the ABI
+/// generator, when registered by `create_type_spec_for_generic!`.
+#[cfg(feature = "abi")]
+pub fn __abi_for_type_decimal<const SCALE: u32>(lut: &BTreeMap<String, u8>) -> Vec<NamedTypeSpec> {
+    let mut type_spec = vec![];
+    Decimal::<SCALE>::__ty_spec_write(&mut type_spec, lut);
+    let mut type_abi = NamedTypeSpec::new_struct(
+        Decimal::<SCALE>::__ty_name(),
+        Decimal::<SCALE>::__ty_identifier(),
+        type_spec,
+    );
+    type_abi.doc = Some(format!(
+        "A decimal number with {SCALE} digits after the decimal point, stored in `raw` \
+        multiplied by 10^{SCALE}."
+    ));
+    type_abi.add_field(NamedEntityAbi::new::<u128>("raw".to_string(), lut));
+    vec![type_abi]
+}
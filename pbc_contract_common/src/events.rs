@@ -199,7 +199,7 @@ impl EventGroupBuilder {
     ///
     /// [`InteractionBuilder::done`] must be called on the produced [`InteractionBuilder`] for it
     /// to be added to this [`EventGroupBuilder`].
-    pub fn call(&mut self, dest: Address, shortname: Shortname) -> InteractionBuilder {
+    pub fn call(&mut self, dest: Address, shortname: Shortname) -> InteractionBuilder<'_> {
         InteractionBuilder {
             dest,
             payload: shortname.bytes(),
@@ -233,7 +233,7 @@ impl EventGroupBuilder {
     /// [`CallbackContext`](crate::context::CallbackContext).
     ///
     /// Note: Incompatible with [`return_data`](Self::return_data).
-    pub fn with_callback(&mut self, shortname: ShortnameCallback) -> CallbackBuilder {
+    pub fn with_callback(&mut self, shortname: ShortnameCallback) -> CallbackBuilder<'_> {
         CallbackBuilder {
             payload: shortname.shortname.bytes(),
             cost: None,
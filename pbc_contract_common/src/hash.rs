@@ -0,0 +1,158 @@
+#[cfg(feature = "abi")]
+use crate::type_spec_default_impl;
+
+#[cfg(feature = "abi")]
+use pbc_traits::CreateTypeSpec;
+use pbc_traits::{PbcWrite, ReadWriteState, WriteRPC};
+use read_write_rpc_derive::ReadWriteRPC;
+use read_write_state_derive::ReadWriteState;
+use sha2::{Digest, Sha256};
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A hash is the result of a hashing process, yielding a unique identifier for the hashed artifact.
+///
+/// This identifier always consists of 32 bytes.
+///
+/// Values can be hashed with SHA-256 through their serialization, using [`Hash::of_state`] or
+/// [`Hash::of_rpc`]. As the serialization formats are canonical, equal values always produce
+/// equal hashes, making these suitable for commit-reveal schemes and deduplication.
+///
+/// ```
+/// # use pbc_contract_common::Hash;
+/// let commitment = Hash::of_state(&(42u64, "salt".to_string()));
+/// assert_eq!(commitment, Hash::of_state(&(42u64, "salt".to_string())));
+/// assert_eq!(commitment.to_string().parse::<Hash>(), Ok(commitment));
+/// ```
+#[derive(Eq, PartialEq, Debug, Clone, PartialOrd, Ord, ReadWriteState, ReadWriteRPC)]
+pub struct Hash {
+    /// The bytes of the hash.
+    pub bytes: [u8; 32],
+}
+
+#[cfg(feature = "abi")]
+impl CreateTypeSpec for Hash {
+    type_spec_default_impl!("Hash", 0x13);
+}
+
+/// Writer feeding serialized values to a hasher, without buffering them.
+struct HashWriter(Sha256);
+
+impl PbcWrite for HashWriter {
+    fn write_bytes(&mut self, buf: &[u8]) -> pbc_traits::io::Result<()> {
+        self.0.update(buf);
+        Ok(())
+    }
+}
+
+/// Number of hexadecimal characters in the string form of a [`Hash`].
+const HASH_HEX_LENGTH: usize = 64;
+
+impl Hash {
+    /// SHA-256 hash of the State serialization of the value.
+    pub fn of_state<T: ReadWriteState>(value: &T) -> Self {
+        let mut writer = HashWriter(Sha256::new());
+        value
+            .state_write_to(&mut writer)
+            .expect("Hashing never fails");
+        Self::from_hasher(writer.0)
+    }
+
+    /// SHA-256 hash of the RPC serialization of the value.
+    pub fn of_rpc<T: WriteRPC>(value: &T) -> Self {
+        let mut writer = HashWriter(Sha256::new());
+        value
+            .rpc_write_to(&mut writer)
+            .expect("Hashing never fails");
+        Self::from_hasher(writer.0)
+    }
+
+    /// SHA-256 hash of the given bytes.
+    pub fn of_bytes(bytes: &[u8]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(bytes);
+        Self::from_hasher(hasher)
+    }
+
+    fn from_hasher(hasher: Sha256) -> Self {
+        Self {
+            bytes: hasher.finalize().into(),
+        }
+    }
+}
+
+impl fmt::LowerHex for Hash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in self.bytes {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::UpperHex for Hash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in self.bytes {
+            write!(f, "{:02X}", b)?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the hash as 64 lowercase hexadecimal characters.
+impl fmt::Display for Hash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+/// Parses the 64 character hexadecimal form of a hash, in either case.
+impl FromStr for Hash {
+    type Err = ParseHashError;
+
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        if hex.len() != HASH_HEX_LENGTH {
+            return Err(ParseHashError::InvalidLength { length: hex.len() });
+        }
+        let mut bytes = [0u8; 32];
+        for (index, character) in hex.char_indices() {
+            let nibble = character
+                .to_digit(16)
+                .ok_or(ParseHashError::InvalidHexCharacter { index })?;
+            bytes[index / 2] = (bytes[index / 2] << 4) | nibble as u8;
+        }
+        Ok(Self { bytes })
+    }
+}
+
+/// Error produced when parsing a [`Hash`] from a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseHashError {
+    /// The string does not consist of exactly 64 characters.
+    InvalidLength {
+        /// Length of the string in bytes.
+        length: usize,
+    },
+    /// The string contains a character that is not a hexadecimal digit.
+    InvalidHexCharacter {
+        /// Byte index of the character.
+        index: usize,
+    },
+}
+
+impl fmt::Display for ParseHashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength { length } => write!(
+                f,
+                "Hash must consist of {HASH_HEX_LENGTH} hexadecimal characters, but was {length} long"
+            ),
+            Self::InvalidHexCharacter { index } => {
+                write!(f, "Invalid hexadecimal character at index {index}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseHashError {}
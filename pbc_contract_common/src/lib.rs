@@ -3,20 +3,29 @@
 //! Defines common types and methods used in PBC smart contracts.
 extern crate quote;
 
-//// Internal modules to be reexported
+// Internal modules to be reexported
 
 mod address_internal;
+#[cfg(feature = "bls")]
+mod bls;
+mod decimal;
+mod hash;
+mod limbs;
 mod result_buffer;
+#[cfg(feature = "secp256k1")]
+mod secp256k1;
+mod u256;
 
 #[cfg(feature = "abi")]
 mod raw_ptr;
 
-//// Directly exported modules
+// Directly exported modules
 
 pub use function_name::{FunctionKind, FunctionName};
-use std::io::{Read, Write};
 pub mod context;
 pub mod events;
+pub mod merkle;
+pub mod random;
 pub mod signature;
 
 pub mod sorted_vec_map;
@@ -25,7 +34,7 @@ pub mod avl_tree_map;
 
 pub mod zk;
 
-//// Reexports
+// Reexports
 
 #[cfg(feature = "abi")]
 pub use raw_ptr::RawPtr;
@@ -35,20 +44,26 @@ pub use pbc_contract_core::abi;
 pub use pbc_contract_core::{function_name, shortname};
 #[cfg(feature = "abi")]
 use pbc_traits::CreateTypeSpec;
-use pbc_traits::{ReadRPC, WriteRPC};
 use read_write_rpc_derive::ReadWriteRPC;
 use read_write_state_derive::ReadWriteState;
 
 /// Definitions for blockchain [`Address`](address_internal::Address)es.
 pub mod address {
-    pub use super::address_internal::{Address, AddressType};
+    pub use super::address_internal::{
+        Address, AddressType, InvalidAddressTypeError, ParseAddressError,
+    };
     pub use super::shortname::{Shortname, ShortnameCallback};
 }
 
 #[cfg(any(test, doc, feature = "test_examples"))]
 pub mod test_examples;
 
+#[cfg(feature = "abi")]
+pub use decimal::__abi_for_type_decimal;
+pub use decimal::{Decimal, ParseDecimalError, Rounding};
+pub use hash::{Hash, ParseHashError};
 pub use result_buffer::ContractResultBuffer;
+pub use u256::{NumericU256, ParseU256Error, TryFromU256Error, U256};
 
 /// Creates the code for implementing the CreateTypeSpec trait for built-in types.
 ///
@@ -80,21 +95,7 @@ macro_rules! type_spec_default_impl {
     };
 }
 
-//// Some actual functionality
-
-/// A hash is the result of a hashing process, yielding a unique identifier for the hashed artifact.
-///
-/// This identifier always consists of 32 bytes.
-#[derive(Eq, PartialEq, Debug, Clone, PartialOrd, Ord, ReadWriteState, ReadWriteRPC)]
-pub struct Hash {
-    /// The bytes of the hash.
-    pub bytes: [u8; 32],
-}
-
-#[cfg(feature = "abi")]
-impl CreateTypeSpec for Hash {
-    type_spec_default_impl!("Hash", 0x13);
-}
+// Some actual functionality
 
 /// A public key is used to send encrypted transactions on the blockchain.
 /// Transactions must be encrypted under a public key registered on the blockchain otherwise they will fail.
@@ -111,6 +112,28 @@ impl CreateTypeSpec for PublicKey {
     type_spec_default_impl!("PublicKey", 0x14);
 }
 
+#[cfg(feature = "secp256k1")]
+impl PublicKey {
+    /// The [`Address`](address::Address) of the account with this public key.
+    ///
+    /// Account addresses consist of [`AddressType::Account`](address::AddressType::Account)
+    /// followed by the last 20 bytes of the SHA-256 hash of the 65 byte uncompressed encoding of
+    /// the public key.
+    ///
+    /// Requires the `secp256k1` feature.
+    ///
+    /// Returns `None` if the bytes are not a compressed secp256k1 public key, which can be the
+    /// case for keys from sources such as arguments of actions, but never for keys recovered from
+    /// a [`Signature`](signature::Signature).
+    pub fn to_address(&self) -> Option<address::Address> {
+        let uncompressed = secp256k1::decompress_public_key(&self.bytes)?;
+        let hash = Hash::of_bytes(&uncompressed);
+        Some(address::Address::account(
+            hash.bytes[12..].try_into().unwrap(),
+        ))
+    }
+}
+
 /// A BLS (Boneh-Lynn-Shacham) is a different type of [public key](PublicKey), that allows for aggregation.
 ///
 /// It is used to authenticate aggregated [BLS signatures](BlsSignature), by aggregating the public keys that was used for signing, into a 'single' key.
@@ -127,6 +150,20 @@ impl CreateTypeSpec for BlsPublicKey {
     type_spec_default_impl!("BlsPublicKey", 0x16);
 }
 
+#[cfg(feature = "bls")]
+impl BlsPublicKey {
+    /// Aggregates public keys into a single key, which verifies the
+    /// [aggregate](BlsSignature::aggregate) of their signatures of a message.
+    ///
+    /// Requires the `bls` feature.
+    ///
+    /// Returns a [`BlsError`] if there are no keys, or if one of them is not a valid public key.
+    pub fn aggregate(public_keys: &[BlsPublicKey]) -> Result<BlsPublicKey, BlsError> {
+        let bytes = bls::aggregate_public_keys(public_keys.iter().map(|key| &key.bytes))?;
+        Ok(BlsPublicKey { bytes })
+    }
+}
+
 /// A BLS (Boneh-Lynn-Shacham) is a different type of [signature](signature::Signature), that allows for aggregation.
 ///
 /// It is used to produce a joint (single) signature on e.g. a block between a group of users, rather than one signature per user as is the case for [signature](signature::Signature).
@@ -143,37 +180,110 @@ impl CreateTypeSpec for BlsSignature {
     type_spec_default_impl!("BlsSignature", 0x17);
 }
 
-/// A u256 is a 256-bit unsigned integer.
-///
-/// It consists of a 32 byte array.
-#[derive(Eq, PartialEq, Debug, Clone, PartialOrd, Ord, ReadWriteState)]
-pub struct U256 {
-    /// The bytes of the u256.
-    pub bytes: [u8; 32],
-}
+#[cfg(feature = "bls")]
+impl BlsSignature {
+    /// Verifies that this is a signature of the given message by the given public key.
+    ///
+    /// Signatures are points of G1 and public keys points of G2 of the BLS12-381 curve, in their
+    /// compressed encodings. The message is hashed to G1 by the suite
+    /// `BLS12381G1_XMD:SHA-256_SSWU_RO_` of [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380),
+    /// under the domain separation tag `BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_`.
+    /// Malformed signatures and keys, and the point at infinity, never verify.
+    ///
+    /// Requires the `bls` feature.
+    pub fn verify(&self, message: &[u8], public_key: &BlsPublicKey) -> bool {
+        bls::verify(&public_key.bytes, message, &self.bytes)
+    }
 
-#[cfg(feature = "abi")]
-impl CreateTypeSpec for U256 {
-    type_spec_default_impl!("U256", 0x18);
-}
+    /// Verifies that this is the [aggregate](Self::aggregate) of signatures of the given message
+    /// by every one of the given public keys.
+    ///
+    /// This allows checking a certificate signed by a committee using a single pairing check:
+    ///
+    /// ```
+    /// # use pbc_contract_common::{BlsPublicKey, BlsSignature, Hash};
+    /// # use std::collections::BTreeSet;
+    /// fn is_certified(
+    ///     block: &Hash,
+    ///     certificate: &BlsSignature,
+    ///     committee: &[BlsPublicKey],
+    ///     signers: &[usize],
+    ///     threshold: usize,
+    /// ) -> bool {
+    ///     // Every member counts once towards the threshold, however often it is listed.
+    ///     let signers: BTreeSet<usize> = signers.iter().copied().collect();
+    ///     let keys: Option<Vec<BlsPublicKey>> =
+    ///         signers.iter().map(|&i| committee.get(i).cloned()).collect();
+    ///     match keys {
+    ///         Some(keys) => {
+    ///             keys.len() >= threshold && certificate.verify_aggregate(&block.bytes, &keys)
+    ///         }
+    ///         None => false,
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// The keys must be known to be held by their signers, for instance by requiring proof of
+    /// possession when they are registered, as a key chosen as the negation of other keys could
+    /// otherwise cancel them out. The signers must also be distinct.
+    ///
+    /// Requires the `bls` feature.
+    pub fn verify_aggregate(&self, message: &[u8], public_keys: &[BlsPublicKey]) -> bool {
+        bls::verify_aggregate(
+            public_keys.iter().map(|key| &key.bytes),
+            message,
+            &self.bytes,
+        )
+    }
 
-impl ReadRPC for U256 {
-    fn rpc_read_from<T: Read>(reader: &mut T) -> Self {
-        let mut bytes: [u8; 32] = [0; 32];
-        reader.read_exact(&mut bytes).unwrap();
-        bytes.reverse();
-        Self { bytes }
+    /// Aggregates signatures into a single signature, which is verified by the
+    /// [aggregate](BlsPublicKey::aggregate) of the public keys.
+    ///
+    /// Requires the `bls` feature.
+    ///
+    /// Returns a [`BlsError`] if there are no signatures, or if one of them is malformed.
+    pub fn aggregate(signatures: &[BlsSignature]) -> Result<BlsSignature, BlsError> {
+        let bytes = bls::aggregate_signatures(signatures.iter().map(|signature| &signature.bytes))?;
+        Ok(BlsSignature { bytes })
     }
 }
 
-impl WriteRPC for U256 {
-    fn rpc_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
-        let mut bytes: [u8; 32] = self.bytes;
-        bytes.reverse();
-        writer.write_all(&bytes)
+/// Error produced when aggregating [`BlsPublicKey`]s or [`BlsSignature`]s.
+#[cfg(feature = "bls")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlsError {
+    /// There was nothing to aggregate.
+    NothingToAggregate,
+    /// A public key is not the encoding of a point of G2 other than the point at infinity.
+    InvalidPublicKey {
+        /// The index of the invalid public key.
+        index: usize,
+    },
+    /// A signature is not the encoding of a point of G1.
+    InvalidSignature {
+        /// The index of the invalid signature.
+        index: usize,
+    },
+}
+
+#[cfg(feature = "bls")]
+impl std::fmt::Display for BlsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NothingToAggregate => write!(f, "Nothing to aggregate"),
+            Self::InvalidPublicKey { index } => {
+                write!(f, "BLS public key at index {index} is invalid")
+            }
+            Self::InvalidSignature { index } => {
+                write!(f, "BLS signature at index {index} is invalid")
+            }
+        }
     }
 }
 
+#[cfg(feature = "bls")]
+impl std::error::Error for BlsError {}
+
 /// The shortname for the init method of a contract.
 const FN_INIT_SHORTNAME: u32 = 0xFFFFFFFF;
 
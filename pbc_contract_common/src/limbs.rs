@@ -0,0 +1,98 @@
+//! Arithmetic on unsigned integers of a fixed size, stored as 64-bit limbs, least significant
+//! first.
+//!
+//! Used by [`U256`](crate::U256), which works on integers of 4 limbs.
+
+use std::cmp::Ordering;
+
+/// Addition, returning the wrapped result and whether it overflowed.
+pub(crate) fn overflowing_add<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], bool) {
+    let mut result = [0; N];
+    let mut carry = false;
+    for index in 0..N {
+        let (sum, carry_1) = a[index].overflowing_add(b[index]);
+        let (sum, carry_2) = sum.overflowing_add(u64::from(carry));
+        result[index] = sum;
+        carry = carry_1 || carry_2;
+    }
+    (result, carry)
+}
+
+/// Subtraction, returning the wrapped result and whether it overflowed.
+pub(crate) fn overflowing_sub<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], bool) {
+    let mut result = [0; N];
+    let mut borrow = false;
+    for index in 0..N {
+        let (difference, borrow_1) = a[index].overflowing_sub(b[index]);
+        let (difference, borrow_2) = difference.overflowing_sub(u64::from(borrow));
+        result[index] = difference;
+        borrow = borrow_1 || borrow_2;
+    }
+    (result, borrow)
+}
+
+/// The full product of `a` and `b`, written to `result` of `a.len() + b.len()` limbs.
+pub(crate) fn mul_wide(a: &[u64], b: &[u64], result: &mut [u64]) {
+    assert_eq!(result.len(), a.len() + b.len());
+    result.fill(0);
+    for (i, &a_limb) in a.iter().enumerate() {
+        let mut carry: u64 = 0;
+        for (j, &b_limb) in b.iter().enumerate() {
+            let product = u128::from(a_limb) * u128::from(b_limb)
+                + u128::from(result[i + j])
+                + u128::from(carry);
+            result[i + j] = product as u64;
+            carry = (product >> 64) as u64;
+        }
+        result[i + b.len()] = carry;
+    }
+}
+
+/// Shifts left by less than `64 * N` bits, discarding the bits shifted out.
+pub(crate) fn shl<const N: usize>(value: &[u64; N], shift: u32) -> [u64; N] {
+    let (limb_shift, bit_shift) = ((shift / 64) as usize, shift % 64);
+    let mut result = [0; N];
+    for index in limb_shift..N {
+        result[index] = value[index - limb_shift] << bit_shift;
+        if bit_shift > 0 && index > limb_shift {
+            result[index] |= value[index - limb_shift - 1] >> (64 - bit_shift);
+        }
+    }
+    result
+}
+
+/// Shifts right by less than `64 * N` bits.
+pub(crate) fn shr<const N: usize>(value: &[u64; N], shift: u32) -> [u64; N] {
+    let (limb_shift, bit_shift) = ((shift / 64) as usize, shift % 64);
+    let mut result = [0; N];
+    for index in 0..N - limb_shift {
+        result[index] = value[index + limb_shift] >> bit_shift;
+        if bit_shift > 0 && index + limb_shift + 1 < N {
+            result[index] |= value[index + limb_shift + 1] << (64 - bit_shift);
+        }
+    }
+    result
+}
+
+/// Numeric comparison.
+pub(crate) fn cmp<const N: usize>(a: &[u64; N], b: &[u64; N]) -> Ordering {
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+/// Reads the limbs from `8 * N` little endian bytes.
+pub(crate) fn from_le_bytes<const N: usize>(bytes: &[u8]) -> [u64; N] {
+    assert_eq!(bytes.len(), 8 * N);
+    let mut limbs = [0; N];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    limbs
+}
+
+/// Writes the limbs to `8 * N` little endian bytes.
+pub(crate) fn to_le_bytes<const N: usize>(limbs: &[u64; N], bytes: &mut [u8]) {
+    assert_eq!(bytes.len(), 8 * N);
+    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(limbs) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+}
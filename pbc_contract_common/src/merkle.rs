@@ -0,0 +1,332 @@
+//! Merkle trees over [`Hash`]es, and proofs that leaves belong to a tree with a given root.
+//!
+//! A contract stores only the root of a tree, such as an airdrop allowlist, and users prove that
+//! their leaf is in it by passing a [`MerkleProof`] as an action argument. Trees are built
+//! off-chain with [`MerkleTree`], using the same [`MerkleScheme`] as the verifying contract.
+//!
+//! Each level of a tree hashes pairs of adjacent nodes of the level below, and a node without a
+//! neighbour is moved up unchanged. With [`LeafHashing::DomainSeparated`] and
+//! [`PairOrder::Positional`] this gives the tree hashes of RFC 6962.
+//!
+//! ```
+//! # use pbc_contract_common::address::Address;
+//! # use pbc_contract_common::merkle::{LeafHashing, MerkleScheme, MerkleTree, PairOrder};
+//! const SCHEME: MerkleScheme = MerkleScheme::new(LeafHashing::DoubleSha256, PairOrder::Sorted);
+//!
+//! // Off-chain, the allowlist is built into a tree, and its root is given to the contract.
+//! let allowlist: Vec<(Address, u128)> = (1..=5u8)
+//!     .map(|n| (Address::account([n; 20]), u128::from(n) * 100))
+//!     .collect();
+//! let tree = MerkleTree::from_values(SCHEME, &allowlist);
+//! let root = tree.root();
+//!
+//! // On-chain, a claim is checked against the root.
+//! let proof = tree.proof(2).unwrap();
+//! let claim = (Address::account([3; 20]), 300u128);
+//! assert!(proof.verify(&SCHEME, &root, &SCHEME.hash_leaf_rpc(&claim)));
+//! ```
+
+use create_type_spec_derive::CreateTypeSpecInternal;
+use pbc_traits::WriteRPC;
+use read_write_rpc_derive::{ReadRPC, WriteRPC};
+use read_write_state_derive::ReadWriteState;
+use sha2::{Digest, Sha256};
+
+use crate::Hash;
+
+/// How leaf values, and the children of inner nodes, are hashed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeafHashing {
+    /// Leaves are the SHA-256 hash of the value, and inner nodes the SHA-256 hash of their
+    /// children.
+    ///
+    /// An inner node is then a valid leaf for a value of 64 bytes; only use this scheme if
+    /// values of that size cannot occur, or if the leaves are not trusted to be values.
+    Sha256,
+    /// Leaves are the SHA-256 hash of the SHA-256 hash of the value, and inner nodes the SHA-256
+    /// hash of their children. Inner nodes can thereby not be passed off as leaves.
+    DoubleSha256,
+    /// Leaves are the SHA-256 hash of `0x00` followed by the value, and inner nodes the SHA-256
+    /// hash of `0x01` followed by their children, as in RFC 6962. Inner nodes can thereby not be
+    /// passed off as leaves.
+    DomainSeparated,
+}
+
+/// Order in which the children of inner nodes are hashed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairOrder {
+    /// The left child is hashed first, such that the root commits to the order of the leaves.
+    Positional,
+    /// The smallest child is hashed first, such that verification does not depend on whether
+    /// each node is a left or a right child.
+    Sorted,
+}
+
+/// The hashing of a Merkle tree, which the builder of a tree and the verifier of its proofs must
+/// agree on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MerkleScheme {
+    /// How leaves and inner nodes are hashed.
+    pub leaf_hashing: LeafHashing,
+    /// Order in which the children of inner nodes are hashed.
+    pub pair_order: PairOrder,
+}
+
+impl MerkleScheme {
+    /// Creates a scheme from its leaf hashing and pair order.
+    pub const fn new(leaf_hashing: LeafHashing, pair_order: PairOrder) -> Self {
+        Self {
+            leaf_hashing,
+            pair_order,
+        }
+    }
+
+    /// The leaf of the given bytes.
+    pub fn hash_leaf(&self, value: &[u8]) -> Hash {
+        match self.leaf_hashing {
+            LeafHashing::Sha256 => Hash::of_bytes(value),
+            LeafHashing::DoubleSha256 => Hash::of_bytes(&Hash::of_bytes(value).bytes),
+            LeafHashing::DomainSeparated => hash_parts(&[&[0x00], value]),
+        }
+    }
+
+    /// The leaf of the RPC serialization of the given value.
+    pub fn hash_leaf_rpc<T: WriteRPC>(&self, value: &T) -> Hash {
+        let mut bytes = Vec::new();
+        value
+            .rpc_write_to(&mut bytes)
+            .expect("Writing to a Vec never fails");
+        self.hash_leaf(&bytes)
+    }
+
+    /// The inner node with the given children.
+    pub fn hash_pair(&self, left: &Hash, right: &Hash) -> Hash {
+        let (left, right) = match self.pair_order {
+            PairOrder::Sorted if right < left => (right, left),
+            _ => (left, right),
+        };
+        match self.leaf_hashing {
+            LeafHashing::Sha256 | LeafHashing::DoubleSha256 => {
+                hash_parts(&[&left.bytes, &right.bytes])
+            }
+            LeafHashing::DomainSeparated => hash_parts(&[&[0x01], &left.bytes, &right.bytes]),
+        }
+    }
+}
+
+fn hash_parts(parts: &[&[u8]]) -> Hash {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    Hash {
+        bytes: hasher.finalize().into(),
+    }
+}
+
+/// Proof that a leaf is in a Merkle tree.
+///
+/// Holds the siblings of the nodes on the path from the leaf to the root, leaving out levels
+/// where the node has no sibling.
+#[derive(
+    Eq, PartialEq, Debug, Clone, ReadRPC, WriteRPC, ReadWriteState, CreateTypeSpecInternal,
+)]
+pub struct MerkleProof {
+    /// Position of the leaf among the leaves of the tree.
+    pub leaf_index: u64,
+    /// Number of leaves in the tree.
+    pub leaf_count: u64,
+    /// Siblings on the path from the leaf to the root.
+    pub siblings: Vec<Hash>,
+}
+
+impl MerkleProof {
+    /// Whether `leaf` is at [`leaf_index`](Self::leaf_index) in the tree with the given root.
+    ///
+    /// With [`PairOrder::Sorted`] the position of the leaf is only used to determine the levels
+    /// where the node has no sibling, and the proof shows that the leaf is in the tree.
+    pub fn verify(&self, scheme: &MerkleScheme, root: &Hash, leaf: &Hash) -> bool {
+        let leaves = [(self.leaf_index, leaf.clone())];
+        compute_root(scheme, self.leaf_count, &leaves, &self.siblings).as_ref() == Some(root)
+    }
+}
+
+/// Proof that several leaves are in a Merkle tree, sharing the nodes their paths to the root
+/// have in common.
+///
+/// Holds the siblings needed to compute the root, level by level from the leaves up, and from
+/// left to right within each level.
+#[derive(
+    Eq, PartialEq, Debug, Clone, ReadRPC, WriteRPC, ReadWriteState, CreateTypeSpecInternal,
+)]
+pub struct MerkleMultiProof {
+    /// Positions of the leaves among the leaves of the tree, in increasing order.
+    pub leaf_indices: Vec<u64>,
+    /// Number of leaves in the tree.
+    pub leaf_count: u64,
+    /// Siblings needed to compute the root, which are not themselves on a path to the root.
+    pub siblings: Vec<Hash>,
+}
+
+impl MerkleMultiProof {
+    /// Whether `leaves` are at [`leaf_indices`](Self::leaf_indices) in the tree with the given
+    /// root. The leaves must be given in the order of their indices.
+    pub fn verify(&self, scheme: &MerkleScheme, root: &Hash, leaves: &[Hash]) -> bool {
+        if leaves.len() != self.leaf_indices.len() {
+            return false;
+        }
+        let leaves: Vec<(u64, Hash)> = self
+            .leaf_indices
+            .iter()
+            .copied()
+            .zip(leaves.iter().cloned())
+            .collect();
+        compute_root(scheme, self.leaf_count, &leaves, &self.siblings).as_ref() == Some(root)
+    }
+}
+
+/// The root of a tree with `leaf_count` leaves, from the given leaves and their positions, and
+/// the siblings needed to compute it. Returns `None` if the positions are not increasing and
+/// within the tree, or if the siblings are not exactly the needed ones.
+fn compute_root(
+    scheme: &MerkleScheme,
+    leaf_count: u64,
+    leaves: &[(u64, Hash)],
+    siblings: &[Hash],
+) -> Option<Hash> {
+    let positions_valid = leaves.windows(2).all(|pair| pair[0].0 < pair[1].0)
+        && leaves.last().is_some_and(|(index, _)| *index < leaf_count);
+    if !positions_valid {
+        return None;
+    }
+
+    let mut siblings = siblings.iter();
+    let mut nodes = leaves.to_vec();
+    let mut count = leaf_count;
+    while count > 1 {
+        let mut parents = Vec::with_capacity(nodes.len());
+        let mut known = nodes.into_iter().peekable();
+        while let Some((index, node)) = known.next() {
+            let parent = if index % 2 == 1 {
+                scheme.hash_pair(siblings.next()?, &node)
+            } else if index + 1 == count {
+                node
+            } else if let Some((_, right)) = known.next_if(|(next, _)| *next == index + 1) {
+                scheme.hash_pair(&node, &right)
+            } else {
+                scheme.hash_pair(&node, siblings.next()?)
+            };
+            parents.push((index / 2, parent));
+        }
+        nodes = parents;
+        count = count.div_ceil(2);
+    }
+    if siblings.next().is_some() {
+        return None;
+    }
+    nodes.pop().map(|(_, root)| root)
+}
+
+/// A Merkle tree holding all of its nodes, for creating proofs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTree {
+    scheme: MerkleScheme,
+    /// The levels of the tree, from the leaves to the root.
+    levels: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    /// Builds the tree with the given leaves, as hashed by [`MerkleScheme::hash_leaf`].
+    ///
+    /// Panics if there are no leaves.
+    pub fn new(scheme: MerkleScheme, leaves: Vec<Hash>) -> Self {
+        assert!(
+            !leaves.is_empty(),
+            "A Merkle tree must have at least one leaf"
+        );
+        let mut levels = vec![leaves];
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let parents = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => scheme.hash_pair(left, right),
+                    [single] => single.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(parents);
+        }
+        Self { scheme, levels }
+    }
+
+    /// Builds the tree with the leaves of the RPC serializations of the given values.
+    ///
+    /// Panics if there are no values.
+    pub fn from_values<T: WriteRPC>(scheme: MerkleScheme, values: &[T]) -> Self {
+        let leaves = values
+            .iter()
+            .map(|value| scheme.hash_leaf_rpc(value))
+            .collect();
+        Self::new(scheme, leaves)
+    }
+
+    /// The scheme the tree is hashed with.
+    pub fn scheme(&self) -> &MerkleScheme {
+        &self.scheme
+    }
+
+    /// The root of the tree.
+    pub fn root(&self) -> Hash {
+        self.levels.last().unwrap()[0].clone()
+    }
+
+    /// The leaves of the tree.
+    pub fn leaves(&self) -> &[Hash] {
+        &self.levels[0]
+    }
+
+    /// Proof that the leaf at the given index is in the tree, or `None` if there is no such
+    /// leaf.
+    pub fn proof(&self, leaf_index: u64) -> Option<MerkleProof> {
+        let multi_proof = self.multi_proof(&[leaf_index])?;
+        Some(MerkleProof {
+            leaf_index,
+            leaf_count: multi_proof.leaf_count,
+            siblings: multi_proof.siblings,
+        })
+    }
+
+    /// Proof that the leaves at the given indices are in the tree, or `None` if there are no
+    /// indices or one of them is not the index of a leaf. Duplicate indices are ignored.
+    pub fn multi_proof(&self, leaf_indices: &[u64]) -> Option<MerkleMultiProof> {
+        let mut leaf_indices = leaf_indices.to_vec();
+        leaf_indices.sort_unstable();
+        leaf_indices.dedup();
+        let leaf_count = self.levels[0].len() as u64;
+        if leaf_indices.last().is_none_or(|index| *index >= leaf_count) {
+            return None;
+        }
+
+        // Mirrors the traversal of compute_root, collecting the siblings it needs.
+        let mut siblings = vec![];
+        let mut known = leaf_indices.clone();
+        for level in &self.levels[..self.levels.len() - 1] {
+            let count = level.len() as u64;
+            let mut indices = known.iter().copied().peekable();
+            while let Some(index) = indices.next() {
+                if index % 2 == 1 {
+                    siblings.push(level[index as usize - 1].clone());
+                } else if index + 1 != count && indices.next_if_eq(&(index + 1)).is_none() {
+                    siblings.push(level[index as usize + 1].clone());
+                }
+            }
+            known = known.iter().map(|index| index / 2).collect();
+            known.dedup();
+        }
+        Some(MerkleMultiProof {
+            leaf_indices,
+            leaf_count,
+            siblings,
+        })
+    }
+}
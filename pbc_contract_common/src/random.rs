@@ -0,0 +1,287 @@
+//! Deterministic pseudo-random values for contract invocations.
+//!
+//! Contract execution must be deterministic, such that every node computes the same state. Values
+//! that appear random must therefore be derived from the invocation itself, which
+//! [`TransactionRng`] does from the [`ContractContext`]:
+//!
+//! ```
+//! # use pbc_contract_common::address::Address;
+//! # use pbc_contract_common::context::ContractContext;
+//! # use pbc_contract_common::random::RandomSource;
+//! fn draw_winners(context: &ContractContext, mut tickets: Vec<Address>) -> Vec<Address> {
+//!     let mut rng = context.rng(b"lottery");
+//!     rng.shuffle(&mut tickets);
+//!     tickets.truncate(rng.gen_range(1..=3));
+//!     tickets
+//! }
+//! ```
+//!
+//! # Security
+//!
+//! The values are pseudo-random, but **not secret and not unpredictable** to everyone:
+//!
+//! - Anyone can recompute them once the invocation has been executed, so they must not be used
+//!   for keys, salts or other secrets.
+//! - The sender chooses the transaction, and with it the hashes the values are derived from. By
+//!   preparing several transactions, or simulating them before sending, a sender can choose
+//!   between outcomes, and even grind for a favourable one.
+//! - Block producers know all inputs before executing, and can choose whether and when to
+//!   include a transaction.
+//!
+//! This is adequate where the outcome is worth less than the cost of influencing it, such as
+//! breaking ties or sampling auditors among many parties. Where the outcome has value to the
+//! sender, let the participants commit to secret values that are revealed only after the
+//! commitments are fixed, or generate the randomness in a zero-knowledge computation.
+//!
+//! Each invocation, with a given domain, gives a single sequence of values. Draws meant to be
+//! independent must use separate domains, or continue drawing from the same [`TransactionRng`].
+
+use std::ops::{Bound, RangeBounds};
+
+use sha2::{Digest, Sha256};
+
+use crate::context::ContractContext;
+
+/// A source of uniformly distributed random bytes, with methods for sampling values from them.
+///
+/// Implemented by [`TransactionRng`] for contracts, and by generators of example data for tests.
+pub trait RandomSource {
+    /// Fills `dest` with random bytes.
+    fn fill_bytes(&mut self, dest: &mut [u8]);
+
+    /// A random `u32`.
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_be_bytes(bytes)
+    }
+
+    /// A random `u64`.
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_be_bytes(bytes)
+    }
+
+    /// A random `u128`.
+    fn next_u128(&mut self) -> u128 {
+        let mut bytes = [0; 16];
+        self.fill_bytes(&mut bytes);
+        u128::from_be_bytes(bytes)
+    }
+
+    /// A value sampled uniformly from the given range.
+    ///
+    /// Panics if the range is empty.
+    fn gen_range<T: SampleUniform, R: RangeBounds<T>>(&mut self, range: R) -> T
+    where
+        Self: Sized,
+    {
+        let low = match range.start_bound() {
+            Bound::Included(low) => Some(*low),
+            Bound::Excluded(low) => low.successor(),
+            Bound::Unbounded => Some(T::MIN),
+        };
+        let high = match range.end_bound() {
+            Bound::Included(high) => Some(*high),
+            Bound::Excluded(high) => high.predecessor(),
+            Bound::Unbounded => Some(T::MAX),
+        };
+        match (low, high) {
+            (Some(low), Some(high)) if low <= high => T::sample_inclusive(self, low, high),
+            _ => panic!("Cannot sample from an empty range"),
+        }
+    }
+
+    /// Whether an event with probability `numerator / denominator` happens.
+    ///
+    /// Panics if `denominator` is zero, or smaller than `numerator`.
+    fn gen_ratio(&mut self, numerator: u64, denominator: u64) -> bool
+    where
+        Self: Sized,
+    {
+        assert!(
+            numerator <= denominator && denominator != 0,
+            "Invalid probability {numerator}/{denominator}"
+        );
+        self.gen_range(0..denominator) < numerator
+    }
+
+    /// Shuffles the given elements, such that every permutation is equally likely.
+    fn shuffle<T>(&mut self, elements: &mut [T])
+    where
+        Self: Sized,
+    {
+        for index in (1..elements.len()).rev() {
+            elements.swap(index, self.gen_range(0..=index));
+        }
+    }
+
+    /// A uniformly chosen element, or `None` if there are no elements.
+    fn choose<'a, T>(&mut self, elements: &'a [T]) -> Option<&'a T>
+    where
+        Self: Sized,
+    {
+        if elements.is_empty() {
+            None
+        } else {
+            Some(&elements[self.gen_range(0..elements.len())])
+        }
+    }
+}
+
+/// Integer types that can be sampled uniformly from a range with [`RandomSource::gen_range`].
+pub trait SampleUniform: Copy + PartialOrd {
+    /// The smallest value.
+    const MIN: Self;
+    /// The largest value.
+    const MAX: Self;
+
+    /// The next value, or `None` for [`Self::MAX`].
+    fn successor(self) -> Option<Self>;
+
+    /// The previous value, or `None` for [`Self::MIN`].
+    fn predecessor(self) -> Option<Self>;
+
+    /// A value sampled uniformly from `low..=high`, where `low <= high`.
+    fn sample_inclusive<R: RandomSource>(rng: &mut R, low: Self, high: Self) -> Self;
+}
+
+macro_rules! sample_uniform_impl {
+    ($($type:ty => $unsigned:ty),*) => {
+        $(
+            impl SampleUniform for $type {
+                const MIN: Self = <$type>::MIN;
+                const MAX: Self = <$type>::MAX;
+
+                fn successor(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn predecessor(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn sample_inclusive<R: RandomSource>(rng: &mut R, low: Self, high: Self) -> Self {
+                    let span = (high as $unsigned).wrapping_sub(low as $unsigned);
+                    let offset = sample_up_to(rng, span as u128) as $unsigned;
+                    (low as $unsigned).wrapping_add(offset) as $type
+                }
+            }
+        )*
+    };
+}
+
+sample_uniform_impl!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
+
+/// A value sampled uniformly from `0..=max`.
+///
+/// Draws are rejected below `2^bits mod (max + 1)`, leaving a number of accepted draws divisible
+/// by `max + 1`, such that the remainder is uniform.
+fn sample_up_to<R: RandomSource>(rng: &mut R, max: u128) -> u128 {
+    if let Ok(max) = u64::try_from(max) {
+        let Some(count) = max.checked_add(1) else {
+            return u128::from(rng.next_u64());
+        };
+        let threshold = count.wrapping_neg() % count;
+        loop {
+            let draw = rng.next_u64();
+            if draw >= threshold {
+                return u128::from(draw % count);
+            }
+        }
+    }
+    let Some(count) = max.checked_add(1) else {
+        return rng.next_u128();
+    };
+    let threshold = count.wrapping_neg() % count;
+    loop {
+        let draw = rng.next_u128();
+        if draw >= threshold {
+            return draw % count;
+        }
+    }
+}
+
+/// Pseudo-random generator seeded from a [`ContractContext`] and a domain.
+///
+/// The seed is the SHA-256 hash of the domain, the [`current_transaction`], the
+/// [`original_transaction`] and the [`block_time`]. Output is produced in blocks of 32 bytes,
+/// block `i` being the SHA-256 hash of the seed followed by `i` as a big-endian `u64`.
+///
+/// See the [module documentation](self) for the security limits of the values.
+///
+/// [`current_transaction`]: ContractContext::current_transaction
+/// [`original_transaction`]: ContractContext::original_transaction
+/// [`block_time`]: ContractContext::block_time
+#[derive(Debug, Clone)]
+pub struct TransactionRng {
+    seed: [u8; 32],
+    counter: u64,
+    block: [u8; 32],
+    /// Number of bytes of the block that have been used.
+    used: usize,
+}
+
+impl TransactionRng {
+    /// Prefix of the seed, separating it from other hashes of the same fields.
+    const SEED_PREFIX: &'static [u8] = b"PBC_TRANSACTION_RNG";
+
+    /// Creates the generator for the given invocation and domain.
+    ///
+    /// The domain separates the values drawn for different purposes within the same invocation,
+    /// such as `b"winner"` and `b"shuffle"`.
+    pub fn new(context: &ContractContext, domain: &[u8]) -> Self {
+        let domain_length = u32::try_from(domain.len()).expect("Domain too long");
+        let mut hasher = Sha256::new();
+        hasher.update(Self::SEED_PREFIX);
+        hasher.update(domain_length.to_be_bytes());
+        hasher.update(domain);
+        hasher.update(context.current_transaction.bytes);
+        hasher.update(context.original_transaction.bytes);
+        hasher.update(context.block_time.to_be_bytes());
+        Self {
+            seed: hasher.finalize().into(),
+            counter: 0,
+            block: [0; 32],
+            used: 32,
+        }
+    }
+
+    fn next_block(&mut self) {
+        let mut hasher = Sha256::new();
+        hasher.update(self.seed);
+        hasher.update(self.counter.to_be_bytes());
+        self.block = hasher.finalize().into();
+        self.counter += 1;
+        self.used = 0;
+    }
+}
+
+impl RandomSource for TransactionRng {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut filled = 0;
+        while filled < dest.len() {
+            if self.used == self.block.len() {
+                self.next_block();
+            }
+            let count = (dest.len() - filled).min(self.block.len() - self.used);
+            dest[filled..filled + count].copy_from_slice(&self.block[self.used..self.used + count]);
+            filled += count;
+            self.used += count;
+        }
+    }
+}
+
+impl ContractContext {
+    /// Pseudo-random generator for this invocation and the given domain.
+    ///
+    /// See [`TransactionRng`], and the [module documentation](crate::random) for the security
+    /// limits of the values.
+    pub fn rng(&self, domain: &[u8]) -> TransactionRng {
+        TransactionRng::new(self, domain)
+    }
+}
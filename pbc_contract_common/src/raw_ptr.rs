@@ -1,6 +1,8 @@
-use std::io::{Read, Write};
+use pbc_traits::{PbcRead, PbcWrite};
 
 /// A wrapper for a raw pointer that allows (unsafe) reading and writing to raw pointers.
+///
+/// Implements [`PbcRead`] and [`PbcWrite`] directly, without going through [`std::io`].
 pub struct RawPtr {
     offset: usize,
     base: *mut u8,
@@ -38,26 +40,43 @@ impl RawPtr {
     }
 }
 
-impl Read for RawPtr {
-    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+impl PbcRead for RawPtr {
+    fn read_bytes(&mut self, buf: &mut [u8]) -> pbc_traits::io::Result<()> {
         for buf_item in buf.iter_mut() {
             *buf_item = self.read();
         }
 
-        Ok(buf.len())
+        Ok(())
     }
 }
 
-impl Write for RawPtr {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+impl PbcWrite for RawPtr {
+    fn write_bytes(&mut self, buf: &[u8]) -> pbc_traits::io::Result<()> {
         for buf_item in buf.iter() {
             self.write(*buf_item);
         }
 
-        Ok(buf.len())
+        Ok(())
     }
+}
 
-    fn flush(&mut self) -> std::io::Result<()> {
-        Ok(())
+#[cfg(test)]
+mod test {
+    use pbc_traits::{ReadWriteState, WriteRPC};
+
+    use super::RawPtr;
+
+    #[test]
+    fn read_and_write_through_pointer() {
+        let mut buffer = [0u8; 8];
+        let mut writer = RawPtr::new(buffer.as_mut_ptr());
+        (7u32, 9u16).state_write_to(&mut writer).unwrap();
+        0x0102u16.rpc_write_to(&mut writer).unwrap();
+        assert_eq!(writer.get_offset(), 8);
+        assert_eq!(buffer, [7, 0, 0, 0, 9, 0, 1, 2]);
+
+        let mut reader = RawPtr::new(buffer.as_mut_ptr());
+        assert_eq!(<(u32, u16)>::state_read_from(&mut reader), (7, 9));
+        assert_eq!(reader.get_offset(), 6);
     }
 }
@@ -5,7 +5,7 @@ use pbc_traits::{ReadWriteState, WriteRPC};
 use crate::zk;
 use pbc_zk_core::{SecretBinary, SecretBinaryFixedSize};
 
-fn write_u32_be_at_idx(buffer: &mut [u8], idx: usize, value: u32) -> std::io::Result<()> {
+fn write_u32_be_at_idx(buffer: &mut [u8], idx: usize, value: u32) -> pbc_traits::io::Result<()> {
     let mut value_buffer = Vec::with_capacity(4);
     value_buffer.write_u32_be(value)?;
     buffer[idx..(4 + idx)].clone_from_slice(&value_buffer[..4]);
@@ -47,8 +47,11 @@ impl ContractResultBuffer {
     ///
     /// Should be used in conjunction with [`Self::finalize_result_buffer`], which will place the buffer as
     /// expected by the blockchain binder, and produce some output to locate it.
+    ///
+    /// Sections reserve exactly the space they need when written, based on the serialized size of
+    /// their contents.
     pub fn new() -> Self {
-        let mut data = Vec::with_capacity(10240);
+        let mut data = Vec::with_capacity(4);
         // Preallocate 4 bytes for the length of the rest of the result.
         data.write_u32_be(0).unwrap();
 
@@ -65,17 +68,22 @@ impl ContractResultBuffer {
     /// ```
     ///
     /// Note that we don't know the length of bytes beforehand, so initially we insert a placeholder
-    /// length of zero at the length position, and replace it later on.
+    /// length of zero at the length position, and replace it later on. `data_size` is the expected
+    /// number of bytes written by `section_data_writer`, used to reserve space in the buffer.
     #[inline]
-    fn write_section<F: FnOnce(&mut Vec<u8>) -> std::io::Result<()>>(
+    fn write_section<F: FnOnce(&mut Vec<u8>) -> pbc_traits::io::Result<()>>(
         &mut self,
         section_id: u8,
+        data_size: usize,
         section_data_writer: F,
-    ) -> std::io::Result<()> {
+    ) -> pbc_traits::io::Result<()> {
         // Check that this section id is allowed to be written
         assert!(self.next_allowed_section_id <= section_id, "Duplicated or incorrectly ordered sections. Tried to write section with id 0x{:02x}, but expected section id of at least 0x{:02x}", section_id, self.next_allowed_section_id );
         self.next_allowed_section_id = section_id + 1;
 
+        // Reserve space for id, length and data
+        self.data.reserve(1 + 4 + data_size);
+
         // Write id
         self.data.write_u8(section_id)?;
 
@@ -98,7 +106,8 @@ impl ContractResultBuffer {
         if std::mem::size_of::<S>() == 0 {
             return;
         }
-        self.write_section(result_section_type_id::STATE, |buf| {
+        let size = state.state_serialized_size();
+        self.write_section(result_section_type_id::STATE, size, |buf| {
             state.state_write_to(buf)
         })
         .unwrap();
@@ -111,7 +120,8 @@ impl ContractResultBuffer {
         if events.is_empty() {
             return;
         }
-        self.write_section(result_section_type_id::EVENTS, |buf| {
+        let size = events.rpc_serialized_size();
+        self.write_section(result_section_type_id::EVENTS, size, |buf| {
             events.rpc_write_to(buf)
         })
         .unwrap();
@@ -152,7 +162,8 @@ impl ContractResultBuffer {
         &mut self,
         declaration: zk::ZkInputDef<MetadataT, SecretT>,
     ) {
-        self.write_section(result_section_type_id::ZK_INPUT_DEF, |buf| {
+        let size = declaration.rpc_serialized_size();
+        self.write_section(result_section_type_id::ZK_INPUT_DEF, size, |buf| {
             declaration.rpc_write_to(buf)
         })
         .unwrap();
@@ -160,7 +171,8 @@ impl ContractResultBuffer {
 
     /// Writes a vector of [`zk::ZkStateChange`] to the output buffer.
     pub fn write_zk_state_change(&mut self, changes: Vec<zk::ZkStateChange>) {
-        self.write_section(result_section_type_id::ZK_STATE_CHANGE, |buf| {
+        let size = changes.rpc_serialized_size();
+        self.write_section(result_section_type_id::ZK_STATE_CHANGE, size, |buf| {
             changes.rpc_write_to(buf)
         })
         .unwrap();
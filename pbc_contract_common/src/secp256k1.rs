@@ -0,0 +1,51 @@
+//! Recovery of secp256k1 public keys, as used by the [`Signature`]s and [`PublicKey`]s of
+//! Partisia Blockchain.
+//!
+//! The curve arithmetic is provided by the [`k256`] crate.
+//!
+//! [`Signature`]: crate::signature::Signature
+//! [`PublicKey`]: crate::PublicKey
+
+use crate::signature::SignatureError;
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+/// Recovers the compressed public key that produced the signature `(r, s)` of `hash`.
+///
+/// * `hash` - The signed message hash, as a big endian integer.
+/// * `recovery_id` - Selects the candidate point for `r`; bit 0 is the parity of its `y`
+///   coordinate, and bit 1 indicates whether its `x` coordinate is `r + n`.
+/// * `r`, `s` - The signature values, as big endian integers.
+pub(crate) fn recover_public_key(
+    hash: &[u8; 32],
+    recovery_id: u8,
+    r: &[u8; 32],
+    s: &[u8; 32],
+) -> Result<[u8; 33], SignatureError> {
+    let recovery_id = RecoveryId::from_byte(recovery_id)
+        .ok_or(SignatureError::InvalidRecoveryId { recovery_id })?;
+    let signature =
+        Signature::from_scalars(*r, *s).map_err(|_| SignatureError::ScalarOutOfRange)?;
+
+    // k256 only accepts signatures with a low s. Negating s negates the point that is recovered
+    // from r, so the parity of its y coordinate flips.
+    let (signature, recovery_id) = match signature.normalize_s() {
+        Some(normalized) => (
+            normalized,
+            RecoveryId::new(!recovery_id.is_y_odd(), recovery_id.is_x_reduced()),
+        ),
+        None => (signature, recovery_id),
+    };
+
+    let key = VerifyingKey::recover_from_prehash(hash, &signature, recovery_id)
+        .map_err(|_| SignatureError::NoPublicKey)?;
+    Ok(key.to_encoded_point(true).as_bytes().try_into().unwrap())
+}
+
+/// Decompresses a compressed public key into its 65 byte uncompressed encoding.
+///
+/// Returns `None` if the bytes are not the compressed encoding of a point on the curve.
+pub(crate) fn decompress_public_key(compressed: &[u8; 33]) -> Option<[u8; 65]> {
+    let key = k256::PublicKey::from_sec1_bytes(compressed).ok()?;
+    Some(key.to_encoded_point(false).as_bytes().try_into().unwrap())
+}
@@ -9,6 +9,11 @@ use read_write_rpc_derive::ReadRPC;
 use read_write_rpc_derive::WriteRPC;
 use read_write_state_derive::ReadWriteState;
 
+#[cfg(feature = "secp256k1")]
+use crate::{secp256k1, Hash, PublicKey};
+#[cfg(feature = "secp256k1")]
+use std::fmt;
+
 /// A signature is used to authenticate the sender of a transaction on the blockchain.
 ///
 /// It consists of a 65 byte array.
@@ -26,3 +31,69 @@ pub struct Signature {
 impl CreateTypeSpec for Signature {
     type_spec_default_impl!("Signature", 0x15);
 }
+
+#[cfg(feature = "secp256k1")]
+impl Signature {
+    /// Recovers the public key of the account that produced this signature of the given message
+    /// hash.
+    ///
+    /// Partisia Blockchain signs using ECDSA over the secp256k1 curve, where
+    /// [`recovery_id`](Self::recovery_id) identifies which of the candidate public keys signed.
+    /// Recovery succeeds for any well-formed signature, so the result must be compared to the
+    /// expected signer, usually by its [`Address`](crate::address::Address):
+    ///
+    /// ```
+    /// # use pbc_contract_common::address::Address;
+    /// # use pbc_contract_common::signature::Signature;
+    /// # use pbc_contract_common::Hash;
+    /// fn is_signed_by(signature: &Signature, message: &Hash, signer: &Address) -> bool {
+    ///     let public_key = signature.recover_public_key(message).ok();
+    ///     public_key.and_then(|public_key| public_key.to_address()) == Some(*signer)
+    /// }
+    /// ```
+    ///
+    /// Requires the `secp256k1` feature.
+    ///
+    /// Returns a [`SignatureError`] if the signature is malformed.
+    pub fn recover_public_key(&self, message_hash: &Hash) -> Result<PublicKey, SignatureError> {
+        let bytes = secp256k1::recover_public_key(
+            &message_hash.bytes,
+            self.recovery_id,
+            &self.value_r,
+            &self.value_s,
+        )?;
+        Ok(PublicKey { bytes })
+    }
+}
+
+/// Error produced when recovering the public key of a malformed [`Signature`].
+#[cfg(feature = "secp256k1")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureError {
+    /// The recovery id is not in the range `0..=3`.
+    InvalidRecoveryId {
+        /// The invalid recovery id.
+        recovery_id: u8,
+    },
+    /// The `r` or `s` value is zero, or not less than the order of the curve.
+    ScalarOutOfRange,
+    /// No valid public key produced the signature, as the `r` value and recovery id do not
+    /// identify a point on the curve, or the recovered key is the point at infinity.
+    NoPublicKey,
+}
+
+#[cfg(feature = "secp256k1")]
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRecoveryId { recovery_id } => {
+                write!(f, "Recovery id {recovery_id} is not in the range 0..=3")
+            }
+            Self::ScalarOutOfRange => write!(f, "Signature value is out of range"),
+            Self::NoPublicKey => write!(f, "Signature was not produced by a valid public key"),
+        }
+    }
+}
+
+#[cfg(feature = "secp256k1")]
+impl std::error::Error for SignatureError {}
@@ -5,6 +5,7 @@
 
 use crate::address::{Address, AddressType};
 use crate::context::{CallbackContext, ContractContext, ExecutionResult};
+use crate::random::RandomSource;
 use crate::signature::Signature;
 use crate::sorted_vec_map::SortedVecMap;
 use crate::zk;
@@ -270,7 +271,11 @@ pub fn example_external_event_id() -> zk::evm_event::ExternalEventId {
 }
 
 /// Simple Linear Congruential Generator RNG for generating example data.
-struct Rng {
+///
+/// Implements [`RandomSource`] as a cheap and reproducible stand-in for
+/// [`TransactionRng`](crate::random::TransactionRng) in tests. Its output is far from uniform,
+/// and must never be used outside of tests.
+pub struct Rng {
     state: u32,
 }
 
@@ -307,3 +312,11 @@ impl Rng {
         out
     }
 }
+
+impl RandomSource for Rng {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for entry in dest {
+            *entry = self.get_u8();
+        }
+    }
+}
@@ -17,7 +17,7 @@ impl InstanceGenerator for AddressType {
     }
 }
 
-impl <const N: usize> InstanceGenerator for [u8; N] {
+impl<const N: usize> InstanceGenerator for [u8; N] {
     fn generate(rng: &mut Rng) -> Self {
         let mut x = [0; N];
         for idx in 0..N {
@@ -32,12 +32,13 @@ impl InstanceGenerator for Address {
         let address_type = AddressType::generate(rng);
         let identifier = <[u8; 20]>::generate(rng);
         Address {
-            address_type, identifier
+            address_type,
+            identifier,
         }
     }
 }
 
-impl InstanceGenerator for ContractContext  {
+impl InstanceGenerator for ContractContext {
     fn generate(rng: &mut Rng) -> Self {
         let contract_address = Address::generate(rng);
         let sender = Address::generate(rng);
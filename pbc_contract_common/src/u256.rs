@@ -0,0 +1,555 @@
+use crate::limbs;
+#[cfg(feature = "abi")]
+use crate::type_spec_default_impl;
+
+#[cfg(feature = "abi")]
+use pbc_traits::CreateTypeSpec;
+use pbc_traits::{DecodeError, PbcRead, PbcWrite, ReadRPC, WriteRPC};
+use read_write_state_derive::ReadWriteState;
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Shl, ShlAssign};
+use std::ops::{Shr, ShrAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A u256 is a 256-bit unsigned integer.
+///
+/// **The comparison operators `<`, `<=`, `>` and `>=` do not compare numerically**; they compare
+/// the little endian bytes. Compare amounts with [`U256::cmp_numeric`] or [`NumericU256`].
+///
+/// It consists of a 32 byte array, holding the value in little endian byte order.
+///
+/// ## Arithmetic
+///
+/// Supports the arithmetic of the primitive unsigned integers; the operators `+`, `-`, `*`, `/`,
+/// `%`, `<<` and `>>` panic on overflow and division by zero, while the `checked_` and
+/// `wrapping_` methods handle these explicitly.
+///
+/// ```
+/// # use pbc_contract_common::U256;
+/// let block: U256 = "0x1000".parse().unwrap();
+/// assert_eq!(block + U256::from(1u32), U256::from(4097u32));
+/// assert_eq!(U256::MAX.checked_add(U256::ONE), None);
+/// assert_eq!(format!("{} {:#x}", block, block), "4096 0x1000");
+/// ```
+///
+/// ## Ordering
+///
+/// The [`Ord`] implementation compares the little endian bytes lexicographically, such that maps
+/// and sets keyed by [`U256`] in existing contract state, like [`SortedVecMap`] and
+/// [`BTreeMap`](std::collections::BTreeMap), remain sorted. This is **not** numeric order, so
+/// the operators and methods like `max` and `sort` must not be used to compare amounts. Use
+/// [`U256::cmp_numeric`], or wrap the values in [`NumericU256`] to get numeric operators:
+///
+/// ```
+/// # use pbc_contract_common::U256;
+/// let (small, large) = (U256::from(0x01FFu32), U256::from(0x0200u32));
+/// assert!(small.cmp_numeric(&large).is_lt());
+/// assert!(small.numeric() < large.numeric());
+/// assert_eq!(small.numeric().max(large.numeric()).0, large);
+/// ```
+///
+/// [`SortedVecMap`]: crate::sorted_vec_map::SortedVecMap
+///
+/// ## Serialization
+///
+/// The State format holds the bytes in little endian order, while the RPC format holds the bytes
+/// in big endian order.
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Default, PartialOrd, Ord, ReadWriteState)]
+pub struct U256 {
+    /// The bytes of the u256.
+    pub bytes: [u8; 32],
+}
+
+#[cfg(feature = "abi")]
+impl CreateTypeSpec for U256 {
+    type_spec_default_impl!("U256", 0x18);
+}
+
+impl ReadRPC for U256 {
+    fn rpc_read_from<T: PbcRead>(reader: &mut T) -> Self {
+        let mut bytes: [u8; 32] = [0; 32];
+        reader.read_bytes(&mut bytes).unwrap();
+        bytes.reverse();
+        Self { bytes }
+    }
+
+    fn try_rpc_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        let mut bytes: [u8; 32] = [0; 32];
+        reader.read_bytes(&mut bytes)?;
+        bytes.reverse();
+        Ok(Self { bytes })
+    }
+}
+
+impl WriteRPC for U256 {
+    fn rpc_write_to<T: PbcWrite>(&self, writer: &mut T) -> pbc_traits::io::Result<()> {
+        let mut bytes: [u8; 32] = self.bytes;
+        bytes.reverse();
+        writer.write_bytes(&bytes)
+    }
+}
+
+/// Number of 64-bit limbs in a [`U256`].
+const LIMBS: usize = 4;
+
+/// Largest power of ten fitting in a `u64`, used to print decimal digits in chunks.
+const DECIMAL_CHUNK: u64 = 10_000_000_000_000_000_000;
+
+/// Number of decimal digits in [`DECIMAL_CHUNK`].
+const DECIMAL_CHUNK_DIGITS: usize = 19;
+
+impl U256 {
+    /// The value zero.
+    pub const ZERO: U256 = U256 { bytes: [0; 32] };
+
+    /// The value one.
+    pub const ONE: U256 = U256::from_u128(1);
+
+    /// The largest value, `2^256 - 1`.
+    pub const MAX: U256 = U256 { bytes: [0xFF; 32] };
+
+    /// The size of the type in bits.
+    pub const BITS: u32 = 256;
+
+    /// Creates a [`U256`] from a `u128`.
+    ///
+    /// Usable in constants; otherwise prefer [`From<u128>`].
+    pub const fn from_u128(value: u128) -> Self {
+        let low = value.to_le_bytes();
+        let mut bytes = [0; 32];
+        let mut index = 0;
+        while index < low.len() {
+            bytes[index] = low[index];
+            index += 1;
+        }
+        Self { bytes }
+    }
+
+    /// Whether the value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.bytes == [0; 32]
+    }
+
+    /// Compares the values numerically.
+    ///
+    /// Unlike [`Ord::cmp`], which orders by the little endian bytes.
+    ///
+    /// ```
+    /// # use pbc_contract_common::U256;
+    /// let mut amounts = vec![U256::from(256u32), U256::ONE, U256::MAX, U256::ZERO];
+    /// amounts.sort_by(U256::cmp_numeric);
+    /// assert_eq!(amounts, [U256::ZERO, U256::ONE, U256::from(256u32), U256::MAX]);
+    /// ```
+    pub fn cmp_numeric(&self, other: &Self) -> Ordering {
+        limbs::cmp(&self.to_limbs(), &other.to_limbs())
+    }
+
+    /// Wraps the value in a [`NumericU256`], whose comparison operators compare numerically.
+    pub fn numeric(self) -> NumericU256 {
+        NumericU256(self)
+    }
+
+    /// Number of leading zero bits in the binary representation of the value.
+    pub fn leading_zeros(&self) -> u32 {
+        let limbs = self.to_limbs();
+        let mut zeros = 0;
+        for limb in limbs.iter().rev() {
+            zeros += limb.leading_zeros();
+            if *limb != 0 {
+                break;
+            }
+        }
+        zeros
+    }
+
+    /// Checked addition. Returns `None` if the result overflows.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_add(rhs) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    /// Wrapping addition, wrapping around at the boundary of the type.
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        self.overflowing_add(rhs).0
+    }
+
+    /// Addition, returning the wrapped result and whether the result overflowed.
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let (result, carry) = limbs::overflowing_add(&self.to_limbs(), &rhs.to_limbs());
+        (Self::from_limbs(result), carry)
+    }
+
+    /// Checked subtraction. Returns `None` if the result would be negative.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_sub(rhs) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    /// Wrapping subtraction, wrapping around at the boundary of the type.
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        self.overflowing_sub(rhs).0
+    }
+
+    /// Subtraction, returning the wrapped result and whether the result overflowed.
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let (result, borrow) = limbs::overflowing_sub(&self.to_limbs(), &rhs.to_limbs());
+        (Self::from_limbs(result), borrow)
+    }
+
+    /// Checked multiplication. Returns `None` if the result overflows.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_mul(rhs) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    /// Wrapping multiplication, wrapping around at the boundary of the type.
+    pub fn wrapping_mul(self, rhs: Self) -> Self {
+        self.overflowing_mul(rhs).0
+    }
+
+    /// Multiplication, returning the wrapped result and whether the result overflowed.
+    pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        let mut wide = [0; 2 * LIMBS];
+        limbs::mul_wide(&self.to_limbs(), &rhs.to_limbs(), &mut wide);
+        let (low, high) = wide.split_at(LIMBS);
+        let overflow = high.iter().any(|&limb| limb != 0);
+        (Self::from_limbs(low.try_into().unwrap()), overflow)
+    }
+
+    /// Checked division. Returns `None` if `rhs` is zero.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            None
+        } else {
+            Some(self.div_rem(rhs).0)
+        }
+    }
+
+    /// Wrapping division. Division of unsigned integers never overflows, so this is identical to
+    /// `self / rhs`.
+    ///
+    /// Panics if `rhs` is zero.
+    pub fn wrapping_div(self, rhs: Self) -> Self {
+        self / rhs
+    }
+
+    /// Checked remainder. Returns `None` if `rhs` is zero.
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            None
+        } else {
+            Some(self.div_rem(rhs).1)
+        }
+    }
+
+    /// Wrapping remainder. Division of unsigned integers never overflows, so this is identical to
+    /// `self % rhs`.
+    ///
+    /// Panics if `rhs` is zero.
+    pub fn wrapping_rem(self, rhs: Self) -> Self {
+        self % rhs
+    }
+
+    /// Checked left shift. Returns `None` if `rhs` is at least [`U256::BITS`].
+    pub fn checked_shl(self, rhs: u32) -> Option<Self> {
+        if rhs >= Self::BITS {
+            return None;
+        }
+        Some(Self::from_limbs(limbs::shl(&self.to_limbs(), rhs)))
+    }
+
+    /// Checked right shift. Returns `None` if `rhs` is at least [`U256::BITS`].
+    pub fn checked_shr(self, rhs: u32) -> Option<Self> {
+        if rhs >= Self::BITS {
+            return None;
+        }
+        Some(Self::from_limbs(limbs::shr(&self.to_limbs(), rhs)))
+    }
+
+    /// Parses a value from a string of digits in the given radix.
+    ///
+    /// Panics if `radix` is not in the range `2..=36`.
+    pub fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseU256Error> {
+        assert!(
+            (2..=36).contains(&radix),
+            "from_str_radix: radix must lie in the range `[2, 36]` - found {radix}"
+        );
+        if src.is_empty() {
+            return Err(ParseU256Error::Empty);
+        }
+        let radix_value = Self::from(radix);
+        let mut value = Self::ZERO;
+        for character in src.chars() {
+            let digit = character
+                .to_digit(radix)
+                .ok_or(ParseU256Error::InvalidDigit)?;
+            value = value
+                .checked_mul(radix_value)
+                .and_then(|value| value.checked_add(Self::from(digit)))
+                .ok_or(ParseU256Error::Overflow)?;
+        }
+        Ok(value)
+    }
+
+    /// Quotient and remainder of dividing by a non-zero `divisor`.
+    pub(crate) fn div_rem(self, divisor: Self) -> (Self, Self) {
+        if let (Ok(dividend), Ok(divisor)) = (u128::try_from(self), u128::try_from(divisor)) {
+            return (
+                Self::from(dividend / divisor),
+                Self::from(dividend % divisor),
+            );
+        }
+        if self.cmp_numeric(&divisor).is_lt() {
+            return (Self::ZERO, self);
+        }
+        // Binary long division, from the most significant bit of the dividend.
+        let dividend = self.to_limbs();
+        let mut quotient = [0; LIMBS];
+        let mut remainder = Self::ZERO;
+        for bit in (0..Self::BITS - self.leading_zeros()).rev() {
+            let (limb, offset) = ((bit / 64) as usize, bit % 64);
+            let overflowed = remainder.leading_zeros() == 0;
+            remainder = Self::from_limbs(limbs::shl(&remainder.to_limbs(), 1));
+            remainder.bytes[0] |= ((dividend[limb] >> offset) & 1) as u8;
+            if overflowed || remainder.cmp_numeric(&divisor).is_ge() {
+                remainder = remainder.wrapping_sub(divisor);
+                quotient[limb] |= 1 << offset;
+            }
+        }
+        (Self::from_limbs(quotient), remainder)
+    }
+
+    /// Divides by a non-zero `u64`, returning the quotient and remainder.
+    fn div_rem_u64(self, divisor: u64) -> (Self, u64) {
+        let limbs = self.to_limbs();
+        let mut quotient = [0; LIMBS];
+        let mut remainder: u64 = 0;
+        for index in (0..LIMBS).rev() {
+            let dividend = (u128::from(remainder) << 64) | u128::from(limbs[index]);
+            quotient[index] = (dividend / u128::from(divisor)) as u64;
+            remainder = (dividend % u128::from(divisor)) as u64;
+        }
+        (Self::from_limbs(quotient), remainder)
+    }
+
+    /// The value as 64-bit limbs, least significant first.
+    fn to_limbs(self) -> [u64; LIMBS] {
+        limbs::from_le_bytes(&self.bytes)
+    }
+
+    /// Creates a value from 64-bit limbs, least significant first.
+    fn from_limbs(value: [u64; LIMBS]) -> Self {
+        let mut bytes = [0; 32];
+        limbs::to_le_bytes(&value, &mut bytes);
+        Self { bytes }
+    }
+
+    /// Big endian hexadecimal digits of the value, without leading zeros.
+    fn hex_digits(self, digits: &[u8; 16]) -> String {
+        let mut hex = String::with_capacity(64);
+        for byte in self.bytes.iter().rev() {
+            hex.push(digits[usize::from(byte >> 4)] as char);
+            hex.push(digits[usize::from(byte & 0x0F)] as char);
+        }
+        match hex.trim_start_matches('0') {
+            "" => "0".to_string(),
+            trimmed => trimmed.to_string(),
+        }
+    }
+}
+
+/// Implements lossless conversion into [`U256`] from unsigned integers.
+macro_rules! impl_from_unsigned {
+    ($($type:ty)*) => {
+        $(
+            #[doc = concat!("Converts a [`", stringify!($type), "`] into a [`U256`].")]
+            impl From<$type> for U256 {
+                fn from(value: $type) -> Self {
+                    Self::from_u128(u128::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_unsigned!(u8 u16 u32 u64 u128);
+
+/// Converts a [`U256`] into a `u128`, failing if the value is larger than [`u128::MAX`].
+impl TryFrom<U256> for u128 {
+    type Error = TryFromU256Error;
+
+    fn try_from(value: U256) -> Result<Self, Self::Error> {
+        let (low, high) = value.bytes.split_at(16);
+        if high.iter().any(|byte| *byte != 0) {
+            return Err(TryFromU256Error);
+        }
+        Ok(u128::from_le_bytes(low.try_into().unwrap()))
+    }
+}
+
+/// A [`U256`] ordered numerically.
+///
+/// Unlike [`U256`], whose [`Ord`] implementation orders by the little endian bytes, the
+/// comparison operators, `max`, `min` and sorting of this type follow numeric order.
+///
+/// ```
+/// # use pbc_contract_common::{NumericU256, U256};
+/// let mut amounts = vec![NumericU256(U256::from(256u32)), NumericU256(U256::ONE)];
+/// amounts.sort();
+/// assert_eq!(amounts, [NumericU256(U256::ONE), NumericU256(U256::from(256u32))]);
+/// assert!(U256::from(0x01FFu32).numeric() < U256::from(0x0200u32).numeric());
+/// ```
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Default)]
+pub struct NumericU256(pub U256);
+
+impl Ord for NumericU256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_numeric(&other.0)
+    }
+}
+
+impl PartialOrd for NumericU256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<U256> for NumericU256 {
+    fn from(value: U256) -> Self {
+        Self(value)
+    }
+}
+
+impl From<NumericU256> for U256 {
+    fn from(value: NumericU256) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for NumericU256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// Implements an arithmetic operator and its assigning counterpart, panicking when the checked
+/// operation fails.
+macro_rules! impl_arithmetic_operator {
+    ($($trait:ident $method:ident $assign_trait:ident $assign_method:ident $rhs:ty, $checked:ident $message:literal)*) => {
+        $(
+            impl $trait<$rhs> for U256 {
+                type Output = U256;
+
+                fn $method(self, rhs: $rhs) -> Self {
+                    self.$checked(rhs).expect($message)
+                }
+            }
+
+            impl $assign_trait<$rhs> for U256 {
+                fn $assign_method(&mut self, rhs: $rhs) {
+                    *self = $trait::$method(*self, rhs);
+                }
+            }
+        )*
+    };
+}
+
+impl_arithmetic_operator!(
+    Add add AddAssign add_assign U256, checked_add "attempt to add with overflow"
+    Sub sub SubAssign sub_assign U256, checked_sub "attempt to subtract with overflow"
+    Mul mul MulAssign mul_assign U256, checked_mul "attempt to multiply with overflow"
+    Div div DivAssign div_assign U256, checked_div "attempt to divide by zero"
+    Rem rem RemAssign rem_assign U256, checked_rem "attempt to calculate the remainder with a divisor of zero"
+    Shl shl ShlAssign shl_assign u32, checked_shl "attempt to shift left with overflow"
+    Shr shr ShrAssign shr_assign u32, checked_shr "attempt to shift right with overflow"
+);
+
+/// Formats the value in decimal.
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut chunks = vec![];
+        let mut rest = *self;
+        loop {
+            let (quotient, chunk) = rest.div_rem_u64(DECIMAL_CHUNK);
+            chunks.push(chunk);
+            rest = quotient;
+            if rest.is_zero() {
+                break;
+            }
+        }
+        let mut digits = chunks.pop().unwrap().to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{chunk:0DECIMAL_CHUNK_DIGITS$}"));
+        }
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+/// Formats the value in lowercase hexadecimal, prefixed by `0x` in the alternate form.
+impl fmt::LowerHex for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "0x", &self.hex_digits(b"0123456789abcdef"))
+    }
+}
+
+/// Formats the value in uppercase hexadecimal, prefixed by `0x` in the alternate form.
+impl fmt::UpperHex for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "0x", &self.hex_digits(b"0123456789ABCDEF"))
+    }
+}
+
+/// Parses a decimal value, or a hexadecimal value prefixed by `0x`.
+impl FromStr for U256 {
+    type Err = ParseU256Error;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        match src.strip_prefix("0x").or_else(|| src.strip_prefix("0X")) {
+            Some(hex) => Self::from_str_radix(hex, 16),
+            None => Self::from_str_radix(src, 10),
+        }
+    }
+}
+
+/// Error produced when parsing a [`U256`] from a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseU256Error {
+    /// The string contained no digits.
+    Empty,
+    /// The string contained a character that is not a digit in the radix.
+    InvalidDigit,
+    /// The value is larger than [`U256::MAX`].
+    Overflow,
+}
+
+impl fmt::Display for ParseU256Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "cannot parse U256 from empty string"),
+            Self::InvalidDigit => write!(f, "invalid digit found in string"),
+            Self::Overflow => write!(f, "number too large to fit in U256"),
+        }
+    }
+}
+
+impl std::error::Error for ParseU256Error {}
+
+/// Error produced when converting a [`U256`] into a smaller integer type that cannot hold it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromU256Error;
+
+impl fmt::Display for TryFromU256Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "U256 value out of range for the target type")
+    }
+}
+
+impl std::error::Error for TryFromU256Error {}
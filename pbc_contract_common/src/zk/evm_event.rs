@@ -55,8 +55,10 @@ pub struct EvmEventFilterBuilder {
 
 impl EvmEventFilterBuilder {
     /// Set earliest block to receive events from.
-    pub fn filter_from_block(mut self, block_number: U256) -> Self {
-        self.from_block = Some(block_number);
+    ///
+    /// Accepts a [`U256`] or any unsigned integer.
+    pub fn filter_from_block(mut self, block_number: impl Into<U256>) -> Self {
+        self.from_block = Some(block_number.into());
         self
     }
 
@@ -85,7 +87,7 @@ impl EvmEventFilterBuilder {
         }
         EvmEventFilter {
             address: self.address,
-            from_block: self.from_block.clone().unwrap_or(U256 { bytes: [0; 32] }),
+            from_block: self.from_block.unwrap_or(U256::ZERO),
             topics: self.topics,
         }
     }
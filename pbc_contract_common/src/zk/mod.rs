@@ -3,12 +3,12 @@
 //! These should be used in conjunction with the Zk macros in `pbc_contract_codegen`.
 pub mod evm_event;
 
-use std::io::{Read, Write};
+use pbc_traits::{PbcRead, PbcWrite};
 use std::marker::PhantomData;
 
 use create_type_spec_derive::CreateTypeSpecInternal;
 use pbc_traits::WriteInt;
-use pbc_traits::{ReadRPC, ReadWriteState, WriteRPC};
+use pbc_traits::{DecodeError, ReadRPC, ReadWriteState, WriteRPC};
 use pbc_zk_core::{SecretBinary, SecretBinaryFixedSize};
 use read_write_rpc_derive::ReadRPC;
 use read_write_rpc_derive::WriteRPC;
@@ -54,11 +54,11 @@ impl SecretVarId {
 }
 
 impl SecretBinary for SecretVarId {
-    fn secret_read_from<ReadT: Read>(reader: &mut ReadT) -> Self {
+    fn secret_read_from<ReadT: PbcRead>(reader: &mut ReadT) -> Self {
         <Self as pbc_traits::ReadWriteState>::state_read_from(reader)
     }
 
-    fn secret_write_to<WriteT: Write>(&self, writer: &mut WriteT) -> std::io::Result<()> {
+    fn secret_write_to<WriteT: PbcWrite>(&self, writer: &mut WriteT) -> pbc_traits::io::Result<()> {
         <Self as pbc_traits::ReadWriteState>::state_write_to(self, writer)
     }
 }
@@ -191,7 +191,7 @@ pub struct ZkState<SecretVarMetadataT> {
 }
 
 impl<MetadataT: ReadWriteState> ReadRPC for ZkState<MetadataT> {
-    fn rpc_read_from<T: Read>(reader: &mut T) -> Self {
+    fn rpc_read_from<T: PbcRead>(reader: &mut T) -> Self {
         Self {
             calculation_state: CalculationStatus::rpc_read_from(reader),
             pending_inputs: <_>::state_read_from(reader),
@@ -201,6 +201,17 @@ impl<MetadataT: ReadWriteState> ReadRPC for ZkState<MetadataT> {
             external_events: <_>::state_read_from(reader),
         }
     }
+
+    fn try_rpc_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        Ok(Self {
+            calculation_state: CalculationStatus::try_rpc_read_from(reader)?,
+            pending_inputs: <_>::try_state_read_from(reader)?,
+            secret_variables: <_>::try_state_read_from(reader)?,
+            data_attestations: <_>::try_state_read_from(reader)?,
+            event_subscriptions: <_>::try_state_read_from(reader)?,
+            external_events: <_>::try_state_read_from(reader)?,
+        })
+    }
 }
 
 impl<SecretVarMetadataT: ReadWriteState> ZkState<SecretVarMetadataT> {
@@ -250,7 +261,7 @@ impl<MetadataT, SecretT: SecretBinary> ZkInputDef<MetadataT, SecretT> {
 impl<MetadataT: ReadWriteState, SecretT: SecretBinary + SecretBinaryFixedSize> WriteRPC
     for ZkInputDef<MetadataT, SecretT>
 {
-    fn rpc_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn rpc_write_to<T: PbcWrite>(&self, writer: &mut T) -> pbc_traits::io::Result<()> {
         vec![SecretT::BITS].rpc_write_to(writer)?;
         self.seal.rpc_write_to(writer)?;
         self.metadata.state_write_to(writer)
@@ -458,7 +469,7 @@ impl ZkStateChange {
 
 #[allow(deprecated)]
 impl WriteRPC for ZkStateChange {
-    fn rpc_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn rpc_write_to<T: PbcWrite>(&self, writer: &mut T) -> pbc_traits::io::Result<()> {
         match self {
             Self::StartComputation {
                 function_shortname,
@@ -1,4 +1,6 @@
-use pbc_contract_common::address::{Address, AddressType};
+use pbc_contract_common::address::{
+    Address, AddressType, InvalidAddressTypeError, ParseAddressError,
+};
 use pbc_contract_common::sorted_vec_map::SortedVecMap;
 use std::collections::HashMap;
 
@@ -78,3 +80,99 @@ pub fn hashmap() {
     map.insert(EXAMPLE_ADDRESS, "Hello World 2");
     assert_eq!(map.get(&EXAMPLE_ADDRESS), Some(&"Hello World 2"));
 }
+
+#[test]
+pub fn parse_display_round_trip() {
+    let upper = format!("{}", EXAMPLE_ADDRESS);
+    let lower = format!("{:x}", EXAMPLE_ADDRESS);
+    assert_eq!(upper.parse::<Address>(), Ok(EXAMPLE_ADDRESS));
+    assert_eq!(lower.parse::<Address>(), Ok(EXAMPLE_ADDRESS));
+    assert_eq!(
+        "00".repeat(21).parse::<Address>(),
+        Ok(Address::account([0; 20]))
+    );
+}
+
+#[test]
+pub fn parse_errors() {
+    assert_eq!(
+        "0200".parse::<Address>(),
+        Err(ParseAddressError::InvalidLength { length: 4 })
+    );
+    assert_eq!(
+        format!("0x{}", EXAMPLE_ADDRESS).parse::<Address>(),
+        Err(ParseAddressError::InvalidLength { length: 44 })
+    );
+    assert_eq!(
+        "02000102030405060708090A0B0C0D0E0F55AADDFG".parse::<Address>(),
+        Err(ParseAddressError::InvalidHexCharacter { index: 41 })
+    );
+    assert_eq!(
+        "05000102030405060708090A0B0C0D0E0F55AADDFF".parse::<Address>(),
+        Err(ParseAddressError::InvalidAddressType { discriminant: 5 })
+    );
+    assert_eq!(
+        ParseAddressError::InvalidLength { length: 4 }.to_string(),
+        "Address must consist of 42 hexadecimal characters, but was 4 long"
+    );
+    assert_eq!(
+        ParseAddressError::InvalidAddressType { discriminant: 5 }.to_string(),
+        "No address type with discriminant 0x05"
+    );
+}
+
+#[test]
+pub fn const_from_hex() {
+    const ADDRESS: Address = Address::from_hex("02000102030405060708090a0b0c0d0e0f55aaddff");
+    assert_eq!(ADDRESS, EXAMPLE_ADDRESS);
+}
+
+#[test]
+#[should_panic(expected = "Address contains a character that is not hexadecimal")]
+pub fn from_hex_panics_on_invalid_hex() {
+    Address::from_hex("0200010203040506070809 A0B0C0D0E0F55AADDFF");
+}
+
+#[test]
+pub fn constructors_per_address_type() {
+    let identifier = EXAMPLE_ADDRESS.identifier;
+    let constructed = [
+        Address::account(identifier),
+        Address::system_contract(identifier),
+        Address::public_contract(identifier),
+        Address::zk_contract(identifier),
+        Address::governance_contract(identifier),
+    ];
+    let types = [
+        AddressType::Account,
+        AddressType::SystemContract,
+        AddressType::PublicContract,
+        AddressType::ZkContract,
+        AddressType::GoveranceContract,
+    ];
+    for (address, address_type) in constructed.into_iter().zip(types) {
+        assert_eq!(address.address_type, address_type);
+        assert_eq!(address.identifier, identifier);
+    }
+    assert_eq!(Address::public_contract(identifier), EXAMPLE_ADDRESS);
+}
+
+#[test]
+pub fn address_type_try_from_u8() {
+    assert_eq!(AddressType::try_from(0x00), Ok(AddressType::Account));
+    assert_eq!(AddressType::try_from(0x01), Ok(AddressType::SystemContract));
+    assert_eq!(AddressType::try_from(0x02), Ok(AddressType::PublicContract));
+    assert_eq!(AddressType::try_from(0x03), Ok(AddressType::ZkContract));
+    assert_eq!(
+        AddressType::try_from(0x04),
+        Ok(AddressType::GoveranceContract)
+    );
+    assert_eq!(
+        AddressType::try_from(0x05),
+        Err(InvalidAddressTypeError { discriminant: 0x05 })
+    );
+    assert_eq!(
+        InvalidAddressTypeError { discriminant: 0xFF }.to_string(),
+        "No address type with discriminant 0xff"
+    );
+}
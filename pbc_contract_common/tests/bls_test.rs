@@ -0,0 +1,219 @@
+#![cfg(feature = "bls")]
+
+use blst::min_sig;
+use blst::BLST_ERROR;
+use pbc_contract_common::{BlsError, BlsPublicKey, BlsSignature};
+
+fn bytes<const LEN: usize>(hex: &str) -> [u8; LEN] {
+    let mut bytes = [0; LEN];
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * index..2 * index + 2], 16).unwrap();
+    }
+    bytes
+}
+
+fn public_key(hex: &str) -> BlsPublicKey {
+    BlsPublicKey { bytes: bytes(hex) }
+}
+
+fn signature(hex: &str) -> BlsSignature {
+    BlsSignature { bytes: bytes(hex) }
+}
+
+const MESSAGE: &[u8] = b"block 1234";
+
+/// Public keys and signatures of [`MESSAGE`] for the secret keys 1, 2 and 42.
+fn signers() -> [(BlsPublicKey, BlsSignature); 3] {
+    [
+        (
+            public_key("93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8"),
+            signature("8c2523b4929901b4f61a53c5a9ba6b4605be3cc529223922dd506d4b24b8b65029fa1c4b48db46c2775d5dcfd9697688"),
+        ),
+        (
+            public_key("aa4edef9c1ed7f729f520e47730a124fd70662a904ba1074728114d1031e1572c6c886f6b57ec72a6178288c47c335771638533957d540a9d2370f17cc7ed5863bc0b995b8825e0ee1ea1e1e4d00dbae81f14b0bf3611b78c952aacab827a053"),
+            signature("aea26a088bc0f6ee96de28cf2d89bf7d1f2ed3f606471ae041eef793dba926f03240c95887ccfd3c8dd207e3f7d68d38"),
+        ),
+        (
+            public_key("ac7fa63dfc38bbf3712e27a180391bca4ccabf609c5967a0592eff420b6235f3f2b323051cb099acc3969aca310f7ff4191b2d6db43fafc2c9592f7e5f73981107975d3d92b843891e724dbc9f05b5eee5a3b2b1fc782ede8149f30830b84444"),
+            signature("a215c423f9eca5bb01710e57075b599a4f8213ef5d47c9698c555f14f1f449eea41a63afde246c59e1d8cdacf31875f5"),
+        ),
+    ]
+}
+
+#[test]
+pub fn verify_signature() {
+    let [(one, signature_one), _, (forty_two, signature_forty_two)] = signers();
+    assert!(signature_one.verify(MESSAGE, &one));
+    assert!(signature_forty_two.verify(MESSAGE, &forty_two));
+
+    assert!(!signature_one.verify(b"block 1235", &one));
+    assert!(!signature_one.verify(MESSAGE, &forty_two));
+    let other_message = signature("b0527710fcedf1166153a3306b1e64701b97b1ff814af62073e777bf8629cd885eb5da58bea1a2559c88bd51c8b1700a");
+    assert!(other_message.verify(b"block 1235", &forty_two));
+}
+
+#[test]
+pub fn aggregate_keys_and_signatures() {
+    let signers = signers();
+    let keys: Vec<BlsPublicKey> = signers.iter().map(|(key, _)| key.clone()).collect();
+    let signatures: Vec<BlsSignature> = signers.iter().map(|(_, sig)| sig.clone()).collect();
+
+    let aggregate_key = BlsPublicKey::aggregate(&keys).unwrap();
+    let aggregate_signature = BlsSignature::aggregate(&signatures).unwrap();
+    assert_eq!(
+        aggregate_key,
+        public_key("9426d466185c05b7886457943fa617375ac2c5e0f334c1467981e279e52a14f589ae07d8af8bd6f65a1fc1c09ad2853e0a8c436734bb4c871848d9c358ee048d8b7ffb2ab0ad491b0ffefd2f64174e627e7bd7a50982aa206ffe40449d754e5f")
+    );
+    assert_eq!(
+        aggregate_signature,
+        signature("95a2ee9bba77d88a14039a6ffb02ca0d0c0af746eda5df3f504281cd82ba0c7c4360b60261415379a4a279db98f99cf3")
+    );
+    assert!(aggregate_signature.verify(MESSAGE, &aggregate_key));
+    assert!(aggregate_signature.verify_aggregate(MESSAGE, &keys));
+    assert!(!aggregate_signature.verify_aggregate(MESSAGE, &keys[..2]));
+    assert!(!aggregate_signature.verify_aggregate(MESSAGE, &[]));
+
+    assert_eq!(BlsPublicKey::aggregate(&keys[..1]), Ok(keys[0].clone()));
+    assert_eq!(
+        BlsSignature::aggregate(&signatures[..1]),
+        Ok(signatures[0].clone())
+    );
+}
+
+#[test]
+pub fn keys_cancelling_out_do_not_verify() {
+    let one = signers()[0].0.clone();
+    // The public key of the secret key r - 1, which is the negation of the key of 1.
+    let negated = public_key("b3e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8");
+    let infinity = signature(&format!("c0{}", "00".repeat(47)));
+    assert_eq!(
+        BlsSignature::aggregate(std::slice::from_ref(&infinity)),
+        Ok(infinity.clone())
+    );
+    assert!(!infinity.verify_aggregate(MESSAGE, &[one, negated]));
+}
+
+#[test]
+pub fn malformed_keys_and_signatures() {
+    let [(one, signature_one), ..] = signers();
+    let mut uncompressed = one.clone();
+    uncompressed.bytes[0] &= 0x7F;
+    let infinity = public_key(&format!("c0{}", "00".repeat(95)));
+    let mut not_on_curve = signature_one.clone();
+    not_on_curve.bytes[47] ^= 1;
+
+    assert!(!signature_one.verify(MESSAGE, &uncompressed));
+    assert!(!signature_one.verify(MESSAGE, &infinity));
+    assert!(!not_on_curve.verify(MESSAGE, &one));
+
+    assert_eq!(
+        BlsPublicKey::aggregate(&[one.clone(), uncompressed]),
+        Err(BlsError::InvalidPublicKey { index: 1 })
+    );
+    assert_eq!(
+        BlsPublicKey::aggregate(&[infinity, one]),
+        Err(BlsError::InvalidPublicKey { index: 0 })
+    );
+    assert_eq!(
+        BlsSignature::aggregate(&[signature_one, not_on_curve]),
+        Err(BlsError::InvalidSignature { index: 1 })
+    );
+    assert_eq!(
+        BlsPublicKey::aggregate(&[]),
+        Err(BlsError::NothingToAggregate)
+    );
+    assert_eq!(
+        BlsSignature::aggregate(&[]),
+        Err(BlsError::NothingToAggregate)
+    );
+    assert_eq!(
+        BlsError::InvalidSignature { index: 1 }.to_string(),
+        "BLS signature at index 1 is invalid"
+    );
+}
+
+/// The domain separation tag of the proof of possession scheme with signatures in G1.
+const DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Secret keys of the blst reference implementation, derived from distinct key material.
+fn blst_secret_keys() -> Vec<min_sig::SecretKey> {
+    (0..4u8)
+        .map(|seed| min_sig::SecretKey::key_gen(&[seed; 32], &[]).unwrap())
+        .collect()
+}
+
+#[test]
+pub fn signers_agree_with_blst() {
+    for (secret_key, (public_key, signature)) in [1u8, 2, 42].into_iter().zip(signers()) {
+        let mut bytes = [0; 32];
+        bytes[31] = secret_key;
+        let secret_key = min_sig::SecretKey::from_bytes(&bytes).unwrap();
+        assert_eq!(secret_key.sk_to_pk().compress(), public_key.bytes);
+        assert_eq!(
+            secret_key.sign(MESSAGE, DST, &[]).compress(),
+            signature.bytes
+        );
+    }
+}
+
+#[test]
+pub fn verify_signatures_of_blst() {
+    for secret_key in blst_secret_keys() {
+        for message in [&b""[..], b"abc", MESSAGE] {
+            let public_key = BlsPublicKey {
+                bytes: secret_key.sk_to_pk().compress(),
+            };
+            let signature = BlsSignature {
+                bytes: secret_key.sign(message, DST, &[]).compress(),
+            };
+            assert!(signature.verify(message, &public_key));
+            assert!(!signature.verify(b"other", &public_key));
+        }
+    }
+}
+
+#[test]
+pub fn aggregates_agree_with_blst() {
+    let secret_keys = blst_secret_keys();
+    let public_keys: Vec<min_sig::PublicKey> =
+        secret_keys.iter().map(|key| key.sk_to_pk()).collect();
+    let signatures: Vec<min_sig::Signature> = secret_keys
+        .iter()
+        .map(|key| key.sign(MESSAGE, DST, &[]))
+        .collect();
+    let keys: Vec<BlsPublicKey> = public_keys
+        .iter()
+        .map(|key| BlsPublicKey {
+            bytes: key.compress(),
+        })
+        .collect();
+    let certificate = BlsSignature::aggregate(
+        &signatures
+            .iter()
+            .map(|signature| BlsSignature {
+                bytes: signature.compress(),
+            })
+            .collect::<Vec<_>>(),
+    )
+    .unwrap();
+
+    let blst_key =
+        min_sig::AggregatePublicKey::aggregate(&public_keys.iter().collect::<Vec<_>>(), true)
+            .unwrap()
+            .to_public_key();
+    let blst_signature =
+        min_sig::AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>(), true)
+            .unwrap()
+            .to_signature();
+    assert_eq!(
+        BlsPublicKey::aggregate(&keys).unwrap().bytes,
+        blst_key.compress()
+    );
+    assert_eq!(certificate.bytes, blst_signature.compress());
+    assert!(certificate.verify_aggregate(MESSAGE, &keys));
+    assert!(!certificate.verify_aggregate(MESSAGE, &keys[1..]));
+    assert_eq!(
+        blst_signature.verify(true, MESSAGE, DST, &[], &blst_key, true),
+        BLST_ERROR::BLST_SUCCESS
+    );
+}
@@ -0,0 +1,196 @@
+use pbc_contract_common::{Decimal, ParseDecimalError, Rounding};
+use pbc_traits::{ReadRPC, ReadWriteState, WriteRPC};
+
+type Amount = Decimal<6>;
+
+fn amount(src: &str) -> Amount {
+    src.parse().unwrap()
+}
+
+#[test]
+pub fn constants_and_conversions() {
+    assert_eq!(Amount::ONE.raw(), 1_000_000);
+    assert_eq!(Amount::ZERO, Amount::default());
+    assert_eq!(Amount::MAX.raw(), u128::MAX);
+    assert_eq!(Amount::from_integer(42), Some(Amount::from_raw(42_000_000)));
+    assert_eq!(Amount::from_integer(u128::MAX / 1_000_000 + 1), None);
+    assert_eq!(<Decimal<0>>::ONE.raw(), 1);
+
+    assert_eq!(amount("2.5").to_integer(Rounding::Down), 2);
+    assert_eq!(amount("2.5").to_integer(Rounding::Up), 3);
+    assert_eq!(amount("2.5").to_integer(Rounding::HalfUp), 3);
+    assert_eq!(amount("2.5").to_integer(Rounding::HalfEven), 2);
+    assert_eq!(amount("3.5").to_integer(Rounding::HalfEven), 4);
+    assert_eq!(amount("3.000001").to_integer(Rounding::Up), 4);
+    assert_eq!(amount("3").to_integer(Rounding::Up), 3);
+    assert_eq!(
+        Amount::MAX.to_integer(Rounding::Up),
+        u128::MAX / 1_000_000 + 1
+    );
+}
+
+#[test]
+pub fn addition_and_subtraction() {
+    assert_eq!(amount("1.25") + amount("0.75"), amount("2"));
+    assert_eq!(amount("1.25") - amount("0.75"), amount("0.5"));
+    assert_eq!(Amount::MAX.checked_add(Amount::from_raw(1)), None);
+    assert_eq!(amount("1").checked_sub(amount("1.000001")), None);
+    assert_eq!(Amount::MAX.saturating_add(Amount::ONE), Amount::MAX);
+    assert_eq!(Amount::ONE.saturating_sub(amount("2")), Amount::ZERO);
+
+    let mut balance = amount("10");
+    balance -= amount("2.5");
+    balance += amount("0.000001");
+    assert_eq!(balance, amount("7.500001"));
+    assert!(amount("0.1") < amount("0.11"));
+}
+
+#[test]
+#[should_panic(expected = "attempt to subtract with overflow")]
+pub fn subtraction_overflow_panics() {
+    let _ = Amount::ZERO - Amount::from_raw(1);
+}
+
+#[test]
+pub fn multiplication_and_division_round() {
+    let third = Amount::ONE
+        .checked_div(amount("3"), Rounding::Down)
+        .unwrap();
+    assert_eq!(third, amount("0.333333"));
+    assert_eq!(
+        Amount::ONE.checked_div(amount("3"), Rounding::Up),
+        Some(amount("0.333334"))
+    );
+    assert_eq!(
+        amount("2").checked_div(amount("3"), Rounding::HalfUp),
+        Some(amount("0.666667"))
+    );
+    assert_eq!(
+        amount("0.000005").checked_mul(amount("0.5"), Rounding::HalfUp),
+        Some(amount("0.000003"))
+    );
+    assert_eq!(
+        amount("0.000005").checked_mul(amount("0.5"), Rounding::HalfEven),
+        Some(amount("0.000002"))
+    );
+    assert_eq!(
+        amount("1.5").checked_mul(amount("1.5"), Rounding::Down),
+        Some(amount("2.25"))
+    );
+    assert_eq!(Amount::ONE.checked_div(Amount::ZERO, Rounding::Down), None);
+    assert_eq!(Amount::MAX.checked_mul(amount("2"), Rounding::Down), None);
+    assert_eq!(
+        Amount::MAX.checked_div(amount("0.5"), Rounding::Down),
+        None,
+        "quotient overflows"
+    );
+}
+
+#[test]
+pub fn mul_div_does_not_overflow_in_between() {
+    // The product of the raw values is far larger than u128::MAX.
+    let reserve = Amount::MAX.checked_sub(Amount::ONE).unwrap();
+    assert_eq!(
+        reserve.checked_mul_div(u128::MAX - 1, u128::MAX, Rounding::Down),
+        Some(Amount::from_raw(reserve.raw() - 1))
+    );
+    assert_eq!(
+        reserve.checked_mul_div(u128::MAX - 1, u128::MAX, Rounding::Up),
+        Some(reserve)
+    );
+    assert_eq!(
+        amount("1000").checked_mul_div(3, 1000, Rounding::Down),
+        Some(amount("3"))
+    );
+    assert_eq!(amount("1").checked_mul_div(1, 0, Rounding::Down), None);
+    assert_eq!(Amount::MAX.checked_mul_div(2, 1, Rounding::Down), None);
+    assert_eq!(
+        Amount::MAX.checked_mul_div(1, 1, Rounding::Up),
+        Some(Amount::MAX)
+    );
+}
+
+#[test]
+pub fn rescale() {
+    let value = amount("1.234567");
+    assert_eq!(
+        value.rescale::<2>(Rounding::Down),
+        Some(Decimal::<2>::from_raw(123))
+    );
+    assert_eq!(
+        value.rescale::<2>(Rounding::Up),
+        Some(Decimal::<2>::from_raw(124))
+    );
+    assert_eq!(
+        value.rescale::<18>(Rounding::Down),
+        Some(Decimal::<18>::from_raw(1_234_567_000_000_000_000))
+    );
+    assert_eq!(Amount::MAX.rescale::<7>(Rounding::Down), None);
+    assert_eq!(value.rescale::<6>(Rounding::Up), Some(value));
+}
+
+#[test]
+pub fn parse_and_display() {
+    assert_eq!(amount("12").raw(), 12_000_000);
+    assert_eq!(amount("12.5").raw(), 12_500_000);
+    assert_eq!(amount("0.000001").raw(), 1);
+    assert_eq!(amount("007.10").raw(), 7_100_000);
+    assert_eq!(amount("12.5").to_string(), "12.500000");
+    assert_eq!(Amount::from_raw(1).to_string(), "0.000001");
+    assert_eq!(Amount::ZERO.to_string(), "0.000000");
+    assert_eq!(
+        Amount::MAX.to_string(),
+        "340282366920938463463374607431768.211455"
+    );
+    assert_eq!(amount(&Amount::MAX.to_string()), Amount::MAX);
+    assert_eq!(format!("{:>10}", amount("1.5")), "  1.500000");
+    assert_eq!(<Decimal<0>>::from_raw(15).to_string(), "15");
+    assert_eq!("15".parse::<Decimal<0>>(), Ok(Decimal::from_raw(15)));
+}
+
+#[test]
+pub fn parse_errors() {
+    assert_eq!("".parse::<Amount>(), Err(ParseDecimalError::Empty));
+    for invalid in [".", ".5", "1.", "1.2.3", "-1", "+1", "1e6", " 1", "1,5"] {
+        assert_eq!(
+            invalid.parse::<Amount>(),
+            Err(ParseDecimalError::InvalidDigit),
+            "{invalid}"
+        );
+    }
+    assert_eq!(
+        "0.0000001".parse::<Amount>(),
+        Err(ParseDecimalError::TooManyDecimals)
+    );
+    assert_eq!(
+        "1.0".parse::<Decimal<0>>(),
+        Err(ParseDecimalError::TooManyDecimals)
+    );
+    assert_eq!(
+        "340282366920938463463374607431768.211456".parse::<Amount>(),
+        Err(ParseDecimalError::Overflow)
+    );
+    assert_eq!(
+        ParseDecimalError::TooManyDecimals.to_string(),
+        "too many digits after the decimal point"
+    );
+}
+
+#[test]
+pub fn serializes_as_raw_value() {
+    let value = amount("1.5");
+
+    let mut state = Vec::new();
+    value.state_write_to(&mut state).unwrap();
+    let mut expected = Vec::new();
+    1_500_000u128.state_write_to(&mut expected).unwrap();
+    assert_eq!(state, expected);
+    assert_eq!(Amount::state_read_from(&mut state.as_slice()), value);
+    assert_eq!(value.state_serialized_size(), 16);
+
+    let mut rpc = Vec::new();
+    value.rpc_write_to(&mut rpc).unwrap();
+    assert_eq!(rpc, 1_500_000u128.to_be_bytes());
+    assert_eq!(Amount::try_rpc_read_from(&mut rpc.as_slice()), Ok(value));
+    assert!(Amount::try_rpc_read_from(&mut &rpc[1..]).is_err());
+}
@@ -0,0 +1,69 @@
+use pbc_contract_common::{Hash, ParseHashError};
+
+const EMPTY_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+#[test]
+pub fn of_bytes() {
+    assert_eq!(Hash::of_bytes(&[]).to_string(), EMPTY_SHA256);
+}
+
+#[test]
+pub fn of_rpc_hashes_big_endian_serialization() {
+    assert_eq!(
+        Hash::of_rpc(&0x0102u16).to_string(),
+        "a12871fee210fb8619291eaea194581cbd2531e4b23759d225f6806923f63222"
+    );
+    assert_eq!(
+        Hash::of_rpc(&"abc".to_string()).to_string(),
+        "d04b72a650ce0f8ce4963330a53ee2832733d2baeffff3c1d8e256cca096d120"
+    );
+    assert_eq!(Hash::of_rpc(&()).to_string(), EMPTY_SHA256);
+}
+
+#[test]
+pub fn of_state_hashes_little_endian_serialization() {
+    assert_eq!(
+        Hash::of_state(&0x0102u16).to_string(),
+        "25dfd29c09617dcc9852281c030e5b3037a338a4712a42a21c907f259c6412a0"
+    );
+    assert_eq!(
+        Hash::of_state(&"abc".to_string()).to_string(),
+        "3da9865b43fa2ec490f78da9db16acd5638704dbce5cc7b3df2e3c7a23addf19"
+    );
+    assert_ne!(Hash::of_state(&1u32), Hash::of_state(&2u32));
+}
+
+#[test]
+pub fn display_and_hex() {
+    let hash = Hash::of_bytes(&[]);
+    assert_eq!(format!("{}", hash), EMPTY_SHA256);
+    assert_eq!(format!("{:x}", hash), EMPTY_SHA256);
+    assert_eq!(format!("{:X}", hash), EMPTY_SHA256.to_uppercase());
+}
+
+#[test]
+pub fn parse_round_trip() {
+    let hash = Hash::of_state(&vec![1u8, 2, 3]);
+    assert_eq!(hash.to_string().parse::<Hash>(), Ok(hash.clone()));
+    assert_eq!(format!("{:X}", hash).parse::<Hash>(), Ok(hash));
+}
+
+#[test]
+pub fn parse_errors() {
+    assert_eq!(
+        "abcd".parse::<Hash>(),
+        Err(ParseHashError::InvalidLength { length: 4 })
+    );
+    assert_eq!(
+        format!("0x{}", EMPTY_SHA256).parse::<Hash>(),
+        Err(ParseHashError::InvalidLength { length: 66 })
+    );
+    assert_eq!(
+        format!("{}g", &EMPTY_SHA256[..63]).parse::<Hash>(),
+        Err(ParseHashError::InvalidHexCharacter { index: 63 })
+    );
+    assert_eq!(
+        ParseHashError::InvalidLength { length: 4 }.to_string(),
+        "Hash must consist of 64 hexadecimal characters, but was 4 long"
+    );
+}
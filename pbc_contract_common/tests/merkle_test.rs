@@ -0,0 +1,233 @@
+use pbc_contract_common::merkle::{
+    LeafHashing, MerkleMultiProof, MerkleProof, MerkleScheme, MerkleTree, PairOrder,
+};
+use pbc_contract_common::Hash;
+use pbc_traits::{ReadRPC, WriteRPC};
+
+const SCHEMES: [MerkleScheme; 6] = [
+    MerkleScheme::new(LeafHashing::Sha256, PairOrder::Positional),
+    MerkleScheme::new(LeafHashing::Sha256, PairOrder::Sorted),
+    MerkleScheme::new(LeafHashing::DoubleSha256, PairOrder::Positional),
+    MerkleScheme::new(LeafHashing::DoubleSha256, PairOrder::Sorted),
+    MerkleScheme::new(LeafHashing::DomainSeparated, PairOrder::Positional),
+    MerkleScheme::new(LeafHashing::DomainSeparated, PairOrder::Sorted),
+];
+
+const RFC_6962: MerkleScheme =
+    MerkleScheme::new(LeafHashing::DomainSeparated, PairOrder::Positional);
+
+fn tree(scheme: MerkleScheme, leaf_count: u64) -> MerkleTree {
+    let values: Vec<u64> = (0..leaf_count).collect();
+    MerkleTree::from_values(scheme, &values)
+}
+
+fn concat(parts: &[&[u8]]) -> Vec<u8> {
+    parts.concat()
+}
+
+#[test]
+pub fn roots_of_small_trees() {
+    let scheme = MerkleScheme::new(LeafHashing::Sha256, PairOrder::Positional);
+    let [a, b, c] = [b"a", b"b", b"c"].map(|value| Hash::of_bytes(value));
+    let tree = MerkleTree::new(scheme, vec![a.clone(), b.clone(), c.clone()]);
+    let ab = Hash::of_bytes(&concat(&[&a.bytes, &b.bytes]));
+    let root = Hash::of_bytes(&concat(&[&ab.bytes, &c.bytes]));
+    assert_eq!(tree.root(), root);
+    assert_eq!(tree.leaves(), &[a.clone(), b, c]);
+
+    let single = MerkleTree::new(scheme, vec![a.clone()]);
+    assert_eq!(single.root(), a);
+    assert_eq!(single.proof(0).unwrap().siblings, vec![]);
+}
+
+#[test]
+pub fn rfc_6962_tree_hashes() {
+    // The empty leaf, and the tree of the leaves 0x00 and 0x10, from the test vectors of RFC 6962.
+    assert_eq!(
+        RFC_6962.hash_leaf(&[]),
+        "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d"
+            .parse()
+            .unwrap()
+    );
+    let tree = MerkleTree::new(
+        RFC_6962,
+        vec![
+            RFC_6962.hash_leaf(&[]),
+            RFC_6962.hash_leaf(&[0x00]),
+            RFC_6962.hash_leaf(&[0x10]),
+        ],
+    );
+    assert_eq!(
+        tree.root(),
+        "aeb6bcfe274b70a14fb067a5e5578264db0fa9b51af5e0ba159158f329e06e77"
+            .parse()
+            .unwrap()
+    );
+}
+
+#[test]
+pub fn proofs_of_every_leaf_verify() {
+    for scheme in SCHEMES {
+        for leaf_count in 1..=17 {
+            let tree = tree(scheme, leaf_count);
+            let root = tree.root();
+            for index in 0..leaf_count {
+                let proof = tree.proof(index).unwrap();
+                assert_eq!(proof.leaf_count, leaf_count);
+                let leaf = scheme.hash_leaf_rpc(&index);
+                assert!(proof.verify(&scheme, &root, &leaf), "{scheme:?} {index}");
+                assert!(!proof.verify(&scheme, &root, &scheme.hash_leaf_rpc(&leaf_count)));
+            }
+            assert_eq!(tree.proof(leaf_count), None);
+        }
+    }
+}
+
+#[test]
+pub fn tampered_proofs_do_not_verify() {
+    let tree = tree(RFC_6962, 11);
+    let root = tree.root();
+    let leaf = RFC_6962.hash_leaf_rpc(&6u64);
+    let proof = tree.proof(6).unwrap();
+    assert!(proof.verify(&RFC_6962, &root, &leaf));
+
+    let mut flipped = proof.clone();
+    flipped.siblings[1].bytes[0] ^= 1;
+    assert!(!flipped.verify(&RFC_6962, &root, &leaf));
+
+    let mut moved = proof.clone();
+    moved.leaf_index = 7;
+    assert!(!moved.verify(&RFC_6962, &root, &leaf));
+
+    let mut resized = proof.clone();
+    resized.leaf_count = 8;
+    assert!(!resized.verify(&RFC_6962, &root, &leaf));
+
+    let mut truncated = proof.clone();
+    truncated.siblings.pop();
+    assert!(!truncated.verify(&RFC_6962, &root, &leaf));
+
+    let mut extended = proof.clone();
+    extended.siblings.push(root.clone());
+    assert!(!extended.verify(&RFC_6962, &root, &leaf));
+
+    let mut outside = proof;
+    outside.leaf_index = 11;
+    assert!(!outside.verify(&RFC_6962, &root, &leaf));
+
+    // The children of an inner node cannot be passed off as a value.
+    let inner = MerkleProof {
+        leaf_index: 0,
+        leaf_count: 6,
+        siblings: tree.proof(0).unwrap().siblings[1..].to_vec(),
+    };
+    let children = concat(&[&tree.leaves()[0].bytes, &tree.leaves()[1].bytes]);
+    assert!(!inner.verify(&RFC_6962, &root, &RFC_6962.hash_leaf(&children)));
+
+    let plain = MerkleScheme::new(LeafHashing::Sha256, PairOrder::Positional);
+    let tree = MerkleTree::new(plain, tree.leaves().to_vec());
+    let inner = MerkleProof {
+        siblings: tree.proof(0).unwrap().siblings[1..].to_vec(),
+        ..inner
+    };
+    assert!(inner.verify(&plain, &tree.root(), &plain.hash_leaf(&children)));
+}
+
+#[test]
+pub fn sorted_pairs_ignore_positions() {
+    let positional = MerkleScheme::new(LeafHashing::DoubleSha256, PairOrder::Positional);
+    let sorted = MerkleScheme::new(LeafHashing::DoubleSha256, PairOrder::Sorted);
+    assert_ne!(tree(positional, 8).root(), tree(sorted, 8).root());
+
+    let (left, right) = (Hash::of_bytes(b"left"), Hash::of_bytes(b"right"));
+    assert_eq!(
+        sorted.hash_pair(&left, &right),
+        sorted.hash_pair(&right, &left)
+    );
+    assert_ne!(
+        positional.hash_pair(&left, &right),
+        positional.hash_pair(&right, &left)
+    );
+
+    // A sorted proof of a full tree verifies at the mirrored position as well.
+    let tree = tree(sorted, 8);
+    let mut proof = tree.proof(2).unwrap();
+    proof.leaf_index = 3;
+    assert!(proof.verify(&sorted, &tree.root(), &sorted.hash_leaf_rpc(&2u64)));
+}
+
+#[test]
+pub fn multi_proofs_verify() {
+    for scheme in SCHEMES {
+        for leaf_count in 1..=13u64 {
+            let tree = tree(scheme, leaf_count);
+            let root = tree.root();
+            // Every subset of the leaves of the smaller trees, and a few of the larger ones.
+            for subset in (1u32..1 << leaf_count.min(9)).step_by(leaf_count as usize) {
+                let indices: Vec<u64> = (0..leaf_count).filter(|i| subset >> i & 1 == 1).collect();
+                let leaves: Vec<Hash> = indices.iter().map(|i| scheme.hash_leaf_rpc(i)).collect();
+                let proof = tree.multi_proof(&indices).unwrap();
+                assert_eq!(proof.leaf_indices, indices);
+                assert!(proof.verify(&scheme, &root, &leaves), "{indices:?}");
+
+                let mut missing = leaves.clone();
+                missing.pop();
+                assert!(!proof.verify(&scheme, &root, &missing));
+            }
+        }
+    }
+}
+
+#[test]
+pub fn multi_proofs_share_siblings() {
+    let tree = tree(RFC_6962, 8);
+    let root = tree.root();
+    let all: Vec<u64> = (0..8).collect();
+    assert_eq!(tree.multi_proof(&all).unwrap().siblings, vec![]);
+
+    let proof = tree.multi_proof(&[5, 1, 4, 1]).unwrap();
+    assert_eq!(proof.leaf_indices, vec![1, 4, 5]);
+    assert_eq!(proof.siblings.len(), 3);
+    let leaves = [1u64, 4, 5].map(|i| RFC_6962.hash_leaf_rpc(&i));
+    assert!(proof.verify(&RFC_6962, &root, &leaves));
+
+    let mut reversed = leaves.clone();
+    reversed.reverse();
+    assert!(!proof.verify(&RFC_6962, &root, &reversed));
+
+    let unordered = MerkleMultiProof {
+        leaf_indices: vec![4, 1, 5],
+        ..proof.clone()
+    };
+    assert!(!unordered.verify(
+        &RFC_6962,
+        &root,
+        &[leaves[1].clone(), leaves[0].clone(), leaves[2].clone()]
+    ));
+
+    let empty = MerkleMultiProof {
+        leaf_indices: vec![],
+        leaf_count: 8,
+        siblings: vec![root.clone()],
+    };
+    assert!(!empty.verify(&RFC_6962, &root, &[]));
+    assert_eq!(tree.multi_proof(&[]), None);
+    assert_eq!(tree.multi_proof(&[3, 8]), None);
+}
+
+#[test]
+pub fn proofs_are_rpc_arguments() {
+    let proof = tree(RFC_6962, 5).proof(3).unwrap();
+    let mut rpc = Vec::new();
+    proof.rpc_write_to(&mut rpc).unwrap();
+
+    let mut expected = Vec::new();
+    expected.extend_from_slice(&3u64.to_be_bytes());
+    expected.extend_from_slice(&5u64.to_be_bytes());
+    expected.extend_from_slice(&3u32.to_be_bytes());
+    for sibling in &proof.siblings {
+        expected.extend_from_slice(&sibling.bytes);
+    }
+    assert_eq!(rpc, expected);
+    assert_eq!(MerkleProof::rpc_read_from(&mut rpc.as_slice()), proof);
+}
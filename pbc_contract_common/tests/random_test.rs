@@ -0,0 +1,126 @@
+use pbc_contract_common::context::ContractContext;
+use pbc_contract_common::random::{RandomSource, TransactionRng};
+use pbc_contract_common::test_examples::{Rng, EXAMPLE_CONTEXT, EXAMPLE_HASH_1};
+
+#[test]
+pub fn known_output() {
+    // Computed independently from the documented seed and block construction.
+    let mut rng = EXAMPLE_CONTEXT.rng(b"lottery");
+    assert_eq!(rng.next_u64(), 0x3ead6157dc97bf1a);
+    assert_eq!(rng.next_u128(), 0xca71ce3e0bd2053ffeadb7f6c15c71e9);
+    // Crosses into the second block.
+    assert_eq!(rng.next_u128(), 0x3f6b2f4fd79403d91b72a2ee14419152);
+}
+
+#[test]
+pub fn deterministic_and_separated() {
+    let draws = |mut rng: TransactionRng| -> [u64; 4] { [(); 4].map(|_| rng.next_u64()) };
+    let lottery = draws(TransactionRng::new(&EXAMPLE_CONTEXT, b"lottery"));
+    assert_eq!(lottery, draws(EXAMPLE_CONTEXT.rng(b"lottery")));
+    assert_ne!(lottery, draws(EXAMPLE_CONTEXT.rng(b"shuffle")));
+    assert_ne!(lottery, draws(EXAMPLE_CONTEXT.rng(b"")));
+
+    let later = ContractContext {
+        block_time: 54,
+        ..EXAMPLE_CONTEXT
+    };
+    assert_ne!(lottery, draws(later.rng(b"lottery")));
+    let other = ContractContext {
+        current_transaction: EXAMPLE_HASH_1,
+        original_transaction: EXAMPLE_HASH_1,
+        ..EXAMPLE_CONTEXT
+    };
+    assert_ne!(lottery, draws(other.rng(b"lottery")));
+
+    // Fields that do not identify the transaction do not affect the values.
+    let other_sender = ContractContext {
+        sender: EXAMPLE_CONTEXT.contract_address,
+        ..EXAMPLE_CONTEXT
+    };
+    assert_eq!(lottery, draws(other_sender.rng(b"lottery")));
+}
+
+#[test]
+pub fn byte_draws_continue_the_stream() {
+    let mut whole = EXAMPLE_CONTEXT.rng(b"bytes");
+    let mut expected = [0; 100];
+    whole.fill_bytes(&mut expected);
+
+    let mut pieces = EXAMPLE_CONTEXT.rng(b"bytes");
+    let mut actual = [0; 100];
+    let (first, rest) = actual.split_at_mut(7);
+    pieces.fill_bytes(first);
+    let (second, third) = rest.split_at_mut(40);
+    pieces.fill_bytes(second);
+    pieces.fill_bytes(third);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+pub fn ranges_are_respected() {
+    let mut rng = EXAMPLE_CONTEXT.rng(b"ranges");
+    for _ in 0..1000 {
+        assert!((10..20).contains(&rng.gen_range(10..20u32)));
+        assert!((-5..=5).contains(&rng.gen_range(-5..=5i64)));
+        assert!(rng.gen_range(u128::MAX - 1..) >= u128::MAX - 1);
+        assert!(rng.gen_range(..-100i8) < -100);
+    }
+    assert_eq!(rng.gen_range(7..=7u8), 7);
+    assert_eq!(rng.gen_range(i128::MIN..=i128::MIN), i128::MIN);
+
+    // Full ranges reach both halves.
+    let draws: Vec<i16> = (0..100).map(|_| rng.gen_range(..)).collect();
+    assert!(draws.iter().any(|draw| *draw < 0) && draws.iter().any(|draw| *draw > 0));
+}
+
+#[test]
+#[should_panic(expected = "Cannot sample from an empty range")]
+pub fn empty_range_panics() {
+    EXAMPLE_CONTEXT.rng(b"empty").gen_range(5..5u64);
+}
+
+#[test]
+pub fn samples_are_uniform() {
+    let mut rng = EXAMPLE_CONTEXT.rng(b"die");
+    let mut counts = [0u32; 6];
+    for _ in 0..60_000 {
+        counts[rng.gen_range(0..6usize)] += 1;
+    }
+    for count in counts {
+        assert!((9_500..10_500).contains(&count), "{counts:?}");
+    }
+
+    let hits = (0..10_000).filter(|_| rng.gen_ratio(1, 4)).count();
+    assert!((2_300..2_700).contains(&hits), "{hits}");
+    assert!(rng.gen_ratio(3, 3));
+    assert!(!rng.gen_ratio(0, 3));
+}
+
+#[test]
+pub fn shuffle_and_choose() {
+    let mut rng = EXAMPLE_CONTEXT.rng(b"shuffle");
+    let mut deck: Vec<u32> = (0..52).collect();
+    rng.shuffle(&mut deck);
+    assert_ne!(deck, (0..52).collect::<Vec<_>>());
+    let mut sorted = deck.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..52).collect::<Vec<_>>());
+
+    let mut empty: [u32; 0] = [];
+    rng.shuffle(&mut empty);
+    assert_eq!(rng.choose(&empty), None);
+    assert_eq!(rng.choose(&[9]), Some(&9));
+    assert!(deck.contains(rng.choose(&deck).unwrap()));
+}
+
+#[test]
+pub fn example_rng_is_a_random_source() {
+    let sample = |seed| {
+        let mut rng = Rng::new(seed);
+        let mut deck: Vec<u8> = (0..10).collect();
+        rng.shuffle(&mut deck);
+        (rng.gen_range(100..200u16), deck)
+    };
+    assert_eq!(sample(312), sample(312));
+    assert_ne!(sample(312), sample(313));
+}
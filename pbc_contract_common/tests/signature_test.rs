@@ -0,0 +1,181 @@
+#![cfg(feature = "secp256k1")]
+
+use pbc_contract_common::address::Address;
+use pbc_contract_common::signature::{Signature, SignatureError};
+use pbc_contract_common::{Hash, PublicKey};
+
+fn bytes<const LEN: usize>(hex: &str) -> [u8; LEN] {
+    let mut bytes = [0; LEN];
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * index..2 * index + 2], 16).unwrap();
+    }
+    bytes
+}
+
+fn signature(recovery_id: u8, value_r: &str, value_s: &str) -> Signature {
+    Signature {
+        recovery_id,
+        value_r: bytes(value_r),
+        value_s: bytes(value_s),
+    }
+}
+
+fn public_key(hex: &str) -> PublicKey {
+    PublicKey { bytes: bytes(hex) }
+}
+
+/// Signature of the SHA-256 hash of "permit" by the private key `1`.
+fn permit() -> (Hash, Signature) {
+    (
+        Hash::of_bytes(b"permit"),
+        signature(
+            1,
+            "8c696b10927de69ae2127043dc6c69b7a7017dbba96abfd0451a17298567d2f7",
+            "bf56b2f94e3a83b8232b9892d0f1446f38b79705762f52239a31d0eb759518e3",
+        ),
+    )
+}
+
+#[test]
+pub fn recover_public_key() {
+    let (hash, permit) = permit();
+    assert_eq!(
+        permit.recover_public_key(&hash),
+        Ok(public_key(
+            "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        ))
+    );
+
+    let meta_transaction = signature(
+        0,
+        "c6e15ff4b9b5b771d45c059c488805725200c7f3371b43498b47c9f65dc94125",
+        "1a61a1b6df0218b998fc1325d98f5353cc0db4872190612414b3b4076e17dc40",
+    );
+    assert_eq!(
+        meta_transaction.recover_public_key(&Hash::of_bytes(b"meta-transaction")),
+        Ok(public_key(
+            "032a5bbcb0eede528e6abe5f2ec50ad7887eb5677af383a460b05ee23bf892dfe5"
+        ))
+    );
+
+    let attestation = signature(
+        1,
+        "939b8789b7df34c01cc17cff4f3dc5f3676faa04d05e711fb5b73a89ea5c8f9f",
+        "5151455d6b0ab29a8ea5f7fc1a3ebc47bd1d895fda5dcabf286ee95778920afe",
+    );
+    assert_eq!(
+        attestation.recover_public_key(&Hash::of_bytes(b"attestation")),
+        Ok(public_key(
+            "03cdd1c738e14ebf6ca7b7aa795f5852110cf730f6553d425bfe53f14132052f1e"
+        ))
+    );
+}
+
+#[test]
+pub fn wrong_recovery_id_or_message_recovers_other_key() {
+    let (hash, mut permit) = permit();
+    let signer = permit.recover_public_key(&hash).unwrap();
+
+    assert_ne!(
+        permit.recover_public_key(&Hash::of_bytes(b"other")),
+        Ok(signer.clone())
+    );
+    permit.recovery_id = 0;
+    assert_eq!(
+        permit.recover_public_key(&hash),
+        Ok(public_key(
+            "030930385bdf7e99fa03743f2a55214b88957514eab1c90814e889119a4f418497"
+        ))
+    );
+}
+
+#[test]
+pub fn malformed_signatures_are_rejected() {
+    let (hash, permit) = permit();
+    assert_eq!(
+        Signature {
+            recovery_id: 4,
+            ..permit.clone()
+        }
+        .recover_public_key(&hash),
+        Err(SignatureError::InvalidRecoveryId { recovery_id: 4 })
+    );
+    assert_eq!(
+        Signature {
+            value_r: [0; 32],
+            ..permit.clone()
+        }
+        .recover_public_key(&hash),
+        Err(SignatureError::ScalarOutOfRange)
+    );
+    assert_eq!(
+        Signature {
+            value_s: [0xFF; 32],
+            ..permit.clone()
+        }
+        .recover_public_key(&hash),
+        Err(SignatureError::ScalarOutOfRange)
+    );
+    // r + n exceeds the field prime for almost all r.
+    assert_eq!(
+        Signature {
+            recovery_id: 3,
+            ..permit.clone()
+        }
+        .recover_public_key(&hash),
+        Err(SignatureError::NoPublicKey)
+    );
+    // x = 5 has no point on the curve, as 5^3 + 7 is not a square modulo the field prime.
+    let mut value_r = [0; 32];
+    value_r[31] = 5;
+    assert_eq!(
+        Signature { value_r, ..permit }.recover_public_key(&hash),
+        Err(SignatureError::NoPublicKey)
+    );
+    assert_eq!(
+        SignatureError::InvalidRecoveryId { recovery_id: 4 }.to_string(),
+        "Recovery id 4 is not in the range 0..=3"
+    );
+}
+
+#[test]
+pub fn public_key_to_address() {
+    let expected = [
+        (
+            "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            "0035e97a5e078a5a0f28ec96d547bfee9ace803ac0",
+        ),
+        (
+            "032a5bbcb0eede528e6abe5f2ec50ad7887eb5677af383a460b05ee23bf892dfe5",
+            "0019831999127dbb21c1827df5776db5a87a0274f9",
+        ),
+        (
+            "03cdd1c738e14ebf6ca7b7aa795f5852110cf730f6553d425bfe53f14132052f1e",
+            "000e52013b206fcb9cde0809cc492941e26747b34b",
+        ),
+    ];
+    for (key, address) in expected {
+        let address = address.parse::<Address>().unwrap();
+        assert_eq!(public_key(key).to_address(), Some(address));
+    }
+}
+
+#[test]
+pub fn recovered_signer_address() {
+    let (hash, permit) = permit();
+    let signer = Address::from_hex("0035e97a5e078a5a0f28ec96d547bfee9ace803ac0");
+    assert_eq!(
+        permit.recover_public_key(&hash).unwrap().to_address(),
+        Some(signer)
+    );
+}
+
+#[test]
+pub fn invalid_public_key_has_no_address() {
+    // Neither a compressed point prefix, nor an x coordinate of a point on the curve.
+    assert_eq!(PublicKey { bytes: [0x04; 33] }.to_address(), None);
+    let mut off_curve = [0; 33];
+    off_curve[0] = 0x02;
+    off_curve[32] = 5;
+    assert_eq!(PublicKey { bytes: off_curve }.to_address(), None);
+}
@@ -0,0 +1,289 @@
+use pbc_contract_common::zk::evm_event::EvmEventFilter;
+use pbc_contract_common::{NumericU256, ParseU256Error, TryFromU256Error, U256};
+use pbc_traits::{ReadRPC, ReadWriteState, WriteRPC};
+use std::cmp::Ordering;
+
+/// 2^128, the smallest value not fitting in a `u128`.
+fn two_pow_128() -> U256 {
+    U256::ONE << 128
+}
+
+fn parse(src: &str) -> U256 {
+    src.parse().unwrap()
+}
+
+#[test]
+pub fn conversions_from_and_to_u128() {
+    assert_eq!(U256::from(0x0102u16).bytes[..3], [0x02, 0x01, 0x00]);
+    assert_eq!(U256::from(7u8), U256::from_u128(7));
+    assert_eq!(u128::try_from(U256::from(u128::MAX)), Ok(u128::MAX));
+    assert_eq!(u128::try_from(U256::ZERO), Ok(0));
+    assert_eq!(u128::try_from(two_pow_128()), Err(TryFromU256Error));
+    assert_eq!(u128::try_from(U256::MAX), Err(TryFromU256Error));
+}
+
+#[test]
+pub fn numeric_comparisons() {
+    let small = U256::from(0x01FFu32);
+    let large = U256::from(0x0200u32);
+    assert_eq!(small.cmp_numeric(&large), Ordering::Less);
+    assert_eq!(
+        two_pow_128().cmp_numeric(&U256::from(u128::MAX)),
+        Ordering::Greater
+    );
+    assert_eq!(U256::MAX.cmp_numeric(&two_pow_128()), Ordering::Greater);
+    assert_eq!(U256::ONE.cmp_numeric(&U256::ONE), Ordering::Equal);
+
+    let mut values = vec![two_pow_128(), U256::ONE, U256::MAX, U256::ZERO];
+    values.sort_by(U256::cmp_numeric);
+    assert_eq!(
+        values,
+        vec![U256::ZERO, U256::ONE, two_pow_128(), U256::MAX]
+    );
+}
+
+#[test]
+pub fn numeric_wrapper_orders_numerically() {
+    let small = U256::from(0x01FFu32);
+    let large = U256::from(0x0200u32);
+    assert!(small.numeric() < large.numeric());
+    assert!(NumericU256(U256::MAX) > NumericU256(two_pow_128()));
+    assert_eq!(small.numeric().max(large.numeric()), NumericU256(large));
+    assert_eq!(U256::from(small.numeric()), small);
+
+    let mut values: Vec<NumericU256> = [two_pow_128(), U256::ONE, U256::MAX, U256::ZERO]
+        .into_iter()
+        .map(NumericU256::from)
+        .collect();
+    values.sort();
+    assert_eq!(
+        values,
+        [U256::ZERO, U256::ONE, two_pow_128(), U256::MAX].map(NumericU256)
+    );
+    assert_eq!(large.numeric().to_string(), "512");
+}
+
+/// The ordering of persisted maps and sets keyed by U256 is unchanged.
+#[test]
+pub fn ordering_is_by_little_endian_bytes() {
+    let small = U256::from(0x01FFu32);
+    let large = U256::from(0x0200u32);
+    assert!(small > large);
+    assert_eq!(small.cmp(&large), small.bytes.cmp(&large.bytes));
+
+    let mut values = vec![two_pow_128(), U256::ONE, U256::from(256u32), U256::ZERO];
+    values.sort();
+    assert_eq!(
+        values,
+        vec![U256::ZERO, two_pow_128(), U256::from(256u32), U256::ONE]
+    );
+}
+
+#[test]
+pub fn addition_and_subtraction() {
+    assert_eq!(
+        U256::from(u128::MAX) + U256::ONE,
+        two_pow_128(),
+        "carry between limbs"
+    );
+    assert_eq!(two_pow_128() - U256::ONE, U256::from(u128::MAX));
+    assert_eq!(U256::MAX.checked_add(U256::ONE), None);
+    assert_eq!(U256::ZERO.checked_sub(U256::ONE), None);
+    assert_eq!(U256::MAX.wrapping_add(U256::from(2u8)), U256::ONE);
+    assert_eq!(U256::ZERO.wrapping_sub(U256::ONE), U256::MAX);
+    assert_eq!(U256::MAX.overflowing_add(U256::ONE), (U256::ZERO, true));
+
+    let mut value = U256::from(10u8);
+    value += U256::from(5u8);
+    value -= U256::from(3u8);
+    assert_eq!(value, U256::from(12u8));
+}
+
+#[test]
+pub fn multiplication() {
+    assert_eq!(
+        U256::from(u64::MAX) * U256::from(u64::MAX),
+        U256::from(u128::from(u64::MAX) * u128::from(u64::MAX))
+    );
+    assert_eq!(
+        two_pow_128() * two_pow_128().checked_shr(1).unwrap(),
+        U256::ONE << 255
+    );
+    assert_eq!(two_pow_128().checked_mul(two_pow_128()), None);
+    assert_eq!(U256::MAX.checked_mul(U256::from(2u8)), None);
+    assert_eq!(U256::MAX.checked_mul(U256::ONE), Some(U256::MAX));
+    assert_eq!(
+        U256::MAX.wrapping_mul(U256::from(2u8)),
+        U256::MAX - U256::ONE
+    );
+    assert_eq!(two_pow_128().wrapping_mul(two_pow_128()), U256::ZERO);
+
+    let mut value = U256::from(6u8);
+    value *= U256::from(7u8);
+    assert_eq!(value, U256::from(42u8));
+}
+
+#[test]
+pub fn division_and_remainder() {
+    assert_eq!(U256::from(17u8) / U256::from(5u8), U256::from(3u8));
+    assert_eq!(U256::from(17u8) % U256::from(5u8), U256::from(2u8));
+    assert_eq!(U256::MAX / U256::MAX, U256::ONE);
+    assert_eq!(U256::MAX % U256::MAX, U256::ZERO);
+    assert_eq!(U256::MAX / two_pow_128(), U256::from(u128::MAX));
+    assert_eq!(U256::MAX % two_pow_128(), U256::from(u128::MAX));
+    assert_eq!(
+        U256::MAX / (U256::ONE << 255),
+        U256::ONE,
+        "divisor above 2^255"
+    );
+
+    let dividend =
+        parse("115792089237316195423570985008687907853269984665640564039457584007913129639935");
+    let divisor = parse("340282366920938463463374607431768211457");
+    let quotient = dividend / divisor;
+    let remainder = dividend % divisor;
+    assert!(remainder.cmp_numeric(&divisor).is_lt());
+    assert_eq!(quotient * divisor + remainder, dividend);
+
+    assert_eq!(U256::ONE.checked_div(U256::ZERO), None);
+    assert_eq!(U256::ONE.checked_rem(U256::ZERO), None);
+    assert_eq!(
+        U256::from(9u8).wrapping_div(U256::from(2u8)),
+        U256::from(4u8)
+    );
+    assert_eq!(U256::from(9u8).wrapping_rem(U256::from(2u8)), U256::ONE);
+
+    let mut value = U256::from(100u8);
+    value /= U256::from(7u8);
+    value %= U256::from(4u8);
+    assert_eq!(value, U256::from(2u8));
+}
+
+#[test]
+#[should_panic(expected = "attempt to subtract with overflow")]
+pub fn subtraction_overflow_panics() {
+    let _ = U256::ZERO - U256::ONE;
+}
+
+#[test]
+#[should_panic(expected = "attempt to divide by zero")]
+pub fn division_by_zero_panics() {
+    let _ = U256::ONE / U256::ZERO;
+}
+
+#[test]
+pub fn shifts() {
+    assert_eq!(U256::ONE << 0, U256::ONE);
+    assert_eq!(U256::ONE << 64, U256::from(1u128 << 64));
+    assert_eq!(U256::from(0x81u8) << 63 >> 63, U256::from(0x81u8));
+    assert_eq!((U256::ONE << 255) >> 255, U256::ONE);
+    assert_eq!(U256::MAX >> 200, U256::from((1u64 << 56) - 1));
+    assert_eq!(U256::MAX << 255, U256::ONE << 255);
+    assert_eq!(U256::ONE.checked_shl(256), None);
+    assert_eq!(U256::ONE.checked_shr(256), None);
+    assert_eq!(U256::MAX.leading_zeros(), 0);
+    assert_eq!(U256::ONE.leading_zeros(), 255);
+    assert_eq!(U256::ZERO.leading_zeros(), 256);
+
+    let mut value = U256::ONE;
+    value <<= 130;
+    value >>= 2;
+    assert_eq!(value, two_pow_128());
+}
+
+#[test]
+pub fn parse_decimal_and_hex() {
+    assert_eq!(parse("0"), U256::ZERO);
+    assert_eq!(parse("4096"), U256::from(4096u32));
+    assert_eq!(parse("0x1000"), U256::from(4096u32));
+    assert_eq!(parse("0XfF"), U256::from(255u8));
+    assert_eq!(
+        parse("340282366920938463463374607431768211456"),
+        two_pow_128()
+    );
+    assert_eq!(
+        parse("115792089237316195423570985008687907853269984665640564039457584007913129639935"),
+        U256::MAX
+    );
+    assert_eq!(parse(&format!("0x{}", "f".repeat(64))), U256::MAX);
+    assert_eq!(U256::from_str_radix("101", 2), Ok(U256::from(5u8)));
+}
+
+#[test]
+pub fn parse_errors() {
+    assert_eq!("".parse::<U256>(), Err(ParseU256Error::Empty));
+    assert_eq!("0x".parse::<U256>(), Err(ParseU256Error::Empty));
+    assert_eq!("12a".parse::<U256>(), Err(ParseU256Error::InvalidDigit));
+    assert_eq!("-1".parse::<U256>(), Err(ParseU256Error::InvalidDigit));
+    assert_eq!("0xg".parse::<U256>(), Err(ParseU256Error::InvalidDigit));
+    assert_eq!(
+        "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+            .parse::<U256>(),
+        Err(ParseU256Error::Overflow)
+    );
+    assert_eq!(
+        format!("0x1{}", "0".repeat(64)).parse::<U256>(),
+        Err(ParseU256Error::Overflow)
+    );
+    assert_eq!(
+        ParseU256Error::Overflow.to_string(),
+        "number too large to fit in U256"
+    );
+}
+
+#[test]
+pub fn display_decimal_and_hex() {
+    assert_eq!(U256::ZERO.to_string(), "0");
+    assert_eq!(U256::from(4096u32).to_string(), "4096");
+    assert_eq!(
+        (U256::from(10_000_000_000_000_000_000u64) + U256::from(5u8)).to_string(),
+        "10000000000000000005"
+    );
+    assert_eq!(
+        U256::MAX.to_string(),
+        "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+    );
+    assert_eq!(format!("{:x}", U256::from(0xABCDu32)), "abcd");
+    assert_eq!(format!("{:X}", U256::from(0xABCDu32)), "ABCD");
+    assert_eq!(format!("{:#x}", U256::ZERO), "0x0");
+    assert_eq!(format!("{:>6}", U256::from(42u8)), "    42");
+    assert_eq!(format!("{:#06x}", U256::from(42u8)), "0x002a");
+    assert_eq!(format!("{:x}", U256::MAX), "f".repeat(64));
+
+    for value in [U256::MAX, two_pow_128(), U256::from(1234567890u64)] {
+        assert_eq!(parse(&value.to_string()), value);
+        assert_eq!(parse(&format!("{value:#x}")), value);
+    }
+}
+
+#[test]
+pub fn serialization_is_unchanged() {
+    let value = U256::from(0x0102u16);
+    let mut rpc = Vec::new();
+    value.rpc_write_to(&mut rpc).unwrap();
+    assert_eq!(rpc[30..], [0x01, 0x02]);
+    assert_eq!(U256::rpc_read_from(&mut rpc.as_slice()), value);
+
+    let mut state = Vec::new();
+    value.state_write_to(&mut state).unwrap();
+    assert_eq!(state[..2], [0x02, 0x01]);
+}
+
+#[test]
+pub fn filter_from_block_accepts_integers() {
+    let address = [7; 20];
+    assert_eq!(
+        EvmEventFilter::builder(address)
+            .filter_from_block(1234u64)
+            .build(),
+        EvmEventFilter::builder(address)
+            .filter_from_block(U256::from(1234u64))
+            .build()
+    );
+    assert_ne!(
+        EvmEventFilter::builder(address)
+            .filter_from_block(1234u64)
+            .build(),
+        EvmEventFilter::builder(address).build()
+    );
+}
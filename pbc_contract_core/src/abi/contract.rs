@@ -1,12 +1,69 @@
-use crate::abi::types::NamedTypeSpec;
+use crate::abi::types::{KindInfo, NamedTypeSpec};
 use std::io::Write;
 
-use super::{abi_serialize_slice, FnAbi};
+use pbc_traits::WriteInt;
+
+use super::read::{
+    contains_wide_array, BASE_CLIENT_VERSION, DOCUMENTATION_VERSION, RETURN_TYPES_VERSION,
+    WIDE_ARRAYS_VERSION,
+};
+use super::{abi_serialize_slice, FnAbi, NamedEntityAbi};
 
 /// The `ContractAbi` describes the ABI for a contract including all the actions
 /// in the contract and the contract state + all user-defined structs within the state and actions.
 ///
-/// Serialized with the ABI format.
+/// Serialized with the ABI format, and parsed with [`AbiFile::parse`](super::AbiFile::parse).
+///
+/// ## Layout
+///
+/// Following the [header](super::AbiHeader), the ABI consists of:
+///
+/// 1. The list of named types.
+/// 2. The list of functions.
+/// 3. The type spec of the state.
+/// 4. From client version 5.6.0: the documentation section.
+/// 5. From client version 5.7.0: the return type section.
+///
+/// Files end after the last element of their client version, and the sections of later versions
+/// are left out when serializing for an earlier version. Lists are prefixed by their big-endian
+/// `u32` length, and sections by the big-endian `u32` length of their content in bytes.
+///
+/// The client version is the earliest that can represent the contract, see
+/// [`ContractAbi::client_version`], so the sections are only present for contracts using them.
+///
+/// ## Type specs
+///
+/// Type specs start with a type ordinal, followed by the elements of the type:
+///
+/// - `0x00`: A named type, followed by its index as a byte.
+/// - `0x01` to `0x0d`, and `0x13` to `0x18`: Simple types, without further elements.
+/// - `0x0e`, `0x10` and `0x12`: `Vec`, `BTreeSet` and `Option`, followed by the element type.
+/// - `0x0f` and `0x19`: `BTreeMap` and `AvlTreeMap`, followed by the key and value types.
+/// - `0x11`: A byte array of at most 127 elements, followed by the length as a byte.
+/// - `0x1a`: Any other array, followed by the element type and the length as a big-endian `u32`.
+///
+/// The `0x1a` ordinal is from client version 5.5.0, and used for the arrays not having the
+/// `0x11` ordinal.
+///
+/// ## Documentation section
+///
+/// Only present in files documenting some named type, field, function or argument, which then
+/// have client version 5.6.0. The section has empty content in files of later versions
+/// documenting nothing, and otherwise:
+///
+/// - For each named type: its documentation and a list with the documentation of each field.
+///   The list is empty for enums, whose variants are documented by the struct types of the
+///   variants.
+/// - For each function: its documentation and a list with the documentation of each argument.
+///
+/// Documentation is a string, like names, and undocumented elements have the empty string.
+///
+/// ## Return type section
+///
+/// Only present in files declaring the return type of some function, which then have client
+/// version 5.7.0. The content is a list with an entry for each function: `0x00` for functions
+/// without a return type, or `0x01` followed by the type spec of the returned data.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ContractAbi {
     types: Vec<NamedTypeSpec>,
     actions: Vec<FnAbi>,
@@ -35,10 +92,181 @@ impl ContractAbi {
         self.types = types;
     }
 
-    /// Serialize this struct according to the ABI specification.
+    /// The named types of the contract, which type specs reference by their index.
+    pub fn named_types(&self) -> &[NamedTypeSpec] {
+        &self.types
+    }
+
+    /// The functions of the contract.
+    pub fn functions(&self) -> &[FnAbi] {
+        &self.actions
+    }
+
+    /// The type spec of the contract state.
+    pub fn state_type_spec(&self) -> &[u8] {
+        &self.state
+    }
+
+    /// The named type of the contract state, if the state is a named type.
+    pub fn state_type(&self) -> Option<&NamedTypeSpec> {
+        match self.state[..] {
+            [0x00, index] => self.types.get(usize::from(index)),
+            _ => None,
+        }
+    }
+
+    /// The earliest client version of the ABI format able to represent this contract:
+    ///
+    /// - 5.7.0 if some function declares a return type.
+    /// - 5.6.0 if some named type, field, function or argument is documented.
+    /// - 5.5.0 if some type spec contains an array with the `0x1a` ordinal.
+    /// - 5.4.0 otherwise.
+    pub fn client_version(&self) -> [u8; 3] {
+        if self.declares_return_types() {
+            RETURN_TYPES_VERSION
+        } else if self.is_documented() {
+            DOCUMENTATION_VERSION
+        } else if self.type_specs().any(contains_wide_array) {
+            WIDE_ARRAYS_VERSION
+        } else {
+            BASE_CLIENT_VERSION
+        }
+    }
+
+    /// Serialize this struct according to the ABI specification, in the earliest client version
+    /// able to represent it, [`ContractAbi::client_version`].
     pub fn serialize_abi<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+        self.serialize_abi_for_version(self.client_version(), writer)
+    }
+
+    /// Serialize this struct according to the ABI specification, in the given client version.
+    /// Sections that the version does not have are left out.
+    pub fn serialize_abi_for_version<T: Write>(
+        &self,
+        version_client: [u8; 3],
+        writer: &mut T,
+    ) -> std::io::Result<()> {
         abi_serialize_slice(&self.types, writer)?;
         abi_serialize_slice(&self.actions, writer)?;
-        writer.write_all(&self.state)
+        writer.write_all(&self.state)?;
+        if version_client < DOCUMENTATION_VERSION {
+            return Ok(());
+        }
+        let mut section = Vec::new();
+        if self.is_documented() {
+            self.serialize_documentation(&mut section)?;
+        }
+        writer.write_u32_be(section.len() as u32)?;
+        writer.write_all(&section)?;
+        if version_client >= RETURN_TYPES_VERSION {
+            let mut section = Vec::new();
+            self.serialize_return_types(&mut section)?;
+            writer.write_u32_be(section.len() as u32)?;
+            writer.write_all(&section)?;
+        }
+        Ok(())
+    }
+
+    /// All type specs of the contract: of fields, variants, arguments, return types and state.
+    fn type_specs(&self) -> impl Iterator<Item = &[u8]> {
+        let types = self
+            .types
+            .iter()
+            .flat_map(|named_type| match &named_type.kind_information {
+                KindInfo::Struct { fields } => fields
+                    .iter()
+                    .map(|field| &field.type_spec[..])
+                    .collect::<Vec<_>>(),
+                KindInfo::Enum { variants } => variants
+                    .iter()
+                    .map(|variant| &variant.type_spec[..])
+                    .collect(),
+            });
+        let functions = self.actions.iter().flat_map(|function| {
+            let args = function.args.iter().chain(&function.secret_arg);
+            args.map(|arg| &arg.type_spec[..])
+                .chain(function.return_type.as_deref())
+        });
+        types
+            .chain(functions)
+            .chain(std::iter::once(&self.state[..]))
+    }
+
+    /// Whether any function declares a return type.
+    fn declares_return_types(&self) -> bool {
+        self.actions
+            .iter()
+            .any(|function| function.return_type.is_some())
+    }
+
+    /// Whether any element of the contract is documented.
+    fn is_documented(&self) -> bool {
+        let types = self.types.iter().any(|named_type| {
+            named_type.doc.is_some() || any_documented(named_type_fields(named_type))
+        });
+        let functions = self
+            .actions
+            .iter()
+            .any(|function| function.doc.is_some() || any_documented(&function.args));
+        types || functions
+    }
+
+    /// Serialize the content of the documentation section.
+    fn serialize_documentation<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+        writer.write_u32_be(self.types.len() as u32)?;
+        for named_type in &self.types {
+            serialize_doc(&named_type.doc, writer)?;
+            serialize_entity_docs(named_type_fields(named_type), writer)?;
+        }
+        writer.write_u32_be(self.actions.len() as u32)?;
+        for function in &self.actions {
+            serialize_doc(&function.doc, writer)?;
+            serialize_entity_docs(&function.args, writer)?;
+        }
+        Ok(())
+    }
+
+    /// Serialize the content of the return type section.
+    fn serialize_return_types<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+        writer.write_u32_be(self.actions.len() as u32)?;
+        for function in &self.actions {
+            match &function.return_type {
+                None => writer.write_u8(0x00)?,
+                Some(type_spec) => {
+                    writer.write_u8(0x01)?;
+                    writer.write_all(type_spec)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The fields of a struct, or no fields for an enum.
+fn named_type_fields(named_type: &NamedTypeSpec) -> &[NamedEntityAbi] {
+    match &named_type.kind_information {
+        KindInfo::Struct { fields } => fields,
+        KindInfo::Enum { .. } => &[],
     }
 }
+
+fn any_documented(entities: &[NamedEntityAbi]) -> bool {
+    entities.iter().any(|entity| entity.doc.is_some())
+}
+
+fn serialize_entity_docs<T: Write>(
+    entities: &[NamedEntityAbi],
+    writer: &mut T,
+) -> std::io::Result<()> {
+    writer.write_u32_be(entities.len() as u32)?;
+    for entity in entities {
+        serialize_doc(&entity.doc, writer)?;
+    }
+    Ok(())
+}
+
+fn serialize_doc<T: Write>(doc: &Option<String>, writer: &mut T) -> std::io::Result<()> {
+    let doc = doc.as_deref().unwrap_or_default();
+    writer.write_u32_be(doc.len() as u32)?;
+    writer.write_all(doc.as_bytes())
+}
@@ -7,6 +7,7 @@ use super::AbiSerialize;
 /// A struct representing an enum variant.
 ///
 /// Serialized with the ABI format.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EnumVariant {
     /// The discriminant of the variant.
     pub discriminant: u8,
@@ -6,18 +6,21 @@ use std::io::Write;
 use pbc_traits::{CreateTypeSpec, WriteRPC};
 use pbc_zk_core::{Sbi32, SecretBinary};
 
-use super::{abi_serialize_slice, AbiSerialize, NamedEntityAbi};
+use super::{abi_serialize_slice, type_spec_from_type, AbiSerialize, NamedEntityAbi};
 use crate::function_name::{FunctionKind, FunctionName};
 use crate::shortname::Shortname;
 
 /// A struct representing a function in the ABI.
 ///
 /// Serialized with the ABI format.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FnAbi {
     name: FunctionName,
     fn_kind: FunctionKind,
-    args: Vec<NamedEntityAbi>,
-    secret_arg: Option<NamedEntityAbi>,
+    pub(super) args: Vec<NamedEntityAbi>,
+    pub(super) secret_arg: Option<NamedEntityAbi>,
+    pub(super) doc: Option<String>,
+    pub(super) return_type: Option<Vec<u8>>,
 }
 
 impl FnAbi {
@@ -33,6 +36,8 @@ impl FnAbi {
             fn_kind,
             args: Vec::new(),
             secret_arg: None,
+            doc: None,
+            return_type: None,
         }
     }
 
@@ -60,6 +65,62 @@ impl FnAbi {
         self.secret_arg = Some(NamedEntityAbi::new::<T>("secret_input".to_string(), lut));
     }
 
+    /// Declare the type of the data returned by the function, as set with
+    /// `EventGroupBuilder::return_data`. Types are inferred.
+    ///
+    /// * `lut` - the lookup table for the ABI generation. See `pbc-abigen` for details.
+    pub fn return_type<T: CreateTypeSpec>(&mut self, lut: &BTreeMap<String, u8>) {
+        self.return_type = Some(type_spec_from_type::<T>(lut));
+    }
+
+    /// Set the documentation of the function.
+    pub fn set_doc(&mut self, doc: String) {
+        self.doc = Some(doc);
+    }
+
+    /// Set the documentation of the argument with the given name.
+    ///
+    /// Panics if the function has no argument with the name.
+    pub fn set_argument_doc(&mut self, name: &str, doc: String) {
+        let argument = self
+            .args
+            .iter_mut()
+            .find(|argument| argument.name == name)
+            .unwrap_or_else(|| panic!("Function has no argument named {name}"));
+        argument.doc = Some(doc);
+    }
+
+    /// The name of the function.
+    pub fn name(&self) -> &FunctionName {
+        &self.name
+    }
+
+    /// The kind of the function.
+    pub fn kind(&self) -> FunctionKind {
+        self.fn_kind
+    }
+
+    /// The arguments of the function.
+    pub fn arguments(&self) -> &[NamedEntityAbi] {
+        &self.args
+    }
+
+    /// The secret argument of the function, for functions of kind
+    /// [`FunctionKind::ZkSecretInputWithExplicitType`].
+    pub fn secret_input(&self) -> Option<&NamedEntityAbi> {
+        self.secret_arg.as_ref()
+    }
+
+    /// The documentation of the function, should it be documented.
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
+    /// The type spec of the data returned by the function, should it declare a return type.
+    pub fn return_type_spec(&self) -> Option<&[u8]> {
+        self.return_type.as_deref()
+    }
+
     /// Add a default secret argument of type Sbi32 to this instance.
     /// Name is "secret_input"
     ///
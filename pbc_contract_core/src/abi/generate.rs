@@ -1,9 +1,9 @@
 use crate::abi::types::NamedTypeSpec;
-use std::collections::BTreeMap;
-use std::io::Write;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
 use std::slice::Iter;
 
-use super::{ContractAbi, FnAbi};
+use super::{AbiFile, AbiHeader, ContractAbi, FnAbi};
 
 /// Cast a raw function pointer to a: `fn(&BTreeMap<String, u8>) -> T`, for any T.
 unsafe fn cast_pointer_unconditionally<T>(ptr: *const ()) -> unsafe fn(&BTreeMap<String, u8>) -> T {
@@ -75,11 +75,18 @@ pub unsafe fn generate_abi(
     let mut contract = ContractAbi::new(state_type.type_spec.to_vec());
     contract.actions(actions);
     contract.types(types);
-    let abi_header_buffer = abi_header_bytes(version_binder, version_client);
+    // The version is raised should the contract use encodings added since.
+    let version_client = version_client.max(contract.client_version());
+    let file = AbiFile {
+        header: AbiHeader {
+            version_binder,
+            version_client,
+        },
+        contract,
+    };
 
     let mut output: Vec<u8> = Vec::new();
-    output.write_all(&abi_header_buffer).unwrap();
-    contract.serialize_abi(&mut output).unwrap();
+    file.serialize_abi(&mut output).unwrap();
 
     let length = output.len() as u64;
     let pointer = output.as_ptr() as u64;
@@ -97,36 +104,25 @@ pub unsafe fn generate_abi(
 pub unsafe fn generate_types(
     iter: Iter<LookupTable<Vec<NamedTypeSpec>>>,
 ) -> (BTreeMap<String, u8>, Vec<NamedTypeSpec>) {
-    // Pass 1: construct the type index lookup table
+    // Pass 1: construct the type index lookup table. Types, such as tuples, may be supplied more
+    // than once, in which case only the first occurrence is used.
     let mut lut: BTreeMap<String, u8> = BTreeMap::new();
     let mut index = 0;
     for type_abi_fn in iter.clone() {
         let type_abis = type_abi_fn(&BTreeMap::new());
         for type_abi in type_abis {
-            lut.insert(type_abi.type_identifier, index as u8);
-            index += 1;
+            if let Entry::Vacant(entry) = lut.entry(type_abi.type_identifier) {
+                entry.insert(index as u8);
+                index += 1;
+            }
         }
     }
 
     // Pass 2: Construct enriched TypeAbi objects
-    let types = iter.flat_map(|type_abi_fn| type_abi_fn(&lut)).collect();
+    let mut seen: BTreeSet<String> = BTreeSet::new();
+    let types = iter
+        .flat_map(|type_abi_fn| type_abi_fn(&lut))
+        .filter(|type_abi| seen.insert(type_abi.type_identifier.clone()))
+        .collect();
     (lut, types)
 }
-
-/// Create a header for the given version
-unsafe fn abi_header_bytes(version_binder: [u8; 3], version_client: [u8; 3]) -> [u8; 12] {
-    let mut bytes = [0u8; 12];
-    for (i, byte) in "PBCABI".as_bytes().iter().enumerate() {
-        bytes[i] = *byte;
-    }
-
-    bytes[6] = version_binder[0];
-    bytes[7] = version_binder[1];
-    bytes[8] = version_binder[2];
-
-    bytes[9] = version_client[0];
-    bytes[10] = version_client[1];
-    bytes[11] = version_client[2];
-
-    bytes
-}
@@ -0,0 +1,560 @@
+//! JSON rendering of contract ABIs, for tooling that does not read the binary format.
+//!
+//! The rendering holds the same information as the binary format, such that it can be converted
+//! back with [`ContractAbi::from_json`]. Objects have the following keys:
+//!
+//! - Contract: `types`, a list of named types whose positions are their indices; `functions`, a
+//!   list of functions; and `state`, the type of the contract state. Rendered
+//!   [`AbiFile`]s additionally have `version_binder` and `version_client`, such as `"5.5.0"`.
+//! - Named type: `name`, and `kind` which is either `"struct"` with a list of `fields`, each with
+//!   a `name` and a `type`, or `"enum"` with a list of `variants`, each with a `discriminant` and
+//!   the `type` of the struct holding its fields.
+//! - Function: `kind`, the snake case name of the [`FunctionKind`] such as `"action"` or
+//!   `"zk_on_secret_input"`; `name`; `shortname`, the LEB128 bytes of the shortname in
+//!   hexadecimal; `arguments`, each with a `name` and a `type`; and `secret_argument` with a
+//!   `name` and a `type`, for functions that have one; and `return_type`, the type of the
+//!   returned data, for functions that declare one.
+//! - Documented named types, fields, functions and arguments additionally have a `doc` string.
+//! - Type: `type`, one of
+//!   - a simple type: `"u8"`, `"u16"`, `"u32"`, `"u64"`, `"u128"`, `"i8"`, `"i16"`, `"i32"`,
+//!     `"i64"`, `"i128"`, `"U256"`, `"String"`, `"bool"`, `"Address"`, `"Hash"`,
+//!     `"PublicKey"`, `"Signature"`, `"BlsPublicKey"` or `"BlsSignature"`;
+//!   - `"Named"` with the `index` and resolved `name` of the named type;
+//!   - `"Vec"`, `"Set"` or `"Option"` with the `element` type;
+//!   - `"Map"` or `"AvlTreeMap"` with the `key` and `value` types;
+//!   - `"ByteArray"` with a `length` of at most 127, for the compact encoding of byte arrays;
+//!   - `"Array"` with the `element` type and `length`.
+//!
+//!
+//! The rendering is indented text, with the keys of each object in alphabetical order.
+//!
+//! ```
+//! # use pbc_contract_core::abi::ContractAbi;
+//! let json = r#"{
+//!     "types": [],
+//!     "functions": [{
+//!         "kind": "action",
+//!         "name": "transfer",
+//!         "shortname": "01",
+//!         "arguments": [{ "name": "amounts", "type": { "type": "Vec", "element": { "type": "u128" } } }]
+//!     }],
+//!     "state": { "type": "Map", "key": { "type": "Address" }, "value": { "type": "u128" } }
+//! }"#;
+//! let abi = ContractAbi::from_json(json).unwrap();
+//! assert_eq!(abi.state_type_spec(), &[0x0f, 0x0d, 0x05]);
+//! assert_eq!(ContractAbi::from_json(&abi.to_json()), Ok(abi));
+//! ```
+
+use std::fmt;
+
+use serde_json::{json, Value};
+
+use super::{AbiFile, AbiHeader, ContractAbi, EnumVariant, FnAbi, NamedEntityAbi};
+use super::{KindInfo, NamedTypeSpec};
+use crate::function_name::{FunctionKind, FunctionName};
+use crate::shortname::Shortname;
+
+/// Types represented by a single ordinal, by their name in the JSON rendering.
+const SIMPLE_TYPES: [(u8, &str); 19] = [
+    (0x01, "u8"),
+    (0x02, "u16"),
+    (0x03, "u32"),
+    (0x04, "u64"),
+    (0x05, "u128"),
+    (0x06, "i8"),
+    (0x07, "i16"),
+    (0x08, "i32"),
+    (0x09, "i64"),
+    (0x0a, "i128"),
+    (0x0b, "String"),
+    (0x0c, "bool"),
+    (0x0d, "Address"),
+    (0x13, "Hash"),
+    (0x14, "PublicKey"),
+    (0x15, "Signature"),
+    (0x16, "BlsPublicKey"),
+    (0x17, "BlsSignature"),
+    (0x18, "U256"),
+];
+
+/// Function kinds, by their name in the JSON rendering.
+#[allow(deprecated)]
+const FUNCTION_KINDS: [(FunctionKind, &str); 12] = [
+    (FunctionKind::Init, "init"),
+    (FunctionKind::Action, "action"),
+    (FunctionKind::Callback, "callback"),
+    (FunctionKind::ZkSecretInput, "zk_on_secret_input"),
+    (FunctionKind::ZkVarInputted, "zk_on_variable_inputted"),
+    (FunctionKind::ZkVarRejected, "zk_on_variable_rejected"),
+    (FunctionKind::ZkComputeComplete, "zk_on_compute_complete"),
+    (FunctionKind::ZkVarOpened, "zk_on_variable_opened"),
+    (FunctionKind::ZkUserVarOpened, "zk_on_user_variable_opened"),
+    (
+        FunctionKind::ZkAttestationComplete,
+        "zk_on_attestation_complete",
+    ),
+    (
+        FunctionKind::ZkSecretInputWithExplicitType,
+        "zk_on_secret_input_with_explicit_type",
+    ),
+    (FunctionKind::ZkExternalEvent, "zk_on_external_event"),
+];
+
+impl ContractAbi {
+    /// Render this ABI as JSON, following the schema of the [module documentation](self).
+    ///
+    /// Panics if a type spec is not valid, which cannot happen for generated and parsed ABIs.
+    pub fn to_json(&self) -> String {
+        to_text(&contract_json(self))
+    }
+
+    /// Create an ABI from its JSON rendering, following the schema of the
+    /// [module documentation](self).
+    ///
+    /// Named types are given their name as type identifier, as when parsing an ABI file.
+    pub fn from_json(json: &str) -> Result<Self, AbiJsonError> {
+        contract_from_json(&parse(json)?)
+    }
+}
+
+impl AbiFile {
+    /// Render this file as JSON: the rendering of the contract, with the versions of the header.
+    pub fn to_json(&self) -> String {
+        let mut value = contract_json(&self.contract);
+        let [major, minor, patch] = self.header.version_binder;
+        value["version_binder"] = format!("{major}.{minor}.{patch}").into();
+        let [major, minor, patch] = self.header.version_client;
+        value["version_client"] = format!("{major}.{minor}.{patch}").into();
+        to_text(&value)
+    }
+
+    /// Create a file from its JSON rendering. Together with [`AbiFile::serialize_abi`] this
+    /// converts JSON to the binary format.
+    pub fn from_json(json: &str) -> Result<Self, AbiJsonError> {
+        let value = parse(json)?;
+        let header = AbiHeader {
+            version_binder: version_from_json(&value, "version_binder")?,
+            version_client: version_from_json(&value, "version_client")?,
+        };
+        let contract = contract_from_json(&value)?;
+        Ok(AbiFile { header, contract })
+    }
+}
+
+/// Error from creating an ABI from JSON, at the first value that does not follow the schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiJsonError {
+    /// Path to the value, such as `functions[1].arguments[0].type`, or the position in the text,
+    /// such as `line 1 column 12`, should the text not be JSON.
+    pub path: String,
+    /// Description of what was expected at the path.
+    pub expected: &'static str,
+}
+
+impl fmt::Display for AbiJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {} at {}", self.expected, self.path)
+    }
+}
+
+impl std::error::Error for AbiJsonError {}
+
+fn parse(json: &str) -> Result<Value, AbiJsonError> {
+    serde_json::from_str(json).map_err(|error| AbiJsonError {
+        path: format!("line {} column {}", error.line(), error.column()),
+        expected: "JSON",
+    })
+}
+
+fn to_text(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap()
+}
+
+fn contract_json(contract: &ContractAbi) -> Value {
+    let types = contract.named_types();
+    let named_types: Vec<Value> = types
+        .iter()
+        .map(|named_type| named_type_json(named_type, types))
+        .collect();
+    let functions: Vec<Value> = contract
+        .functions()
+        .iter()
+        .map(|function| function_json(function, types))
+        .collect();
+    json!({
+        "types": named_types,
+        "functions": functions,
+        "state": type_json(contract.state_type_spec(), types),
+    })
+}
+
+fn contract_from_json(value: &Value) -> Result<ContractAbi, AbiJsonError> {
+    let types = list(value, "", "types")?;
+    let type_count = types.len();
+    let types = types
+        .iter()
+        .enumerate()
+        .map(|(index, named_type)| {
+            let path = format!("types[{index}]");
+            let mut named_type = named_type_from_json(named_type, &path, type_count)?;
+            named_type.type_spec = vec![0x00, index as u8];
+            Ok(named_type)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let functions = list(value, "", "functions")?
+        .iter()
+        .enumerate()
+        .map(|(index, function)| {
+            function_from_json(function, &format!("functions[{index}]"), type_count)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let state = type_from_json(field(value, "", "state")?, "state", type_count)?;
+
+    let mut contract = ContractAbi::new(state);
+    contract.types(types);
+    contract.actions(functions);
+    Ok(contract)
+}
+
+fn named_type_json(named_type: &NamedTypeSpec, types: &[NamedTypeSpec]) -> Value {
+    let value = match &named_type.kind_information {
+        KindInfo::Struct { fields } => {
+            let fields: Vec<Value> = fields
+                .iter()
+                .map(|field| entity_json(field, types))
+                .collect();
+            json!({ "name": named_type.name, "kind": "struct", "fields": fields })
+        }
+        KindInfo::Enum { variants } => {
+            let variants: Vec<Value> = variants
+                .iter()
+                .map(|variant| {
+                    json!({
+                        "discriminant": variant.discriminant,
+                        "type": type_json(&variant.type_spec, types),
+                    })
+                })
+                .collect();
+            json!({ "name": named_type.name, "kind": "enum", "variants": variants })
+        }
+    };
+    with_doc(value, named_type.doc.as_deref())
+}
+
+fn function_json(function: &FnAbi, types: &[NamedTypeSpec]) -> Value {
+    let kind = FUNCTION_KINDS
+        .iter()
+        .find(|(kind, _)| *kind == function.kind())
+        .map(|(_, name)| *name)
+        .unwrap();
+    let arguments: Vec<Value> = function
+        .arguments()
+        .iter()
+        .map(|argument| entity_json(argument, types))
+        .collect();
+    let mut value = json!({
+        "kind": kind,
+        "name": function.name().name(),
+        "shortname": function.name().shortname().to_string(),
+        "arguments": arguments,
+    });
+    if let Some(secret_argument) = function.secret_input() {
+        value["secret_argument"] = entity_json(secret_argument, types);
+    }
+    if let Some(return_type) = function.return_type_spec() {
+        value["return_type"] = type_json(return_type, types);
+    }
+    with_doc(value, function.doc())
+}
+
+fn entity_json(entity: &NamedEntityAbi, types: &[NamedTypeSpec]) -> Value {
+    let value = json!({ "name": entity.name, "type": type_json(&entity.type_spec, types) });
+    with_doc(value, entity.doc.as_deref())
+}
+
+/// Adds the documentation to the rendered object, should the element be documented.
+fn with_doc(mut value: Value, doc: Option<&str>) -> Value {
+    if let Some(doc) = doc {
+        value["doc"] = doc.into();
+    }
+    value
+}
+
+fn type_json(type_spec: &[u8], types: &[NamedTypeSpec]) -> Value {
+    let (value, rest) = type_json_prefix(type_spec, types);
+    assert!(rest.is_empty(), "Invalid type spec {type_spec:?}");
+    value
+}
+
+/// Render the type spec at the start of the given bytes, returning the remaining bytes.
+fn type_json_prefix<'a>(type_spec: &'a [u8], types: &[NamedTypeSpec]) -> (Value, &'a [u8]) {
+    let (&ordinal, rest) = type_spec
+        .split_first()
+        .unwrap_or_else(|| invalid(type_spec));
+    if let Some((_, name)) = SIMPLE_TYPES.iter().find(|(simple, _)| *simple == ordinal) {
+        return (json!({ "type": name }), rest);
+    }
+    match ordinal {
+        0x00 => {
+            let (&index, rest) = rest.split_first().unwrap_or_else(|| invalid(type_spec));
+            let name = types
+                .get(usize::from(index))
+                .map(|named_type| &named_type.name);
+            let value = json!({ "type": "Named", "index": index, "name": name });
+            (value, rest)
+        }
+        0x0e | 0x10 | 0x12 => {
+            let (element, rest) = type_json_prefix(rest, types);
+            let kind = match ordinal {
+                0x0e => "Vec",
+                0x10 => "Set",
+                _ => "Option",
+            };
+            let value = json!({ "type": kind, "element": element });
+            (value, rest)
+        }
+        0x0f | 0x19 => {
+            let (key, rest) = type_json_prefix(rest, types);
+            let (value, rest) = type_json_prefix(rest, types);
+            let kind = if ordinal == 0x0f { "Map" } else { "AvlTreeMap" };
+            let value = json!({ "type": kind, "key": key, "value": value });
+            (value, rest)
+        }
+        0x11 => {
+            let (&length, rest) = rest.split_first().unwrap_or_else(|| invalid(type_spec));
+            let value = json!({ "type": "ByteArray", "length": length });
+            (value, rest)
+        }
+        0x1a => {
+            let (element, rest) = type_json_prefix(rest, types);
+            if rest.len() < 4 {
+                invalid(type_spec)
+            }
+            let (length, rest) = rest.split_at(4);
+            let length = u32::from_be_bytes(length.try_into().unwrap());
+            let value = json!({ "type": "Array", "element": element, "length": length });
+            (value, rest)
+        }
+        _ => invalid(type_spec),
+    }
+}
+
+fn invalid<T>(type_spec: &[u8]) -> T {
+    panic!("Invalid type spec {type_spec:?}")
+}
+
+fn error<T>(path: &str, expected: &'static str) -> Result<T, AbiJsonError> {
+    Err(AbiJsonError {
+        path: path.to_string(),
+        expected,
+    })
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+fn field<'a>(value: &'a Value, path: &str, key: &str) -> Result<&'a Value, AbiJsonError> {
+    match value.get(key) {
+        Some(field) => Ok(field),
+        None => error(&join(path, key), "a value"),
+    }
+}
+
+fn string<'a>(value: &'a Value, path: &str, key: &str) -> Result<&'a str, AbiJsonError> {
+    match field(value, path, key)?.as_str() {
+        Some(string) => Ok(string),
+        None => error(&join(path, key), "a string"),
+    }
+}
+
+fn integer<T: TryFrom<u64>>(value: &Value, path: &str, key: &str) -> Result<T, AbiJsonError> {
+    match field(value, path, key)?
+        .as_u64()
+        .and_then(|integer| T::try_from(integer).ok())
+    {
+        Some(integer) => Ok(integer),
+        None => error(&join(path, key), "an integer in range"),
+    }
+}
+
+/// The optional `doc` string of the object at `path`.
+fn doc_from_json(value: &Value, path: &str) -> Result<Option<String>, AbiJsonError> {
+    match value.get("doc") {
+        None => Ok(None),
+        Some(_) => Ok(Some(string(value, path, "doc")?.to_string())),
+    }
+}
+
+fn list<'a>(value: &'a Value, path: &str, key: &str) -> Result<&'a [Value], AbiJsonError> {
+    match field(value, path, key)?.as_array() {
+        Some(list) => Ok(list),
+        None => error(&join(path, key), "a list"),
+    }
+}
+
+fn version_from_json(value: &Value, key: &str) -> Result<[u8; 3], AbiJsonError> {
+    let version = string(value, "", key)?;
+    let parts: Vec<Option<u8>> = version.split('.').map(|part| part.parse().ok()).collect();
+    match parts[..] {
+        [Some(major), Some(minor), Some(patch)] => Ok([major, minor, patch]),
+        _ => error(key, "a version such as \"5.5.0\""),
+    }
+}
+
+fn named_type_from_json(
+    value: &Value,
+    path: &str,
+    type_count: usize,
+) -> Result<NamedTypeSpec, AbiJsonError> {
+    let name = string(value, path, "name")?.to_string();
+    let doc = doc_from_json(value, path)?;
+    let mut named_type = match string(value, path, "kind")? {
+        "struct" => {
+            let mut named_type = NamedTypeSpec::new_struct(name.clone(), name, vec![]);
+            for (index, field) in list(value, path, "fields")?.iter().enumerate() {
+                let path = format!("{path}.fields[{index}]");
+                named_type.add_field(entity_from_json(field, &path, type_count)?);
+            }
+            named_type
+        }
+        "enum" => {
+            let mut named_type = NamedTypeSpec::new_enum(name.clone(), name, vec![]);
+            for (index, variant) in list(value, path, "variants")?.iter().enumerate() {
+                let path = format!("{path}.variants[{index}]");
+                let discriminant = integer(variant, &path, "discriminant")?;
+                let type_spec = type_from_json(
+                    field(variant, &path, "type")?,
+                    &join(&path, "type"),
+                    type_count,
+                )?;
+                named_type.add_variant(EnumVariant::new(discriminant, type_spec));
+            }
+            named_type
+        }
+        _ => return error(&join(path, "kind"), "\"struct\" or \"enum\""),
+    };
+    named_type.doc = doc;
+    Ok(named_type)
+}
+
+fn function_from_json(value: &Value, path: &str, type_count: usize) -> Result<FnAbi, AbiJsonError> {
+    let kind = string(value, path, "kind")?;
+    let Some((fn_kind, _)) = FUNCTION_KINDS.iter().find(|(_, name)| *name == kind) else {
+        return error(&join(path, "kind"), "a function kind");
+    };
+    let name = string(value, path, "name")?.to_string();
+    let Some(shortname) = shortname_from_json(string(value, path, "shortname")?) else {
+        return error(&join(path, "shortname"), "LEB128 bytes in hexadecimal");
+    };
+
+    let mut function = FnAbi::from_name(FunctionName::new(name, Some(shortname)), *fn_kind);
+    function.doc = doc_from_json(value, path)?;
+    for (index, argument) in list(value, path, "arguments")?.iter().enumerate() {
+        let path = format!("{path}.arguments[{index}]");
+        function
+            .args
+            .push(entity_from_json(argument, &path, type_count)?);
+    }
+    if let Some(secret_argument) = value.get("secret_argument") {
+        let path = join(path, "secret_argument");
+        function.secret_arg = Some(entity_from_json(secret_argument, &path, type_count)?);
+    }
+    if let Some(return_type) = value.get("return_type") {
+        let path = join(path, "return_type");
+        function.return_type = Some(type_from_json(return_type, &path, type_count)?);
+    }
+    Ok(function)
+}
+
+fn shortname_from_json(hex: &str) -> Option<Shortname> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    Shortname::from_be_bytes(&bytes).ok()
+}
+
+fn entity_from_json(
+    value: &Value,
+    path: &str,
+    type_count: usize,
+) -> Result<NamedEntityAbi, AbiJsonError> {
+    let name = string(value, path, "name")?.to_string();
+    let type_spec = type_from_json(field(value, path, "type")?, &join(path, "type"), type_count)?;
+    let mut entity = NamedEntityAbi::from_type_spec(name, type_spec);
+    entity.doc = doc_from_json(value, path)?;
+    Ok(entity)
+}
+
+/// The type spec of the rendered type found at `path`.
+fn type_from_json(value: &Value, path: &str, type_count: usize) -> Result<Vec<u8>, AbiJsonError> {
+    let mut type_spec = vec![];
+    write_type_from_json(value, path, type_count, &mut type_spec)?;
+    Ok(type_spec)
+}
+
+fn write_type_from_json(
+    value: &Value,
+    path: &str,
+    type_count: usize,
+    type_spec: &mut Vec<u8>,
+) -> Result<(), AbiJsonError> {
+    let kind = string(value, path, "type")?;
+    if let Some((ordinal, _)) = SIMPLE_TYPES.iter().find(|(_, name)| *name == kind) {
+        type_spec.push(*ordinal);
+        return Ok(());
+    }
+    let nested = |key: &str, type_spec: &mut Vec<u8>| {
+        write_type_from_json(
+            field(value, path, key)?,
+            &join(path, key),
+            type_count,
+            type_spec,
+        )
+    };
+    match kind {
+        "Named" => {
+            let index: u8 = integer(value, path, "index")?;
+            if usize::from(index) >= type_count {
+                return error(&join(path, "index"), "the index of a named type");
+            }
+            type_spec.extend([0x00, index]);
+        }
+        "Vec" | "Set" | "Option" => {
+            type_spec.push(match kind {
+                "Vec" => 0x0e,
+                "Set" => 0x10,
+                _ => 0x12,
+            });
+            nested("element", type_spec)?;
+        }
+        "Map" | "AvlTreeMap" => {
+            type_spec.push(if kind == "Map" { 0x0f } else { 0x19 });
+            nested("key", type_spec)?;
+            nested("value", type_spec)?;
+        }
+        "ByteArray" => {
+            let length: u8 = integer(value, path, "length")?;
+            if length > 0x7F {
+                return error(&join(path, "length"), "a length of at most 127");
+            }
+            type_spec.extend([0x11, length]);
+        }
+        "Array" => {
+            type_spec.push(0x1a);
+            nested("element", type_spec)?;
+            let length: u32 = integer(value, path, "length")?;
+            type_spec.extend(length.to_be_bytes());
+        }
+        _ => return error(&join(path, "type"), "a type"),
+    }
+    Ok(())
+}
@@ -6,16 +6,24 @@ use std::io::Write;
 pub use contract::ContractAbi;
 pub use enum_variant::EnumVariant;
 pub use func::FnAbi;
+#[cfg(feature = "abi_json")]
+pub use json::AbiJsonError;
 pub use named_entity::NamedEntityAbi;
 use pbc_traits::WriteInt;
-pub use types::{capitalize, NamedTypeSpec};
+pub use read::{AbiFile, AbiHeader, AbiReadError};
+pub use tuple::{abi_for_tuple, AbiTuple};
+pub use types::{capitalize, KindInfo, NamedTypeSpec};
 
 mod contract;
 mod enum_variant;
 mod func;
 /// ABI generation goes through this module.
 pub mod generate;
+#[cfg(feature = "abi_json")]
+pub mod json;
 mod named_entity;
+mod read;
+mod tuple;
 mod types;
 
 /// Serialize this struct according to the ABI specification.
@@ -10,7 +10,7 @@ use super::{type_spec_from_type, AbiSerialize};
 /// In this case it is function arguments and struct fields.
 ///
 /// Serialized with the ABI format.
-#[derive(WriteRPC)]
+#[derive(WriteRPC, Debug, Clone, PartialEq, Eq)]
 pub struct NamedEntityAbi {
     /// The name of the field or argument.
     pub name: String,
@@ -18,6 +18,8 @@ pub struct NamedEntityAbi {
     pub type_spec: Vec<u8>,
     /// The type index should one exist.
     pub type_index: Option<u8>,
+    /// The documentation of the field or argument, should it be documented.
+    pub doc: Option<String>,
 }
 
 impl NamedEntityAbi {
@@ -33,6 +35,22 @@ impl NamedEntityAbi {
             name,
             type_spec: type_spec_from_type::<T>(lut),
             type_index,
+            doc: None,
+        }
+    }
+
+    /// Instantiate a `NamedEntityAbi` from an existing type spec, such as one read from an ABI
+    /// file. Only type specs referencing a named type have a type index.
+    pub(super) fn from_type_spec(name: String, type_spec: Vec<u8>) -> Self {
+        let type_index = match type_spec[..] {
+            [0x00, index] => Some(index),
+            _ => None,
+        };
+        NamedEntityAbi {
+            name,
+            type_spec,
+            type_index,
+            doc: None,
         }
     }
 }
@@ -65,7 +83,7 @@ mod test {
 
         assert_eq!(
             output,
-            vec![0, 0, 0, 7, 109, 121, 95, 110, 97, 109, 101, 0, 0, 0, 1, 11, 0]
+            vec![0, 0, 0, 7, 109, 121, 95, 110, 97, 109, 101, 0, 0, 0, 1, 11, 0, 0]
         )
     }
 }
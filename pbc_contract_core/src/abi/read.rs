@@ -0,0 +1,481 @@
+//! Parsing of ABI files, as produced by [`generate_abi`](super::generate::generate_abi).
+
+use std::fmt;
+use std::io::Write;
+
+use super::{ContractAbi, EnumVariant, FnAbi, KindInfo, NamedEntityAbi, NamedTypeSpec};
+use crate::function_name::{FunctionKind, FunctionName};
+use crate::shortname::Shortname;
+
+/// The header of an ABI file, identifying the versions of the format.
+///
+/// Minor versions of the client format add encodings that readers of earlier versions reject, so
+/// files are only read by readers supporting their version:
+///
+/// - 5.5.0: Arrays other than byte arrays of at most 127 elements have the type ordinal `0x1a`.
+/// - 5.6.0: The state type is followed by the documentation section.
+/// - 5.7.0: The documentation section is followed by the return type section.
+///
+/// Files are given the earliest version able to represent the contract, see
+/// [`ContractAbi::client_version`]. Contracts using none of these encodings thus keep version
+/// 5.4.0, and can still be read by readers of that version.
+///
+/// The layout following the header is described by [`ContractAbi`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AbiHeader {
+    /// Version of the binder the contract was compiled for, as `[major, minor, patch]`.
+    pub version_binder: [u8; 3],
+    /// Version of the ABI format used by clients, as `[major, minor, patch]`.
+    pub version_client: [u8; 3],
+}
+
+impl AbiHeader {
+    /// Magic bytes that every ABI file starts with.
+    pub const MAGIC: &'static [u8; 6] = b"PBCABI";
+
+    /// Latest client version of the ABI format that can be read. Files of earlier minor versions
+    /// of the same major version can also be read.
+    pub const SUPPORTED_CLIENT_VERSION: [u8; 3] = [5, 7, 0];
+
+    /// The serialized header: the magic bytes followed by the binder and client versions.
+    pub fn bytes(&self) -> [u8; 12] {
+        let mut bytes = [0u8; 12];
+        bytes[..6].copy_from_slice(Self::MAGIC);
+        bytes[6..9].copy_from_slice(&self.version_binder);
+        bytes[9..].copy_from_slice(&self.version_client);
+        bytes
+    }
+}
+
+/// A parsed ABI file, consisting of a header and the ABI of the contract.
+///
+/// ```
+/// # use pbc_contract_core::abi::{AbiFile, AbiHeader, ContractAbi};
+/// let header = AbiHeader {
+///     version_binder: [10, 2, 0],
+///     version_client: [5, 5, 0],
+/// };
+/// let mut bytes = header.bytes().to_vec();
+/// bytes.extend([0, 0, 0, 0, 0, 0, 0, 0, 0x04]); // No types, no functions, and u64 state.
+///
+/// let file = AbiFile::parse(&bytes).unwrap();
+/// assert_eq!(file.header, header);
+/// assert_eq!(file.contract.state_type_spec(), &[0x04]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiFile {
+    /// The header of the file.
+    pub header: AbiHeader,
+    /// The ABI of the contract.
+    pub contract: ContractAbi,
+}
+
+impl AbiFile {
+    /// Parse an ABI file.
+    ///
+    /// Named types are given their name as type identifier, and the type spec referencing them
+    /// by their index, like the type specs of generated named types. Elements are documented by
+    /// the documentation section, and functions given their return types by the return type
+    /// section, should the client version of the file have these.
+    pub fn parse(bytes: &[u8]) -> Result<Self, AbiReadError> {
+        let mut reader = AbiReader {
+            bytes,
+            version_client: AbiHeader::SUPPORTED_CLIENT_VERSION,
+            referenced_types: vec![],
+            wide_arrays: false,
+        };
+        let header = reader.header()?;
+        let contract = reader.contract()?;
+        if !reader.bytes.is_empty() {
+            return Err(AbiReadError::TrailingBytes);
+        }
+        Ok(AbiFile { header, contract })
+    }
+
+    /// Serialize this file according to the ABI specification, in the client version of the
+    /// header.
+    pub fn serialize_abi<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+        writer.write_all(&self.header.bytes())?;
+        self.contract
+            .serialize_abi_for_version(self.header.version_client, writer)
+    }
+}
+
+/// Error from parsing an ABI file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbiReadError {
+    /// The file does not start with the `PBCABI` magic bytes.
+    InvalidHeader,
+    /// The file uses a client version of the format that cannot be read, being of another major
+    /// version or a later minor version than [`AbiHeader::SUPPORTED_CLIENT_VERSION`].
+    UnsupportedVersion {
+        /// The client version of the file.
+        version_client: [u8; 3],
+    },
+    /// The file ends within an element.
+    UnexpectedEnd,
+    /// The file continues after the last element of its client version.
+    TrailingBytes,
+    /// The documentation section does not match the named types and functions.
+    InvalidDocumentation,
+    /// The return type section does not match the functions.
+    InvalidReturnTypes,
+    /// A name is not valid UTF-8.
+    InvalidName,
+    /// A shortname is not a valid LEB128 encoded `u32`.
+    InvalidShortname,
+    /// A named type is neither a struct nor an enum.
+    UnknownTypeKind(u8),
+    /// A function has an unknown kind.
+    UnknownFunctionKind(u8),
+    /// A type spec contains an unknown type ordinal.
+    UnknownTypeOrdinal(u8),
+    /// A type spec references a named type that does not exist.
+    UnknownNamedType(u8),
+}
+
+impl fmt::Display for AbiReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbiReadError::InvalidHeader => write!(f, "missing PBCABI header"),
+            AbiReadError::UnsupportedVersion {
+                version_client: [major, minor, patch],
+            } => write!(f, "unsupported client version {major}.{minor}.{patch}"),
+            AbiReadError::UnexpectedEnd => write!(f, "unexpected end of ABI"),
+            AbiReadError::TrailingBytes => write!(f, "unexpected bytes after the ABI"),
+            AbiReadError::InvalidDocumentation => write!(f, "invalid documentation section"),
+            AbiReadError::InvalidReturnTypes => write!(f, "invalid return type section"),
+            AbiReadError::InvalidName => write!(f, "name is not valid UTF-8"),
+            AbiReadError::InvalidShortname => write!(f, "invalid shortname"),
+            AbiReadError::UnknownTypeKind(kind) => write!(f, "unknown named type kind {kind}"),
+            AbiReadError::UnknownFunctionKind(kind) => {
+                write!(f, "unknown function kind 0x{kind:02x}")
+            }
+            AbiReadError::UnknownTypeOrdinal(ordinal) => {
+                write!(f, "unknown type ordinal 0x{ordinal:02x}")
+            }
+            AbiReadError::UnknownNamedType(index) => write!(f, "unknown named type {index}"),
+        }
+    }
+}
+
+impl std::error::Error for AbiReadError {}
+
+/// Client version of files using none of the encodings added by later minor versions.
+pub(super) const BASE_CLIENT_VERSION: [u8; 3] = [5, 4, 0];
+
+/// Client version introducing the `0x1a` type ordinal for arrays.
+pub(super) const WIDE_ARRAYS_VERSION: [u8; 3] = [5, 5, 0];
+
+/// Client version introducing the documentation section.
+pub(super) const DOCUMENTATION_VERSION: [u8; 3] = [5, 6, 0];
+
+/// Client version introducing the return type section.
+pub(super) const RETURN_TYPES_VERSION: [u8; 3] = [5, 7, 0];
+
+/// Whether the given type spec contains arrays with the `0x1a` ordinal, which require client
+/// version 5.5.0. Ordinals are told apart from the indices and lengths that follow them.
+pub(super) fn contains_wide_array(type_spec: &[u8]) -> bool {
+    let mut reader = AbiReader {
+        bytes: type_spec,
+        version_client: AbiHeader::SUPPORTED_CLIENT_VERSION,
+        referenced_types: vec![],
+        wide_arrays: false,
+    };
+    // A malformed type spec is read until the first unknown ordinal.
+    let _ = reader.type_spec();
+    reader.wide_arrays
+}
+
+/// Elements of a type spec that remain to be read.
+enum Pending {
+    /// A complete type spec.
+    TypeSpec,
+    /// The big endian `u32` length following the element type of a wide array.
+    ArrayLength,
+}
+
+/// The documentation of a named type or function, and of its fields or arguments.
+struct ElementDocs {
+    doc: Option<String>,
+    member_docs: Vec<Option<String>>,
+}
+
+/// Documents the given fields or arguments, which must be as many as the documentation strings.
+fn document_entities(
+    entities: &mut [NamedEntityAbi],
+    docs: Vec<Option<String>>,
+) -> Result<(), AbiReadError> {
+    if entities.len() != docs.len() {
+        return Err(AbiReadError::InvalidDocumentation);
+    }
+    for (entity, doc) in entities.iter_mut().zip(docs) {
+        entity.doc = doc;
+    }
+    Ok(())
+}
+
+/// Reads the elements of an ABI file from the front of a slice.
+struct AbiReader<'a> {
+    bytes: &'a [u8],
+    /// Client version of the file, determining the encodings that are allowed.
+    version_client: [u8; 3],
+    /// Indices of the named types referenced by the type specs read so far.
+    referenced_types: Vec<u8>,
+    /// Whether the type specs read so far contain arrays with the `0x1a` ordinal.
+    wide_arrays: bool,
+}
+
+impl<'a> AbiReader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], AbiReadError> {
+        if self.bytes.len() < count {
+            return Err(AbiReadError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, AbiReadError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, AbiReadError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String, AbiReadError> {
+        let length = self.u32()? as usize;
+        let bytes = self.take(length)?.to_vec();
+        String::from_utf8(bytes).map_err(|_| AbiReadError::InvalidName)
+    }
+
+    fn shortname(&mut self) -> Result<Shortname, AbiReadError> {
+        let length = match self.bytes.iter().position(|byte| *byte < 0x80) {
+            Some(last) if last < 5 => last + 1,
+            Some(_) => return Err(AbiReadError::InvalidShortname),
+            None if self.bytes.len() < 5 => return Err(AbiReadError::UnexpectedEnd),
+            None => return Err(AbiReadError::InvalidShortname),
+        };
+        Shortname::from_be_bytes(self.take(length)?).map_err(|_| AbiReadError::InvalidShortname)
+    }
+
+    fn list<T>(
+        &mut self,
+        mut read_element: impl FnMut(&mut Self) -> Result<T, AbiReadError>,
+    ) -> Result<Vec<T>, AbiReadError> {
+        let length = self.u32()?;
+        // The length is not trusted for preallocation, as each element is at least a byte.
+        let mut elements = Vec::with_capacity(self.bytes.len().min(length as usize));
+        for _ in 0..length {
+            elements.push(read_element(self)?);
+        }
+        Ok(elements)
+    }
+
+    fn header(&mut self) -> Result<AbiHeader, AbiReadError> {
+        if self.take(6).ok() != Some(AbiHeader::MAGIC) {
+            return Err(AbiReadError::InvalidHeader);
+        }
+        let version_binder = self.take(3)?.try_into().unwrap();
+        let version_client: [u8; 3] = self.take(3)?.try_into().unwrap();
+        let [supported_major, supported_minor, _] = AbiHeader::SUPPORTED_CLIENT_VERSION;
+        if version_client[0] != supported_major || version_client[1] > supported_minor {
+            return Err(AbiReadError::UnsupportedVersion { version_client });
+        }
+        self.version_client = version_client;
+        Ok(AbiHeader {
+            version_binder,
+            version_client,
+        })
+    }
+
+    fn contract(&mut self) -> Result<ContractAbi, AbiReadError> {
+        let types = self.list(Self::named_type)?;
+        let mut types = types
+            .into_iter()
+            .enumerate()
+            .map(|(index, mut named_type)| {
+                named_type.type_spec = vec![0x00, index as u8];
+                named_type
+            })
+            .collect::<Vec<_>>();
+        let mut actions = self.list(Self::function)?;
+        let state = self.type_spec()?;
+        if self.version_client >= DOCUMENTATION_VERSION {
+            self.documentation(&mut types, &mut actions)?;
+        }
+        if self.version_client >= RETURN_TYPES_VERSION {
+            self.return_types(&mut actions)?;
+        }
+
+        if let Some(index) = self
+            .referenced_types
+            .iter()
+            .find(|index| usize::from(**index) >= types.len())
+        {
+            return Err(AbiReadError::UnknownNamedType(*index));
+        }
+
+        let mut contract = ContractAbi::new(state);
+        contract.types(types);
+        contract.actions(actions);
+        Ok(contract)
+    }
+
+    fn named_type(&mut self) -> Result<NamedTypeSpec, AbiReadError> {
+        let kind = self.u8()?;
+        let name = self.string()?;
+        let mut named_type = match kind {
+            1 => NamedTypeSpec::new_struct(name.clone(), name, vec![]),
+            2 => NamedTypeSpec::new_enum(name.clone(), name, vec![]),
+            _ => return Err(AbiReadError::UnknownTypeKind(kind)),
+        };
+        if kind == 1 {
+            for field in self.list(Self::named_entity)? {
+                named_type.add_field(field);
+            }
+        } else {
+            for variant in self.list(Self::enum_variant)? {
+                named_type.add_variant(variant);
+            }
+        }
+        Ok(named_type)
+    }
+
+    fn enum_variant(&mut self) -> Result<EnumVariant, AbiReadError> {
+        let discriminant = self.u8()?;
+        Ok(EnumVariant::new(discriminant, self.type_spec()?))
+    }
+
+    fn named_entity(&mut self) -> Result<NamedEntityAbi, AbiReadError> {
+        let name = self.string()?;
+        Ok(NamedEntityAbi::from_type_spec(name, self.type_spec()?))
+    }
+
+    fn function(&mut self) -> Result<FnAbi, AbiReadError> {
+        let kind = self.u8()?;
+        let fn_kind = FunctionKind::from_u8(kind).ok_or(AbiReadError::UnknownFunctionKind(kind))?;
+        let name = self.string()?;
+        let shortname = self.shortname()?;
+        let mut function = FnAbi::from_name(FunctionName::new(name, Some(shortname)), fn_kind);
+        function.args = self.list(Self::named_entity)?;
+        if fn_kind == FunctionKind::ZkSecretInputWithExplicitType {
+            function.secret_arg = Some(self.named_entity()?);
+        }
+        Ok(function)
+    }
+
+    /// Read the documentation section, documenting the given named types and functions. The
+    /// section is empty when nothing is documented.
+    fn documentation(
+        &mut self,
+        types: &mut [NamedTypeSpec],
+        functions: &mut [FnAbi],
+    ) -> Result<(), AbiReadError> {
+        let length = self.u32()? as usize;
+        if length == 0 {
+            return Ok(());
+        }
+        let mut section = AbiReader {
+            bytes: self.take(length)?,
+            version_client: self.version_client,
+            referenced_types: vec![],
+            wide_arrays: false,
+        };
+        let type_docs = section.list(AbiReader::element_docs)?;
+        let function_docs = section.list(AbiReader::element_docs)?;
+        if !section.bytes.is_empty()
+            || type_docs.len() != types.len()
+            || function_docs.len() != functions.len()
+        {
+            return Err(AbiReadError::InvalidDocumentation);
+        }
+
+        for (named_type, docs) in types.iter_mut().zip(type_docs) {
+            named_type.doc = docs.doc;
+            match &mut named_type.kind_information {
+                KindInfo::Struct { fields } => document_entities(fields, docs.member_docs)?,
+                KindInfo::Enum { .. } => document_entities(&mut [], docs.member_docs)?,
+            }
+        }
+        for (function, docs) in functions.iter_mut().zip(function_docs) {
+            function.doc = docs.doc;
+            document_entities(&mut function.args, docs.member_docs)?;
+        }
+        Ok(())
+    }
+
+    /// Read the return type section, declaring the return types of the given functions.
+    fn return_types(&mut self, functions: &mut [FnAbi]) -> Result<(), AbiReadError> {
+        let length = self.u32()? as usize;
+        let mut section = AbiReader {
+            bytes: self.take(length)?,
+            version_client: self.version_client,
+            referenced_types: vec![],
+            wide_arrays: false,
+        };
+        let return_types = section.list(AbiReader::return_type)?;
+        if !section.bytes.is_empty() || return_types.len() != functions.len() {
+            return Err(AbiReadError::InvalidReturnTypes);
+        }
+        self.referenced_types.extend(section.referenced_types);
+
+        for (function, return_type) in functions.iter_mut().zip(return_types) {
+            function.return_type = return_type;
+        }
+        Ok(())
+    }
+
+    /// Read the optional type spec of the data returned by a function.
+    fn return_type(&mut self) -> Result<Option<Vec<u8>>, AbiReadError> {
+        match self.u8()? {
+            0x00 => Ok(None),
+            0x01 => Ok(Some(self.type_spec()?)),
+            _ => Err(AbiReadError::InvalidReturnTypes),
+        }
+    }
+
+    fn element_docs(&mut self) -> Result<ElementDocs, AbiReadError> {
+        Ok(ElementDocs {
+            doc: self.doc()?,
+            member_docs: self.list(Self::doc)?,
+        })
+    }
+
+    /// Read a documentation string, where the empty string denotes an undocumented element.
+    fn doc(&mut self) -> Result<Option<String>, AbiReadError> {
+        let doc = self.string()?;
+        Ok(Some(doc).filter(|doc| !doc.is_empty()))
+    }
+
+    /// Read a complete type spec, without recursing into its element types.
+    fn type_spec(&mut self) -> Result<Vec<u8>, AbiReadError> {
+        let start = self.bytes;
+        let mut pending = vec![Pending::TypeSpec];
+        while let Some(element) = pending.pop() {
+            if let Pending::ArrayLength = element {
+                self.take(4)?;
+                continue;
+            }
+            match self.u8()? {
+                0x00 => {
+                    let index = self.u8()?;
+                    self.referenced_types.push(index);
+                }
+                0x11 => {
+                    self.u8()?;
+                }
+                0x01..=0x0d | 0x13..=0x18 => {}
+                0x0e | 0x10 | 0x12 => pending.push(Pending::TypeSpec),
+                0x0f | 0x19 => pending.extend([Pending::TypeSpec, Pending::TypeSpec]),
+                0x1a if self.version_client >= WIDE_ARRAYS_VERSION => {
+                    self.wide_arrays = true;
+                    pending.extend([Pending::ArrayLength, Pending::TypeSpec])
+                }
+                ordinal => return Err(AbiReadError::UnknownTypeOrdinal(ordinal)),
+            }
+        }
+        Ok(start[..start.len() - self.bytes.len()].to_vec())
+    }
+}
@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+use pbc_traits::CreateTypeSpec;
+
+use super::{NamedEntityAbi, NamedTypeSpec};
+
+/// Tuples that can be described in the ABI as anonymous structs.
+///
+/// Implemented for the unit type, and for all tuples whose elements implement [`CreateTypeSpec`].
+pub trait AbiTuple: CreateTypeSpec {
+    /// Produce the fields of the anonymous struct, named by their position in the tuple.
+    fn __tuple_fields(lut: &BTreeMap<String, u8>) -> Vec<NamedEntityAbi>;
+}
+
+macro_rules! impl_abi_tuple {
+    ( $( $name:ident )+ ) => {
+        impl<$($name: CreateTypeSpec),+> AbiTuple for ($($name,)+) {
+            fn __tuple_fields(lut: &BTreeMap<String, u8>) -> Vec<NamedEntityAbi> {
+                let mut positions = 0usize..;
+                vec![$(
+                    NamedEntityAbi::new::<$name>(positions.next().unwrap().to_string(), lut),
+                )+]
+            }
+        }
+    };
+}
+
+impl AbiTuple for () {
+    fn __tuple_fields(_lut: &BTreeMap<String, u8>) -> Vec<NamedEntityAbi> {
+        vec![]
+    }
+}
+
+impl_abi_tuple! { A }
+impl_abi_tuple! { A B }
+impl_abi_tuple! { A B C }
+impl_abi_tuple! { A B C D }
+impl_abi_tuple! { A B C D E }
+impl_abi_tuple! { A B C D E F }
+impl_abi_tuple! { A B C D E F G }
+impl_abi_tuple! { A B C D E F G H }
+impl_abi_tuple! { A B C D E F G H I }
+impl_abi_tuple! { A B C D E F G H I J }
+impl_abi_tuple! { A B C D E F G H I J K }
+impl_abi_tuple! { A B C D E F G H I J K L }
+
+/// Produce the anonymous struct describing the tuple type `T`.
+///
+/// This is the tuple counterpart of the `__abi_for_type_*` functions generated by the
+/// `CreateTypeSpec` derive, and is made visible to the ABI generator by the contract macros.
+///
+/// * `lut` - the lookup table for the ABI generation. See `pbc-abigen` for details.
+pub fn abi_for_tuple<T: AbiTuple>(lut: &BTreeMap<String, u8>) -> Vec<NamedTypeSpec> {
+    let mut type_spec: Vec<u8> = vec![];
+    T::__ty_spec_write(&mut type_spec, lut);
+
+    let mut type_abi = NamedTypeSpec::new_struct(T::__ty_name(), T::__ty_identifier(), type_spec);
+    for field in T::__tuple_fields(lut) {
+        type_abi.add_field(field);
+    }
+    vec![type_abi]
+}
@@ -9,6 +9,7 @@ use super::{abi_serialize_slice, AbiSerialize};
 /// A struct representing the ABI for a Rust type.
 ///
 /// Serialized with the ABI format.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NamedTypeSpec {
     /// The name of the type.
     pub name: String,
@@ -18,9 +19,13 @@ pub struct NamedTypeSpec {
     pub type_spec: Vec<u8>,
     /// The specific kind information, either struct or enum.
     pub kind_information: KindInfo,
+    /// The documentation of the type, should it be documented. Enum variants are documented by
+    /// the struct types of the variants.
+    pub doc: Option<String>,
 }
 
 /// An enum holding the specific kind information for the different named type specifications.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum KindInfo {
     /// The list of the fields that are associated with the struct.
     Struct {
@@ -42,6 +47,7 @@ impl NamedTypeSpec {
             type_identifier,
             type_spec,
             kind_information: KindInfo::Struct { fields: Vec::new() },
+            doc: None,
         }
     }
 
@@ -54,6 +60,7 @@ impl NamedTypeSpec {
             kind_information: KindInfo::Enum {
                 variants: Vec::new(),
             },
+            doc: None,
         }
     }
 
@@ -16,8 +16,8 @@ use crate::shortname::Shortname;
 /// A small struct that automatically calculates the shortname of a function.
 ///
 /// Serialized with the ABI format.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FunctionName {
-    #[allow(unused)]
     name: String,
     shortname: Shortname,
 }
@@ -43,6 +43,11 @@ impl FunctionName {
         }
     }
 
+    /// Gets the name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Gets the Shortname
     pub fn shortname(&self) -> &Shortname {
         &self.shortname
@@ -82,6 +87,29 @@ pub enum FunctionKind {
     ZkExternalEvent = 0x18,
 }
 
+impl FunctionKind {
+    /// The function kind with the given byte representation, if any.
+    #[allow(deprecated)]
+    pub fn from_u8(value: u8) -> Option<Self> {
+        let kind = match value {
+            0x01 => FunctionKind::Init,
+            0x02 => FunctionKind::Action,
+            0x03 => FunctionKind::Callback,
+            0x10 => FunctionKind::ZkSecretInput,
+            0x11 => FunctionKind::ZkVarInputted,
+            0x12 => FunctionKind::ZkVarRejected,
+            0x13 => FunctionKind::ZkComputeComplete,
+            0x14 => FunctionKind::ZkVarOpened,
+            0x15 => FunctionKind::ZkUserVarOpened,
+            0x16 => FunctionKind::ZkAttestationComplete,
+            0x17 => FunctionKind::ZkSecretInputWithExplicitType,
+            0x18 => FunctionKind::ZkExternalEvent,
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
 #[cfg(feature = "abi")]
 impl AbiSerialize for FunctionName {
     fn serialize_abi<T: std::io::Write>(&self, writer: &mut T) -> std::io::Result<()> {
@@ -1,6 +1,6 @@
 //! Definitions for contract invocation [`Shortname`]s and similar identifiers.
 
-use pbc_traits::WriteRPC;
+use pbc_traits::{PbcWrite, WriteRPC};
 
 use super::leb128;
 
@@ -142,7 +142,7 @@ impl std::fmt::Display for Shortname {
 }
 
 impl WriteRPC for Shortname {
-    fn rpc_write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+    fn rpc_write_to<W: PbcWrite>(&self, writer: &mut W) -> pbc_traits::io::Result<()> {
         for item in &self.bytes() {
             item.rpc_write_to(writer)?;
         }
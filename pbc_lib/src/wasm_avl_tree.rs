@@ -150,9 +150,7 @@ pub mod wasm_avl {
 
     fn get_backing_map<A>(f: impl FnOnce(&mut BackingMapType) -> A) -> A {
         let mut guard = BACKING_MAP.lock().unwrap();
-        let map: &mut BackingMapType = guard
-            .entry(thread::current().id())
-            .or_insert(BTreeMap::new());
+        let map: &mut BackingMapType = guard.entry(thread::current().id()).or_default();
         f(map)
     }
 
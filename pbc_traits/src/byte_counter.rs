@@ -0,0 +1,52 @@
+use crate::io;
+
+/// [`PbcWrite`](crate::PbcWrite) that counts the number of bytes written, and discards the
+/// bytes themselves.
+///
+/// Used to determine the serialized size of values that cannot compute it directly, such as
+/// values with custom serialization.
+#[derive(Default, Debug)]
+pub struct ByteCounter {
+    count: usize,
+}
+
+impl ByteCounter {
+    /// Creates a new counter, having counted zero bytes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of bytes written so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Counts the number of bytes written by the given serialization function.
+    ///
+    /// Panics if the function fails.
+    pub fn count_written<F: FnOnce(&mut ByteCounter) -> io::Result<()>>(write: F) -> usize {
+        let mut counter = ByteCounter::new();
+        write(&mut counter).unwrap();
+        counter.count()
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.count += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl io::PbcWrite for ByteCounter {
+    fn write_bytes(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.count += buf.len();
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+//! Reading of the encodings that have non-canonical forms.
+//!
+//! Lenient readers accept every form, while [strict readers](crate::io::Strict) only accept the
+//! canonical one, and report the offset of any other.
+
+use alloc::vec::Vec;
+
+use crate::decode_error::length_to_usize;
+use crate::io::PbcRead;
+use crate::read_int::ReadInt;
+use crate::DecodeError;
+
+/// Reads a `bool`. Any non-zero byte is `true`, but strict readers only accept `0` and `1`.
+pub(crate) fn try_read_bool<R: PbcRead>(reader: &mut R) -> Result<bool, DecodeError> {
+    let offset = reader.strict_position();
+    match (reader.try_read_u8()?, offset) {
+        (0, _) => Ok(false),
+        (1, _) | (_, None) => Ok(true),
+        (byte, Some(offset)) => Err(DecodeError::InvalidBool { offset, byte }),
+    }
+}
+
+/// Reads the marker of an `Option`, returning whether a value follows. Any non-zero marker is
+/// `Some`, but strict readers only accept `0` and `1`.
+pub(crate) fn try_read_option_marker<R: PbcRead>(reader: &mut R) -> Result<bool, DecodeError> {
+    let offset = reader.strict_position();
+    match (reader.try_read_u8()?, offset) {
+        (0, _) => Ok(false),
+        (1, _) | (_, None) => Ok(true),
+        (marker, Some(offset)) => Err(DecodeError::InvalidOptionMarker { offset, marker }),
+    }
+}
+
+/// Reads the length prefix of an RPC collection. RPC lengths are written as an `i32`, so strict
+/// readers reject lengths that are negative as an `i32`.
+pub(crate) fn try_read_rpc_length<R: PbcRead>(reader: &mut R) -> Result<usize, DecodeError> {
+    let offset = reader.strict_position();
+    let length = reader.try_read_u32_be()?;
+    match offset {
+        Some(offset) if length > i32::MAX as u32 => Err(DecodeError::NegativeLength {
+            offset,
+            length: length as i32,
+        }),
+        _ => length_to_usize(length),
+    }
+}
+
+/// Reads the `len` elements of a map or set, given how to read an element and how to get its
+/// key. Duplicate keys are rejected, as the collection would silently drop them, and strict
+/// readers also require the keys to be in increasing order, as they are written.
+pub(crate) fn try_read_keyed<R: PbcRead, T, K: Ord + ?Sized>(
+    reader: &mut R,
+    len: usize,
+    mut read_element: impl FnMut(&mut R) -> Result<T, DecodeError>,
+    key: impl Fn(&T) -> &K,
+) -> Result<Vec<T>, DecodeError> {
+    let strict = reader.strict_position().is_some();
+    let mut elements: Vec<T> = Vec::new();
+    for _ in 0..len {
+        let offset = reader.strict_position();
+        let element = read_element(reader)?;
+        if let (Some(offset), Some(previous)) = (offset, elements.last()) {
+            match key(previous).cmp(key(&element)) {
+                core::cmp::Ordering::Less => {}
+                core::cmp::Ordering::Equal => return Err(DecodeError::DuplicateKey),
+                core::cmp::Ordering::Greater => return Err(DecodeError::UnsortedKey { offset }),
+            }
+        }
+        elements.push(element);
+    }
+    if !strict {
+        let mut keys: Vec<&K> = elements.iter().map(&key).collect();
+        keys.sort_unstable();
+        if keys.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(DecodeError::DuplicateKey);
+        }
+    }
+    Ok(elements)
+}
@@ -1,4 +1,10 @@
-use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+    NonZeroU32, NonZeroU64, NonZeroU8,
+};
+use std::rc::Rc;
 
 /// This trait adds the runtime type information needed to generate the [contract PBC ABI files](https://partisiablockchain.gitlab.io/documentation/smart-contracts/smart-contract-binary-formats.html).
 ///
@@ -74,6 +80,68 @@ impl_for_type!(
     bool,   0x0c
 );
 
+// Types sharing the serialization, and thus the ordinal, of a simple type.
+//
+// Non-zero integers are serialized as the integer, and `char` as its `u32` scalar value.
+impl_for_type!(
+    NonZeroU8,   0x01
+    NonZeroU16,  0x02
+    NonZeroU32,  0x03
+    NonZeroU64,  0x04
+    NonZeroU128, 0x05
+    NonZeroI8,   0x06
+    NonZeroI16,  0x07
+    NonZeroI32,  0x08
+    NonZeroI64,  0x09
+    NonZeroI128, 0x0a
+    char,        0x03
+);
+
+/// Implementation of the [`CreateTypeSpec`] trait for [`Cow<str>`], which is serialized as a
+/// [`String`].
+impl CreateTypeSpec for Cow<'_, str> {
+    /// Type name is constant string `Cow<str>`.
+    fn __ty_name() -> String {
+        "Cow<str>".to_string()
+    }
+
+    fn __ty_identifier() -> String {
+        Self::__ty_name()
+    }
+
+    /// Ordinal is `0x0b`, as for [`String`].
+    fn __ty_spec_write(w: &mut Vec<u8>, lut: &BTreeMap<String, u8>) {
+        String::__ty_spec_write(w, lut);
+    }
+}
+
+/// Implement the [`CreateTypeSpec`] trait for smart pointers, which are serialized as the value
+/// they point to.
+macro_rules! impl_for_pointer {
+    ($($pointer:ident)*) => {
+        $(
+            #[doc = concat!("Implementation of the [`CreateTypeSpec`] trait for [`", stringify!($pointer), "<T>`] for any `T` that implements [`CreateTypeSpec`].")]
+            impl<T: CreateTypeSpec> CreateTypeSpec for $pointer<T> {
+                #[doc = concat!("Type name is `", stringify!($pointer), "<T>`.")]
+                fn __ty_name() -> String {
+                    format!(concat!(stringify!($pointer), "<{}>"), T::__ty_name())
+                }
+
+                fn __ty_identifier() -> String {
+                    format!(concat!(stringify!($pointer), "<{}>"), T::__ty_identifier())
+                }
+
+                /// Ordinal is the ordinal of `T`.
+                fn __ty_spec_write(w: &mut Vec<u8>, lut: &BTreeMap<String, u8>) {
+                    T::__ty_spec_write(w, lut);
+                }
+            }
+        )*
+    };
+}
+
+impl_for_pointer!(Box Rc);
+
 /// Implementation of the [`CreateTypeSpec`] trait for [`Vec<T>`] for any `T` that implements
 /// [`CreateTypeSpec`].
 impl<T: CreateTypeSpec> CreateTypeSpec for Vec<T> {
@@ -133,6 +201,73 @@ impl<V: CreateTypeSpec> CreateTypeSpec for BTreeSet<V> {
     }
 }
 
+/// Implementation of the [`CreateTypeSpec`] trait for [`HashSet<T>`]
+/// for any `T` that implements [`CreateTypeSpec`]
+impl<V: CreateTypeSpec, S> CreateTypeSpec for HashSet<V, S> {
+    /// Type name is `HashSet<T>`
+    fn __ty_name() -> String {
+        format!("HashSet<{}>", V::__ty_name())
+    }
+
+    fn __ty_identifier() -> String {
+        format!("HashSet<{}>", V::__ty_identifier())
+    }
+
+    /// Ordinal is `0x10` followed by ordinal of `T`, as defined in [ABI Spec](https://partisiablockchain.gitlab.io/documentation/abiv1.html).
+    fn __ty_spec_write(w: &mut Vec<u8>, lut: &BTreeMap<String, u8>) {
+        // Identical to BTreeSet impl
+        BTreeSet::<V>::__ty_spec_write(w, lut);
+    }
+}
+
+/// Implementation of the [`CreateTypeSpec`] trait for [`BTreeMap<K, V>`]
+/// for any key and value type `K`, `V` that implement [`CreateTypeSpec`].
+impl<K: CreateTypeSpec, V: CreateTypeSpec> CreateTypeSpec for BTreeMap<K, V> {
+    /// Type name is `BTreeMap<K, V>`
+    fn __ty_name() -> String {
+        format!("BTreeMap<{}, {}>", K::__ty_name(), V::__ty_name())
+    }
+
+    fn __ty_identifier() -> String {
+        format!(
+            "BTreeMap<{}, {}>",
+            K::__ty_identifier(),
+            V::__ty_identifier()
+        )
+    }
+
+    /// Ordinal is `0x0f` followed by ordinals of `K` and `V`, as defined in [ABI Spec](https://partisiablockchain.gitlab.io/documentation/abiv1.html).
+    fn __ty_spec_write(w: &mut Vec<u8>, lut: &BTreeMap<String, u8>) {
+        // Map is 0x0f followed by the specs for the key and value types
+        w.push(0x0f);
+        K::__ty_spec_write(w, lut);
+        V::__ty_spec_write(w, lut);
+    }
+}
+
+/// Implementation of the [`CreateTypeSpec`] trait for [`HashMap<K, V>`]
+/// for any key and value type `K`, `V` that implement [`CreateTypeSpec`].
+impl<K: CreateTypeSpec, V: CreateTypeSpec, S> CreateTypeSpec for HashMap<K, V, S> {
+    /// Type name is `HashMap<K, V>`
+    fn __ty_name() -> String {
+        format!("HashMap<{}, {}>", K::__ty_name(), V::__ty_name())
+    }
+
+    fn __ty_identifier() -> String {
+        format!(
+            "HashMap<{}, {}>",
+            K::__ty_identifier(),
+            V::__ty_identifier()
+        )
+    }
+
+    /// Ordinal is `0x0f` followed by ordinals of `K` and `V`, as defined in [ABI Spec](https://partisiablockchain.gitlab.io/documentation/abiv1.html).
+    fn __ty_spec_write(w: &mut Vec<u8>, lut: &BTreeMap<String, u8>) {
+        // Identical to BTreeMap impl
+        BTreeMap::<K, V>::__ty_spec_write(w, lut);
+    }
+}
+
 /// Implementation of the [`CreateTypeSpec`] trait for [`Option<T>`]
 /// for any `T` that implements [`CreateTypeSpec`]
 impl<T: CreateTypeSpec> CreateTypeSpec for Option<T> {
@@ -152,26 +287,112 @@ impl<T: CreateTypeSpec> CreateTypeSpec for Option<T> {
     }
 }
 
-/// Implement [`CreateTypeSpec`] for an [`[u8;n]`] array type.
+/// Implement [`CreateTypeSpec`] for arrays of arbitrary sizes, of any type that implements
+/// [`CreateTypeSpec`].
+///
+/// Byte arrays of at most 127 elements have the ordinal `0x11` followed by the length of the
+/// array as a single byte. Other arrays have the ordinal `0x1a` followed by the ordinal of the
+/// element type, and the length of the array as a big endian `u32`.
+///
+/// The `0x1a` ordinal was added in version 5.5.0 of the client ABI format, whose type ordinals are
+/// listed on `ContractAbi` in `pbc_contract_core`. ABIs are only given that version when they
+/// contain such arrays, so contracts using byte arrays alone can still be read by earlier clients.
+impl<const LEN: usize, T: CreateTypeSpec> CreateTypeSpec for [T; LEN] {
+    /// Type name is `[T; LEN]`.
+    fn __ty_name() -> String {
+        format!("[{}; {LEN}]", T::__ty_name())
+    }
+
+    fn __ty_identifier() -> String {
+        format!("[{}; {LEN}]", T::__ty_identifier())
+    }
+
+    /// Ordinal is `0x11` followed by byte repr of length `LEN` for small byte arrays, and `0x1a`
+    /// followed by the ordinal of `T` and the `u32` length `LEN` otherwise, as defined in
+    /// [ABI Spec](https://partisiablockchain.gitlab.io/documentation/abiv1.html).
+    fn __ty_spec_write(w: &mut Vec<u8>, lut: &BTreeMap<String, u8>) {
+        let mut element_spec = Vec::new();
+        T::__ty_spec_write(&mut element_spec, lut);
+        match u8::try_from(LEN) {
+            Ok(length) if length <= 0x7F && element_spec == [0x01] => {
+                w.push(0x11);
+                w.push(length);
+            }
+            _ => {
+                let length = u32::try_from(LEN)
+                    .expect("ABI does not support arrays of sizes larger than u32::MAX.");
+                w.push(0x1a);
+                w.extend(element_spec);
+                w.extend(length.to_be_bytes());
+            }
+        }
+    }
+}
+
+/// Implement the [`CreateTypeSpec`] trait for tuples.
 ///
-/// The ordinal is 0x11 followed by the length of the array
-impl<const LEN: usize> CreateTypeSpec for [u8; LEN] {
-    /// Type name is `[u8; LEN]`.
+/// Tuples are described in the ABI as anonymous structs with positional field names, and are
+/// thus named types referenced by their index in the lookup table. The unit type is described as
+/// an anonymous struct without fields.
+macro_rules! impl_for_tuple {
+    ( $( $name:ident )+ ) => {
+        #[doc = "Implementation of the [`CreateTypeSpec`] trait for tuples of types that implement [`CreateTypeSpec`]."]
+        impl<$($name: CreateTypeSpec),+> CreateTypeSpec for ($($name,)+) {
+            /// Type name is `(A, B, ...)`.
+            fn __ty_name() -> String {
+                tuple_name(&[$($name::__ty_name()),+])
+            }
+
+            fn __ty_identifier() -> String {
+                tuple_name(&[$($name::__ty_identifier()),+])
+            }
+
+            /// Ordinal is `0x00` followed by the index of the anonymous struct, as defined in [ABI Spec](https://partisiablockchain.gitlab.io/documentation/abiv1.html).
+            fn __ty_spec_write(w: &mut Vec<u8>, lut: &BTreeMap<String, u8>) {
+                let type_index: u8 = *lut.get(&Self::__ty_identifier()).unwrap_or(&0xFF);
+                w.push(0x00);
+                w.push(type_index);
+            }
+        }
+    };
+}
+
+/// Formats the given element names using Rust tuple syntax.
+fn tuple_name(elements: &[String]) -> String {
+    match elements {
+        [single] => format!("({single},)"),
+        _ => format!("({})", elements.join(", ")),
+    }
+}
+
+/// Implementation of the [`CreateTypeSpec`] trait for the unit type.
+impl CreateTypeSpec for () {
+    /// Type name is `()`.
     fn __ty_name() -> String {
-        format!("[u8; {LEN}]")
+        tuple_name(&[])
     }
 
     fn __ty_identifier() -> String {
         Self::__ty_name()
     }
 
-    /// Ordinal is `0x11` followed by byte repr of length `LEN`, as defined in [ABI Spec](https://partisiablockchain.gitlab.io/documentation/abiv1.html).
-    fn __ty_spec_write(w: &mut Vec<u8>, _lut: &BTreeMap<String, u8>) {
-        w.push(0x11);
-        let length = u8::try_from(LEN)
-            .ok()
-            .filter(|&x| x <= 0x7F)
-            .expect("ABI does not support byte arrays of sizes larger than 127.");
-        w.push(length);
+    /// Ordinal is `0x00` followed by the index of the anonymous struct, as defined in [ABI Spec](https://partisiablockchain.gitlab.io/documentation/abiv1.html).
+    fn __ty_spec_write(w: &mut Vec<u8>, lut: &BTreeMap<String, u8>) {
+        let type_index: u8 = *lut.get(&Self::__ty_identifier()).unwrap_or(&0xFF);
+        w.push(0x00);
+        w.push(type_index);
     }
 }
+
+impl_for_tuple! { A }
+impl_for_tuple! { A B }
+impl_for_tuple! { A B C }
+impl_for_tuple! { A B C D }
+impl_for_tuple! { A B C D E }
+impl_for_tuple! { A B C D E F }
+impl_for_tuple! { A B C D E F G }
+impl_for_tuple! { A B C D E F G H }
+impl_for_tuple! { A B C D E F G H I }
+impl_for_tuple! { A B C D E F G H I J }
+impl_for_tuple! { A B C D E F G H I J K }
+impl_for_tuple! { A B C D E F G H I J K L }
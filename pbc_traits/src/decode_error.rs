@@ -0,0 +1,133 @@
+//! Definition of [`DecodeError`], the error produced by fallible deserialization.
+
+use core::fmt;
+
+use crate::io;
+
+/// Error produced when a byte buffer cannot be deserialized to a value.
+///
+/// Returned by the fallible deserialization methods
+/// [`ReadRPC::try_rpc_read_from`](crate::ReadRPC::try_rpc_read_from) and
+/// [`ReadWriteState::try_state_read_from`](crate::ReadWriteState::try_state_read_from), which
+/// allow off-chain code to reject malformed input without catching panics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input ended before the value had been fully read.
+    UnexpectedEof,
+    /// An enum discriminant did not correspond to any known variant.
+    InvalidDiscriminant {
+        /// Name of the enum type being read.
+        type_name: &'static str,
+        /// The discriminant that was read.
+        discriminant: u8,
+    },
+    /// A string did not contain valid UTF-8.
+    InvalidUtf8,
+    /// A `char` was not a valid Unicode scalar value.
+    InvalidChar {
+        /// The value that was read.
+        value: u32,
+    },
+    /// A non-zero integer type was zero.
+    InvalidNonZero {
+        /// Name of the non-zero type being read.
+        type_name: &'static str,
+    },
+    /// A length prefix was too large to be represented in memory.
+    LengthOverflow {
+        /// The length that was read.
+        length: u32,
+    },
+    /// The underlying reader failed with an error other than end of input.
+    Io(io::ErrorKind),
+    /// A `bool` was encoded by a byte other than `0` or `1`. Only rejected by
+    /// [strict readers](io::Strict).
+    InvalidBool {
+        /// Offset of the byte in the input.
+        offset: u64,
+        /// The byte that was read.
+        byte: u8,
+    },
+    /// An `Option` marker was a byte other than `0` or `1`. Only rejected by
+    /// [strict readers](io::Strict).
+    InvalidOptionMarker {
+        /// Offset of the marker in the input.
+        offset: u64,
+        /// The marker that was read.
+        marker: u8,
+    },
+    /// An RPC length prefix was negative. Only rejected by [strict readers](io::Strict).
+    NegativeLength {
+        /// Offset of the length prefix in the input.
+        offset: u64,
+        /// The length that was read.
+        length: i32,
+    },
+    /// A map or set contained the same key more than once.
+    DuplicateKey,
+    /// The keys of a map or set were not in increasing order. Only rejected by
+    /// [strict readers](io::Strict).
+    UnsortedKey {
+        /// Offset of the first key that was smaller than the key before it.
+        offset: u64,
+    },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEof => write!(f, "Encountered end of stream while decoding"),
+            Self::InvalidDiscriminant {
+                type_name,
+                discriminant,
+            } => write!(
+                f,
+                "No known {type_name} value with discriminant {discriminant}"
+            ),
+            Self::InvalidUtf8 => write!(f, "String is not valid UTF-8"),
+            Self::InvalidChar { value } => write!(f, "{value} is not a valid char"),
+            Self::InvalidNonZero { type_name } => write!(f, "Zero is not a valid {type_name}"),
+            Self::LengthOverflow { length } => {
+                write!(f, "Length {length} is too large to be decoded")
+            }
+            Self::Io(kind) => write!(f, "Could not read input: {kind}"),
+            Self::InvalidBool { offset, byte } => {
+                write!(f, "Byte {byte} at offset {offset} is not a valid bool")
+            }
+            Self::InvalidOptionMarker { offset, marker } => {
+                write!(
+                    f,
+                    "Byte {marker} at offset {offset} is not a valid option marker"
+                )
+            }
+            Self::NegativeLength { offset, length } => {
+                write!(f, "Length {length} at offset {offset} is negative")
+            }
+            Self::DuplicateKey => write!(f, "Map or set contains a duplicate key"),
+            Self::UnsortedKey { offset } => {
+                write!(
+                    f,
+                    "Key at offset {offset} is smaller than the key before it"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+impl From<io::Error> for DecodeError {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => Self::UnexpectedEof,
+            kind => Self::Io(kind),
+        }
+    }
+}
+
+/// Converts a length prefix to a [`usize`], failing if it cannot be represented.
+pub(crate) fn length_to_usize(length: u32) -> Result<usize, DecodeError> {
+    usize::try_from(length).map_err(|_| DecodeError::LengthOverflow { length })
+}
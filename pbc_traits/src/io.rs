@@ -0,0 +1,206 @@
+//! Minimal byte reader and writer abstractions used by the serialization traits.
+//!
+//! [`PbcRead`] and [`PbcWrite`] only provide what the serialization formats need: reading and
+//! writing exact byte slices. With the `std` feature (enabled by default) they are implemented for
+//! every [`std::io::Read`] and [`std::io::Write`], and [`Error`] and [`Result`] are the ones from
+//! [`std::io`]. Without it, the crate is `no_std` and only needs `alloc`; readers and writers are
+//! then implemented for `&[u8]` and `Vec<u8>`, with a small in-crate [`Error`] type.
+//!
+//! The SDK crates depend on this crate without default features, such that contracts do not link
+//! [`std::io`] unless `std` is enabled, as it is by the `abi` feature.
+//!
+//! Wrapping a reader in [`Strict`] makes the fallible `try_` read methods reject non-canonical
+//! encodings.
+
+#[cfg(feature = "std")]
+pub use std::io::{Error, ErrorKind, Result};
+
+#[cfg(not(feature = "std"))]
+pub use no_std_io::{Error, ErrorKind, Result};
+
+/// Source of bytes for deserialization.
+pub trait PbcRead {
+    /// Reads exactly enough bytes to fill `buf`.
+    ///
+    /// Fails with [`ErrorKind::UnexpectedEof`] if the source ends before `buf` has been filled.
+    fn read_bytes(&mut self, buf: &mut [u8]) -> Result<()>;
+
+    /// Position of the next byte to be read, if the reader is strict; i.e. if decoders must
+    /// reject non-canonical encodings. See [`Strict`].
+    ///
+    /// The default implementation returns `None`, making the reader lenient.
+    fn strict_position(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Sink of bytes for serialization.
+pub trait PbcWrite {
+    /// Writes all of `buf`.
+    fn write_bytes(&mut self, buf: &[u8]) -> Result<()>;
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read + ?Sized> PbcRead for R {
+    fn read_bytes(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.read_exact(buf)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write + ?Sized> PbcWrite for W {
+    fn write_bytes(&mut self, buf: &[u8]) -> Result<()> {
+        self.write_all(buf)
+    }
+}
+
+/// Reader requiring canonical encodings from the fallible `try_` read methods.
+///
+/// Several encodings have non-canonical forms that lenient readers accept; any non-zero byte is
+/// read as a `true` [`bool`] and as the marker of a `Some` [`Option`], and the `i32` length prefix
+/// of RPC collections is read as unsigned. Strict readers reject these forms, such that every
+/// accepted input is the serialization of the value read from it, which matters when the input is
+/// hashed or signed. Rejections are reported as a [`DecodeError`](crate::DecodeError) holding the
+/// offset of the encoding, counted from where the [`Strict`] reader was created.
+///
+/// ```
+/// # use pbc_traits::io::Strict;
+/// # use pbc_traits::{DecodeError, ReadRPC};
+/// let bytes = [1u8, 2];
+/// assert_eq!(<(bool, bool)>::try_rpc_read_from(&mut bytes.as_slice()), Ok((true, true)));
+/// assert_eq!(
+///     <(bool, bool)>::try_rpc_read_from(&mut Strict::new(bytes.as_slice())),
+///     Err(DecodeError::InvalidBool { offset: 1, byte: 2 })
+/// );
+/// ```
+#[derive(Debug)]
+pub struct Strict<R> {
+    reader: R,
+    position: u64,
+}
+
+impl<R: PbcRead> Strict<R> {
+    /// Creates a strict reader reading from the given reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            position: 0,
+        }
+    }
+
+    /// Number of bytes read so far.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Unwraps the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: PbcRead> PbcRead for Strict<R> {
+    fn read_bytes(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.reader.read_bytes(buf)?;
+        self.position += buf.len() as u64;
+        Ok(())
+    }
+
+    fn strict_position(&self) -> Option<u64> {
+        Some(self.position)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod no_std_io {
+    use alloc::vec::Vec;
+    use core::fmt;
+
+    use super::{PbcRead, PbcWrite};
+
+    /// Result of reading or writing bytes.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// The kind of an [`Error`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum ErrorKind {
+        /// The source ended before all requested bytes had been read.
+        UnexpectedEof,
+        /// The sink could not accept all bytes.
+        WriteZero,
+        /// Any other failure.
+        Other,
+    }
+
+    impl fmt::Display for ErrorKind {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::UnexpectedEof => f.write_str("unexpected end of file"),
+                Self::WriteZero => f.write_str("write zero"),
+                Self::Other => f.write_str("other error"),
+            }
+        }
+    }
+
+    /// Error produced by [`PbcRead`] and [`PbcWrite`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Error {
+        kind: ErrorKind,
+    }
+
+    impl Error {
+        /// The kind of the error.
+        pub fn kind(&self) -> ErrorKind {
+            self.kind
+        }
+    }
+
+    impl From<ErrorKind> for Error {
+        fn from(kind: ErrorKind) -> Self {
+            Self { kind }
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.kind.fmt(f)
+        }
+    }
+
+    impl PbcRead for &[u8] {
+        fn read_bytes(&mut self, buf: &mut [u8]) -> Result<()> {
+            if self.len() < buf.len() {
+                *self = &self[self.len()..];
+                return Err(ErrorKind::UnexpectedEof.into());
+            }
+            let (read, rest) = self.split_at(buf.len());
+            buf.copy_from_slice(read);
+            *self = rest;
+            Ok(())
+        }
+    }
+
+    impl<R: PbcRead + ?Sized> PbcRead for &mut R {
+        fn read_bytes(&mut self, buf: &mut [u8]) -> Result<()> {
+            (**self).read_bytes(buf)
+        }
+
+        fn strict_position(&self) -> Option<u64> {
+            (**self).strict_position()
+        }
+    }
+
+    impl PbcWrite for Vec<u8> {
+        fn write_bytes(&mut self, buf: &[u8]) -> Result<()> {
+            self.extend_from_slice(buf);
+            Ok(())
+        }
+    }
+
+    impl<W: PbcWrite + ?Sized> PbcWrite for &mut W {
+        fn write_bytes(&mut self, buf: &[u8]) -> Result<()> {
+            (**self).write_bytes(buf)
+        }
+    }
+}
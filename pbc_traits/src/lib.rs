@@ -6,19 +6,51 @@
 //! - [`ReadRPC`] for RPC serialization.
 //! - [`WriteRPC`] for RPC serialization.
 //! - [`create_type_spec::CreateTypeSpec`] for ABI serialization.
+//!
+//! Deserialization panics on malformed input by default; the `try_` variants of the read methods
+//! return a [`DecodeError`] instead. Reading from an [`io::Strict`] reader additionally rejects
+//! non-canonical encodings.
+//!
+//! The size of a serialized value can be determined without serializing it, using
+//! [`WriteRPC::rpc_serialized_size`] and [`ReadWriteState::state_serialized_size`].
+//!
+//! [`StateView`] gives lazy access to State serialized values, such that only the fields that are
+//! used get decoded.
+//!
+//! Values are read from a [`PbcRead`] and written to a [`PbcWrite`]. With the default `std`
+//! feature these are implemented for all [`std::io::Read`] and [`std::io::Write`]; without it the
+//! crate is `no_std`, and only requires `alloc`. See [`io`] for details.
+//!
+//! With the `serde` feature, [`serde_format`] provides a serde serializer and deserializer for
+//! the RPC and State formats, for off-chain code sharing its serde models with contracts.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+pub use byte_counter::ByteCounter;
 #[cfg(feature = "abi")]
 pub use create_type_spec::CreateTypeSpec;
+pub use decode_error::DecodeError;
+pub use io::{PbcRead, PbcWrite};
 pub use read_int::ReadInt;
 pub use readwrite_rpc::ReadRPC;
 pub use readwrite_rpc::WriteRPC;
 pub use readwrite_state::ReadWriteState;
+pub use state_view::{StateView, StateViewFields};
 pub use write_int::WriteInt;
 
+mod byte_counter;
+mod canonical;
 #[cfg(feature = "abi")]
 mod create_type_spec;
 
+mod decode_error;
+pub mod io;
 mod read_int;
 mod readwrite_rpc;
 mod readwrite_state;
+#[cfg(feature = "serde")]
+pub mod serde_format;
+mod state_view;
 mod write_int;
@@ -1,4 +1,6 @@
-use std::io::Read;
+use crate::io::PbcRead;
+
+use crate::DecodeError;
 
 /// Specifies functions for reading integers statefully, both big and little endians.
 pub trait ReadInt {
@@ -46,51 +48,105 @@ pub trait ReadInt {
     fn read_i8(&mut self) -> i8;
     /// Read a u8
     fn read_u8(&mut self) -> u8;
+
+    /// Read a u128 as big endian, failing if the input is too short.
+    fn try_read_u128_be(&mut self) -> Result<u128, DecodeError>;
+    /// Read an i128 as big endian, failing if the input is too short.
+    fn try_read_i128_be(&mut self) -> Result<i128, DecodeError>;
+
+    /// Read a u64 as big endian, failing if the input is too short.
+    fn try_read_u64_be(&mut self) -> Result<u64, DecodeError>;
+    /// Read an i64 as big endian, failing if the input is too short.
+    fn try_read_i64_be(&mut self) -> Result<i64, DecodeError>;
+
+    /// Read an i32 as big endian, failing if the input is too short.
+    fn try_read_i32_be(&mut self) -> Result<i32, DecodeError>;
+    /// Read a u32 as big endian, failing if the input is too short.
+    fn try_read_u32_be(&mut self) -> Result<u32, DecodeError>;
+
+    /// Read an i16 as big endian, failing if the input is too short.
+    fn try_read_i16_be(&mut self) -> Result<i16, DecodeError>;
+    /// Read a u16 as big endian, failing if the input is too short.
+    fn try_read_u16_be(&mut self) -> Result<u16, DecodeError>;
+
+    /// Read a u128 as little endian, failing if the input is too short.
+    fn try_read_u128_le(&mut self) -> Result<u128, DecodeError>;
+    /// Read an i128 as little endian, failing if the input is too short.
+    fn try_read_i128_le(&mut self) -> Result<i128, DecodeError>;
+
+    /// Read a u64 as little endian, failing if the input is too short.
+    fn try_read_u64_le(&mut self) -> Result<u64, DecodeError>;
+    /// Read an i64 as little endian, failing if the input is too short.
+    fn try_read_i64_le(&mut self) -> Result<i64, DecodeError>;
+
+    /// Read an i32 as little endian, failing if the input is too short.
+    fn try_read_i32_le(&mut self) -> Result<i32, DecodeError>;
+    /// Read a u32 as little endian, failing if the input is too short.
+    fn try_read_u32_le(&mut self) -> Result<u32, DecodeError>;
+
+    /// Read an i16 as little endian, failing if the input is too short.
+    fn try_read_i16_le(&mut self) -> Result<i16, DecodeError>;
+    /// Read a u16 as little endian, failing if the input is too short.
+    fn try_read_u16_le(&mut self) -> Result<u16, DecodeError>;
+
+    /// Read an i8, failing if the input is too short.
+    fn try_read_i8(&mut self) -> Result<i8, DecodeError>;
+    /// Read a u8, failing if the input is too short.
+    fn try_read_u8(&mut self) -> Result<u8, DecodeError>;
 }
 
-/// Generate a read method given the <Type>::from_endian_bytes.
+/// Generate a read method and its fallible variant given the <Type>::from_endian_bytes.
 macro_rules! read_int {
-    ($($type:ty, $len:literal, $fn_name:ident, $from_ee_bytes:ident)*) => {
+    ($($type:ty, $len:literal, $fn_name:ident, $try_fn_name:ident, $from_ee_bytes:ident)*) => {
         $(
             #[doc = "Implementation of ReadInt-trait for "]
             #[doc = stringify!($type)]
             fn $fn_name(&mut self) -> $type {
                 let mut buf = [0u8; $len];
-                self.read_exact(&mut buf).expect(concat!("Encountered end of stream while reading ", stringify!($type)));
+                self.read_bytes(&mut buf).expect(concat!("Encountered end of stream while reading ", stringify!($type)));
 
                 <$type>::$from_ee_bytes(buf)
             }
+
+            #[doc = "Fallible implementation of ReadInt-trait for "]
+            #[doc = stringify!($type)]
+            fn $try_fn_name(&mut self) -> Result<$type, DecodeError> {
+                let mut buf = [0u8; $len];
+                self.read_bytes(&mut buf)?;
+
+                Ok(<$type>::$from_ee_bytes(buf))
+            }
         )*
     }
 }
 
-impl<T: Read> ReadInt for T {
-    read_int!(u128, 16, read_u128_be, from_be_bytes);
-    read_int!(i128, 16, read_i128_be, from_be_bytes);
+impl<T: PbcRead + ?Sized> ReadInt for T {
+    read_int!(u128, 16, read_u128_be, try_read_u128_be, from_be_bytes);
+    read_int!(i128, 16, read_i128_be, try_read_i128_be, from_be_bytes);
 
-    read_int!(u64, 8, read_u64_be, from_be_bytes);
-    read_int!(i64, 8, read_i64_be, from_be_bytes);
+    read_int!(u64, 8, read_u64_be, try_read_u64_be, from_be_bytes);
+    read_int!(i64, 8, read_i64_be, try_read_i64_be, from_be_bytes);
 
-    read_int!(u32, 4, read_u32_be, from_be_bytes);
-    read_int!(i32, 4, read_i32_be, from_be_bytes);
+    read_int!(u32, 4, read_u32_be, try_read_u32_be, from_be_bytes);
+    read_int!(i32, 4, read_i32_be, try_read_i32_be, from_be_bytes);
 
-    read_int!(u16, 2, read_u16_be, from_be_bytes);
-    read_int!(i16, 2, read_i16_be, from_be_bytes);
+    read_int!(u16, 2, read_u16_be, try_read_u16_be, from_be_bytes);
+    read_int!(i16, 2, read_i16_be, try_read_i16_be, from_be_bytes);
 
-    read_int!(u128, 16, read_u128_le, from_le_bytes);
-    read_int!(i128, 16, read_i128_le, from_le_bytes);
+    read_int!(u128, 16, read_u128_le, try_read_u128_le, from_le_bytes);
+    read_int!(i128, 16, read_i128_le, try_read_i128_le, from_le_bytes);
 
-    read_int!(u64, 8, read_u64_le, from_le_bytes);
-    read_int!(i64, 8, read_i64_le, from_le_bytes);
+    read_int!(u64, 8, read_u64_le, try_read_u64_le, from_le_bytes);
+    read_int!(i64, 8, read_i64_le, try_read_i64_le, from_le_bytes);
 
-    read_int!(u32, 4, read_u32_le, from_le_bytes);
-    read_int!(i32, 4, read_i32_le, from_le_bytes);
+    read_int!(u32, 4, read_u32_le, try_read_u32_le, from_le_bytes);
+    read_int!(i32, 4, read_i32_le, try_read_i32_le, from_le_bytes);
 
-    read_int!(u16, 2, read_u16_le, from_le_bytes);
-    read_int!(i16, 2, read_i16_le, from_le_bytes);
+    read_int!(u16, 2, read_u16_le, try_read_u16_le, from_le_bytes);
+    read_int!(i16, 2, read_i16_le, try_read_i16_le, from_le_bytes);
 
-    read_int!(u8, 1, read_u8, from_ne_bytes);
-    read_int!(i8, 1, read_i8, from_ne_bytes);
+    read_int!(u8, 1, read_u8, try_read_u8, from_ne_bytes);
+    read_int!(i8, 1, read_i8, try_read_i8, from_ne_bytes);
 }
 
 #[cfg(test)]
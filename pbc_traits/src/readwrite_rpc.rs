@@ -1,7 +1,25 @@
-use std::io::{Read, Write};
+use crate::io::{self, PbcRead, PbcWrite};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+    NonZeroU32, NonZeroU64, NonZeroU8,
+};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::hash::{BuildHasher, Hash};
 
+use crate::canonical::{
+    try_read_bool, try_read_keyed, try_read_option_marker, try_read_rpc_length,
+};
 use crate::read_int::ReadInt;
 use crate::write_int::WriteInt;
+use crate::{ByteCounter, DecodeError};
 
 /// Marks implementations that read [RPC serialization format](https://partisiablockchain.gitlab.io/documentation/smart-contracts/smart-contract-binary-formats.html).
 ///
@@ -19,7 +37,17 @@ use crate::write_int::WriteInt;
 /// and other contracts.
 pub trait ReadRPC: Sized {
     /// Deserialization method for RPC arguments.
-    fn rpc_read_from<T: Read>(reader: &mut T) -> Self;
+    fn rpc_read_from<T: PbcRead>(reader: &mut T) -> Self;
+
+    /// Fallible deserialization method for RPC arguments.
+    ///
+    /// Returns a [`DecodeError`] when the input is malformed, where
+    /// [`rpc_read_from`](Self::rpc_read_from) would panic. The default implementation delegates to
+    /// [`rpc_read_from`](Self::rpc_read_from), and thus still panics; all implementations in the
+    /// SDK, including the derived ones, override it.
+    fn try_rpc_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        Ok(Self::rpc_read_from(reader))
+    }
 }
 
 /// Marks implementations that the [RPC serialization format](https://partisiablockchain.gitlab.io/documentation/smart-contracts/smart-contract-binary-formats.html).
@@ -38,13 +66,22 @@ pub trait ReadRPC: Sized {
 /// and other contracts.
 pub trait WriteRPC: Sized {
     /// Serialization method for RPC arguments.
-    fn rpc_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()>;
+    fn rpc_write_to<T: PbcWrite>(&self, writer: &mut T) -> io::Result<()>;
+
+    /// Number of bytes written by [`rpc_write_to`](Self::rpc_write_to).
+    ///
+    /// The default implementation counts the bytes written by
+    /// [`rpc_write_to`](Self::rpc_write_to); all implementations in the SDK, including the
+    /// derived ones, compute the size without serializing.
+    fn rpc_serialized_size(&self) -> usize {
+        ByteCounter::count_written(|writer| self.rpc_write_to(writer))
+    }
 }
 
 /// Implementation of the [`ReadRPC`] trait for a vector of any type `T`
 /// that implements [`ReadRPC`]..
 impl<T: ReadRPC> ReadRPC for Vec<T> {
-    fn rpc_read_from<R: Read>(reader: &mut R) -> Self {
+    fn rpc_read_from<R: PbcRead>(reader: &mut R) -> Self {
         let len = reader.read_u32_be() as usize;
         let mut result = Vec::with_capacity(usize::min(len, 128usize));
         for _ in 0..len {
@@ -52,12 +89,21 @@ impl<T: ReadRPC> ReadRPC for Vec<T> {
         }
         result
     }
+
+    fn try_rpc_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        let len = try_read_rpc_length(reader)?;
+        let mut result = Vec::with_capacity(usize::min(len, 128usize));
+        for _ in 0..len {
+            result.push(T::try_rpc_read_from(reader)?)
+        }
+        Ok(result)
+    }
 }
 
 /// Implementation of the [`WriteRPC`] trait for a vector of any type `T`
 /// that implements [`WriteRPC`]..
 impl<T: WriteRPC> WriteRPC for Vec<T> {
-    fn rpc_write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+    fn rpc_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
         writer.write_i32_be(self.len() as i32).unwrap();
         for item in self {
             item.rpc_write_to(writer).unwrap();
@@ -65,22 +111,235 @@ impl<T: WriteRPC> WriteRPC for Vec<T> {
 
         Ok(())
     }
+
+    fn rpc_serialized_size(&self) -> usize {
+        elements_size(self.iter())
+    }
+}
+
+/// Implementation of the [`ReadRPC`] trait for [`BTreeMap<K, V>`] for any `K` and `V` that
+/// implement [`ReadRPC`].
+///
+/// Serialized identically to a [`Vec`] of key-value pairs, sorted by key.
+///
+/// Fallible reads reject duplicate keys, and [strict readers](crate::io::Strict) also reject
+/// keys that are not sorted.
+impl<K: ReadRPC + Ord, V: ReadRPC> ReadRPC for BTreeMap<K, V> {
+    fn rpc_read_from<R: PbcRead>(reader: &mut R) -> Self {
+        let len = reader.read_u32_be();
+        (0..len)
+            .map(|_| (K::rpc_read_from(reader), V::rpc_read_from(reader)))
+            .collect()
+    }
+
+    fn try_rpc_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        let len = try_read_rpc_length(reader)?;
+        let entries = try_read_keyed(
+            reader,
+            len,
+            |reader| Ok((K::try_rpc_read_from(reader)?, V::try_rpc_read_from(reader)?)),
+            |(key, _)| key,
+        )?;
+        Ok(entries.into_iter().collect())
+    }
+}
+
+/// Implementation of the [`WriteRPC`] trait for [`BTreeMap<K, V>`] for any `K` and `V` that
+/// implement [`WriteRPC`].
+impl<K: WriteRPC, V: WriteRPC> WriteRPC for BTreeMap<K, V> {
+    fn rpc_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+        write_entries(self.len(), self.iter(), writer)
+    }
+
+    fn rpc_serialized_size(&self) -> usize {
+        entries_size(self.iter())
+    }
+}
+
+/// Implementation of the [`ReadRPC`] trait for [`BTreeSet<T>`] for any `T` that implements
+/// [`ReadRPC`].
+///
+/// Serialized identically to a sorted [`Vec`] of the elements.
+///
+/// Fallible reads reject duplicate elements, and [strict readers](crate::io::Strict) also
+/// reject elements that are not sorted.
+impl<T: ReadRPC + Ord> ReadRPC for BTreeSet<T> {
+    fn rpc_read_from<R: PbcRead>(reader: &mut R) -> Self {
+        let len = reader.read_u32_be();
+        (0..len).map(|_| T::rpc_read_from(reader)).collect()
+    }
+
+    fn try_rpc_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        let len = try_read_rpc_length(reader)?;
+        let elements = try_read_keyed(reader, len, T::try_rpc_read_from, |element| element)?;
+        Ok(elements.into_iter().collect())
+    }
+}
+
+/// Implementation of the [`WriteRPC`] trait for [`BTreeSet<T>`] for any `T` that implements
+/// [`WriteRPC`].
+impl<T: WriteRPC> WriteRPC for BTreeSet<T> {
+    fn rpc_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+        write_elements(self.len(), self.iter(), writer)
+    }
+
+    fn rpc_serialized_size(&self) -> usize {
+        elements_size(self.iter())
+    }
+}
+
+/// Implementation of the [`ReadRPC`] trait for [`HashMap<K, V>`] for any `K` and `V` that
+/// implement [`ReadRPC`].
+///
+/// Serialized identically to [`BTreeMap<K, V>`].
+#[cfg(feature = "std")]
+impl<K, V, S> ReadRPC for HashMap<K, V, S>
+where
+    K: ReadRPC + Ord + Hash,
+    V: ReadRPC,
+    S: BuildHasher + Default,
+{
+    fn rpc_read_from<R: PbcRead>(reader: &mut R) -> Self {
+        let len = reader.read_u32_be();
+        (0..len)
+            .map(|_| (K::rpc_read_from(reader), V::rpc_read_from(reader)))
+            .collect()
+    }
+
+    fn try_rpc_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        let len = try_read_rpc_length(reader)?;
+        let entries = try_read_keyed(
+            reader,
+            len,
+            |reader| Ok((K::try_rpc_read_from(reader)?, V::try_rpc_read_from(reader)?)),
+            |(key, _)| key,
+        )?;
+        Ok(entries.into_iter().collect())
+    }
+}
+
+/// Implementation of the [`WriteRPC`] trait for [`HashMap<K, V>`] for any `K` and `V` that
+/// implement [`WriteRPC`].
+///
+/// Entries are sorted by key before writing, such that the serialization is independent of the
+/// iteration order of the map.
+#[cfg(feature = "std")]
+impl<K: WriteRPC + Ord, V: WriteRPC, S> WriteRPC for HashMap<K, V, S> {
+    fn rpc_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+        let mut entries: Vec<(&K, &V)> = self.iter().collect();
+        entries.sort_unstable_by_key(|(key, _)| *key);
+        write_entries(entries.len(), entries.into_iter(), writer)
+    }
+
+    fn rpc_serialized_size(&self) -> usize {
+        entries_size(self.iter())
+    }
+}
+
+/// Implementation of the [`ReadRPC`] trait for [`HashSet<T>`] for any `T` that implements
+/// [`ReadRPC`].
+///
+/// Serialized identically to [`BTreeSet<T>`].
+#[cfg(feature = "std")]
+impl<T, S> ReadRPC for HashSet<T, S>
+where
+    T: ReadRPC + Ord + Hash,
+    S: BuildHasher + Default,
+{
+    fn rpc_read_from<R: PbcRead>(reader: &mut R) -> Self {
+        let len = reader.read_u32_be();
+        (0..len).map(|_| T::rpc_read_from(reader)).collect()
+    }
+
+    fn try_rpc_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        let len = try_read_rpc_length(reader)?;
+        let elements = try_read_keyed(reader, len, T::try_rpc_read_from, |element| element)?;
+        Ok(elements.into_iter().collect())
+    }
+}
+
+/// Implementation of the [`WriteRPC`] trait for [`HashSet<T>`] for any `T` that implements
+/// [`WriteRPC`].
+///
+/// Elements are sorted before writing, such that the serialization is independent of the
+/// iteration order of the set.
+#[cfg(feature = "std")]
+impl<T: WriteRPC + Ord, S> WriteRPC for HashSet<T, S> {
+    fn rpc_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+        let mut elements: Vec<&T> = self.iter().collect();
+        elements.sort_unstable();
+        write_elements(elements.len(), elements.into_iter(), writer)
+    }
+
+    fn rpc_serialized_size(&self) -> usize {
+        elements_size(self.iter())
+    }
+}
+
+/// Handles serialization of the given number of key-value pairs.
+fn write_entries<'a, W: PbcWrite, K: WriteRPC + 'a, V: WriteRPC + 'a>(
+    len: usize,
+    entries: impl Iterator<Item = (&'a K, &'a V)>,
+    writer: &mut W,
+) -> io::Result<()> {
+    writer.write_i32_be(len as i32)?;
+    for (key, value) in entries {
+        key.rpc_write_to(writer)?;
+        value.rpc_write_to(writer)?;
+    }
+    Ok(())
+}
+
+/// Handles serialization of the given number of elements.
+fn write_elements<'a, W: PbcWrite, T: WriteRPC + 'a>(
+    len: usize,
+    elements: impl Iterator<Item = &'a T>,
+    writer: &mut W,
+) -> io::Result<()> {
+    writer.write_i32_be(len as i32)?;
+    for element in elements {
+        element.rpc_write_to(writer)?;
+    }
+    Ok(())
+}
+
+/// Size of the serialization of the given key-value pairs, including the length.
+fn entries_size<'a, K: WriteRPC + 'a, V: WriteRPC + 'a>(
+    entries: impl Iterator<Item = (&'a K, &'a V)>,
+) -> usize {
+    let content: usize = entries
+        .map(|(key, value)| key.rpc_serialized_size() + value.rpc_serialized_size())
+        .sum();
+    core::mem::size_of::<i32>() + content
+}
+
+/// Size of the serialization of the given elements, including the length.
+fn elements_size<'a, T: WriteRPC + 'a>(elements: impl Iterator<Item = &'a T>) -> usize {
+    let content: usize = elements.map(WriteRPC::rpc_serialized_size).sum();
+    core::mem::size_of::<i32>() + content
 }
 
 /// Implementation of the [`ReadRPC`] trait for [`Option<T>`] of any type that implements [`ReadRPC`].
 impl<T: ReadRPC> ReadRPC for Option<T> {
-    fn rpc_read_from<R: Read>(reader: &mut R) -> Self {
+    fn rpc_read_from<R: PbcRead>(reader: &mut R) -> Self {
         let marker = reader.read_u8();
         match marker {
             0 => None,
             _ => Some(T::rpc_read_from(reader)),
         }
     }
+
+    fn try_rpc_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        match try_read_option_marker(reader)? {
+            false => Ok(None),
+            true => Ok(Some(T::try_rpc_read_from(reader)?)),
+        }
+    }
 }
 
 /// Implementation of the [`WriteRPC`] trait for [`Option<T>`] of any type that implements [`WriteRPC`].
 impl<T: WriteRPC> WriteRPC for Option<T> {
-    fn rpc_write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+    fn rpc_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
         match &self {
             None => writer.write_u8(0),
             Some(value) => {
@@ -89,6 +348,13 @@ impl<T: WriteRPC> WriteRPC for Option<T> {
             }
         }
     }
+
+    fn rpc_serialized_size(&self) -> usize {
+        match &self {
+            None => 1,
+            Some(value) => 1 + value.rpc_serialized_size(),
+        }
+    }
 }
 
 /// Implementation of the [`ReadRPC`] trait for [`String`].
@@ -98,85 +364,331 @@ impl ReadRPC for String {
     ///
     /// The reason this asymmetry works is that a [`&[u8]`] is the result of borrowing
     /// a [`Vec<u8>`].
-    fn rpc_read_from<T: Read>(reader: &mut T) -> Self {
+    fn rpc_read_from<T: PbcRead>(reader: &mut T) -> Self {
         // We can read this as an vector of bytes even though we wrote it as a slice,
         // since a byte slice &[u8] is simply a borrowed Vec<u8>.
         let vec: Vec<u8> = Vec::rpc_read_from(reader);
         String::from_utf8(vec).unwrap()
     }
+
+    fn try_rpc_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        let vec: Vec<u8> = Vec::try_rpc_read_from(reader)?;
+        String::from_utf8(vec).map_err(|_| DecodeError::InvalidUtf8)
+    }
 }
 
 /// Implementation of the [`WriteRPC`] trait for [`String`].
 impl WriteRPC for String {
-    fn rpc_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn rpc_write_to<T: PbcWrite>(&self, writer: &mut T) -> io::Result<()> {
         let utf_bytes = self.as_bytes();
         writer.write_u32_be(utf_bytes.len() as u32).unwrap();
-        writer.write_all(utf_bytes)
+        writer.write_bytes(utf_bytes)
+    }
+
+    fn rpc_serialized_size(&self) -> usize {
+        core::mem::size_of::<u32>() + self.len()
     }
 }
 
 /// Implementation of the [`ReadRPC`] trait for [`bool`].
 impl ReadRPC for bool {
-    fn rpc_read_from<T: Read>(reader: &mut T) -> Self {
+    fn rpc_read_from<T: PbcRead>(reader: &mut T) -> Self {
         reader.read_u8() != 0
     }
+
+    fn try_rpc_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        try_read_bool(reader)
+    }
 }
 
 /// Implementation of the [`WriteRPC`] trait for [`bool`].
 impl WriteRPC for bool {
-    fn rpc_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn rpc_write_to<T: PbcWrite>(&self, writer: &mut T) -> io::Result<()> {
         writer.write_u8(u8::from(*self))
     }
+
+    fn rpc_serialized_size(&self) -> usize {
+        1
+    }
 }
 
 macro_rules! rw_int {
-    ($($type:ty, $read_method:ident, $write_method:ident)*) => {
+    ($($type:ty, $read_method:ident, $try_read_method:ident, $write_method:ident)*) => {
         $(
             #[doc = "Implementation of [`ReadRPC`] trait for [`"]
             #[doc = stringify!($type)]
             #[doc = "`]."]
             impl ReadRPC for $type {
-                fn rpc_read_from<T: Read>(reader: &mut T) -> Self {
+                fn rpc_read_from<T: PbcRead>(reader: &mut T) -> Self {
                     reader.$read_method()
                 }
+
+                fn try_rpc_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+                    reader.$try_read_method()
+                }
             }
 
             #[doc = "Implementation of [`WriteRPC`] trait for [`"]
             #[doc = stringify!($type)]
             #[doc = "`]."]
             impl WriteRPC for $type {
-                fn rpc_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+                fn rpc_write_to<T: PbcWrite>(&self, writer: &mut T) -> io::Result<()> {
                     writer.$write_method(*self)
                 }
+
+                fn rpc_serialized_size(&self) -> usize {
+                    core::mem::size_of::<$type>()
+                }
             }
         )*
     }
 }
 
-rw_int!(u8, read_u8, write_u8);
-rw_int!(u16, read_u16_be, write_u16_be);
-rw_int!(u32, read_u32_be, write_u32_be);
-rw_int!(u64, read_u64_be, write_u64_be);
-rw_int!(u128, read_u128_be, write_u128_be);
+rw_int!(u8, read_u8, try_read_u8, write_u8);
+rw_int!(u16, read_u16_be, try_read_u16_be, write_u16_be);
+rw_int!(u32, read_u32_be, try_read_u32_be, write_u32_be);
+rw_int!(u64, read_u64_be, try_read_u64_be, write_u64_be);
+rw_int!(u128, read_u128_be, try_read_u128_be, write_u128_be);
 
-rw_int!(i8, read_i8, write_i8);
-rw_int!(i16, read_i16_be, write_i16_be);
-rw_int!(i32, read_i32_be, write_i32_be);
-rw_int!(i64, read_i64_be, write_i64_be);
-rw_int!(i128, read_i128_be, write_i128_be);
+rw_int!(i8, read_i8, try_read_i8, write_i8);
+rw_int!(i16, read_i16_be, try_read_i16_be, write_i16_be);
+rw_int!(i32, read_i32_be, try_read_i32_be, write_i32_be);
+rw_int!(i64, read_i64_be, try_read_i64_be, write_i64_be);
+rw_int!(i128, read_i128_be, try_read_i128_be, write_i128_be);
 
-/// Implementation of [`ReadRPC`] for byte arrays of arbitrary sizes.
-impl<const LEN: usize> ReadRPC for [u8; LEN] {
-    fn rpc_read_from<T: Read>(reader: &mut T) -> Self {
-        let mut buf: [u8; LEN] = [0; LEN];
-        reader.read_exact(&mut buf).unwrap();
-        buf
+/// Implementation of [`ReadRPC`] for arrays of arbitrary sizes, of any type that implements
+/// [`ReadRPC`].
+///
+/// Serialized as the concatenation of the elements, without a length.
+impl<const LEN: usize, ElementT: ReadRPC> ReadRPC for [ElementT; LEN] {
+    fn rpc_read_from<T: PbcRead>(reader: &mut T) -> Self {
+        core::array::from_fn(|_| ElementT::rpc_read_from(reader))
+    }
+
+    fn try_rpc_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        let mut elements = Vec::with_capacity(LEN);
+        for _ in 0..LEN {
+            elements.push(ElementT::try_rpc_read_from(reader)?);
+        }
+        match elements.try_into() {
+            Ok(array) => Ok(array),
+            Err(_) => unreachable!("Exactly {LEN} elements were read"),
+        }
+    }
+}
+
+/// Implementation of [`WriteRPC`] for arrays of arbitrary sizes, of any type that implements
+/// [`WriteRPC`].
+impl<const LEN: usize, ElementT: WriteRPC> WriteRPC for [ElementT; LEN] {
+    fn rpc_write_to<T: PbcWrite>(&self, writer: &mut T) -> io::Result<()> {
+        for element in self {
+            element.rpc_write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    fn rpc_serialized_size(&self) -> usize {
+        self.iter().map(WriteRPC::rpc_serialized_size).sum()
+    }
+}
+
+// Tuple implementations of [`ReadRPC`] and [`WriteRPC`]; tuples are serialized as the
+// concatenation of their elements.
+macro_rules! rw_tuple {
+    ( $( $name:ident $index:tt )+ ) => {
+        #[doc = "Implementation of [`ReadRPC`] for tuples of types that implement [`ReadRPC`]."]
+        impl<$($name: ReadRPC),+> ReadRPC for ($($name,)+) {
+            fn rpc_read_from<R: PbcRead>(reader: &mut R) -> Self {
+                ($($name::rpc_read_from(reader),)+)
+            }
+
+            fn try_rpc_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+                Ok(($($name::try_rpc_read_from(reader)?,)+))
+            }
+        }
+
+        #[doc = "Implementation of [`WriteRPC`] for tuples of types that implement [`WriteRPC`]."]
+        impl<$($name: WriteRPC),+> WriteRPC for ($($name,)+) {
+            fn rpc_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+                $(self.$index.rpc_write_to(writer)?;)+
+                Ok(())
+            }
+
+            fn rpc_serialized_size(&self) -> usize {
+                0 $(+ self.$index.rpc_serialized_size())+
+            }
+        }
+    };
+}
+
+rw_tuple! { A 0 }
+rw_tuple! { A 0 B 1 }
+rw_tuple! { A 0 B 1 C 2 }
+rw_tuple! { A 0 B 1 C 2 D 3 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10 L 11 }
+
+/// Implementation of [`ReadRPC`] for the unit type, which is serialized as no bytes.
+impl ReadRPC for () {
+    fn rpc_read_from<R: PbcRead>(_reader: &mut R) -> Self {}
+
+    fn try_rpc_read_from<R: PbcRead>(_reader: &mut R) -> Result<Self, DecodeError> {
+        Ok(())
+    }
+}
+
+/// Implementation of [`WriteRPC`] for the unit type, which is serialized as no bytes.
+impl WriteRPC for () {
+    fn rpc_write_to<W: PbcWrite>(&self, _writer: &mut W) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn rpc_serialized_size(&self) -> usize {
+        0
+    }
+}
+
+/// Implementation of [`ReadRPC`] for [`char`], serialized as its Unicode scalar value in a `u32`.
+impl ReadRPC for char {
+    fn rpc_read_from<R: PbcRead>(reader: &mut R) -> Self {
+        let value = reader.read_u32_be();
+        char::from_u32(value).unwrap_or_else(|| panic!("{value} is not a valid char"))
+    }
+
+    fn try_rpc_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        let value = reader.try_read_u32_be()?;
+        char::from_u32(value).ok_or(DecodeError::InvalidChar { value })
+    }
+}
+
+/// Implementation of [`WriteRPC`] for [`char`].
+impl WriteRPC for char {
+    fn rpc_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_u32_be(u32::from(*self))
+    }
+
+    fn rpc_serialized_size(&self) -> usize {
+        core::mem::size_of::<u32>()
     }
 }
 
-/// Implementation of [`WriteRPC`] for byte arrays of arbitrary sizes.
-impl<const LEN: usize> WriteRPC for [u8; LEN] {
-    fn rpc_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
-        writer.write_all(self)
+/// Implements [`ReadRPC`] and [`WriteRPC`] for non-zero integers, serialized identically to the
+/// underlying integer. Reading zero fails.
+macro_rules! rw_non_zero {
+    ($($type:ident, $int:ty)*) => {
+        $(
+            #[doc = concat!("Implementation of [`ReadRPC`] trait for [`", stringify!($type), "`].")]
+            impl ReadRPC for $type {
+                fn rpc_read_from<R: PbcRead>(reader: &mut R) -> Self {
+                    <$type>::new(<$int>::rpc_read_from(reader))
+                        .unwrap_or_else(|| panic!(concat!("Zero is not a valid ", stringify!($type))))
+                }
+
+                fn try_rpc_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+                    <$type>::new(<$int>::try_rpc_read_from(reader)?).ok_or(DecodeError::InvalidNonZero {
+                        type_name: stringify!($type),
+                    })
+                }
+            }
+
+            #[doc = concat!("Implementation of [`WriteRPC`] trait for [`", stringify!($type), "`].")]
+            impl WriteRPC for $type {
+                fn rpc_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+                    self.get().rpc_write_to(writer)
+                }
+
+                fn rpc_serialized_size(&self) -> usize {
+                    core::mem::size_of::<$int>()
+                }
+            }
+        )*
+    };
+}
+
+rw_non_zero!(
+    NonZeroU8, u8
+    NonZeroU16, u16
+    NonZeroU32, u32
+    NonZeroU64, u64
+    NonZeroU128, u128
+    NonZeroI8, i8
+    NonZeroI16, i16
+    NonZeroI32, i32
+    NonZeroI64, i64
+    NonZeroI128, i128
+);
+
+/// Implementation of [`ReadRPC`] for [`Box<T>`], serialized identically to `T`.
+impl<T: ReadRPC> ReadRPC for Box<T> {
+    fn rpc_read_from<R: PbcRead>(reader: &mut R) -> Self {
+        Box::new(T::rpc_read_from(reader))
+    }
+
+    fn try_rpc_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        T::try_rpc_read_from(reader).map(Box::new)
+    }
+}
+
+/// Implementation of [`WriteRPC`] for [`Box<T>`], serialized identically to `T`.
+impl<T: WriteRPC> WriteRPC for Box<T> {
+    fn rpc_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+        (**self).rpc_write_to(writer)
+    }
+
+    fn rpc_serialized_size(&self) -> usize {
+        (**self).rpc_serialized_size()
+    }
+}
+
+/// Implementation of [`ReadRPC`] for [`Rc<T>`], serialized identically to `T`.
+impl<T: ReadRPC> ReadRPC for Rc<T> {
+    fn rpc_read_from<R: PbcRead>(reader: &mut R) -> Self {
+        Rc::new(T::rpc_read_from(reader))
+    }
+
+    fn try_rpc_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        T::try_rpc_read_from(reader).map(Rc::new)
+    }
+}
+
+/// Implementation of [`WriteRPC`] for [`Rc<T>`], serialized identically to `T`.
+impl<T: WriteRPC> WriteRPC for Rc<T> {
+    fn rpc_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+        (**self).rpc_write_to(writer)
+    }
+
+    fn rpc_serialized_size(&self) -> usize {
+        (**self).rpc_serialized_size()
+    }
+}
+
+/// Implementation of [`ReadRPC`] for [`Cow<str>`], serialized identically to [`String`]. Always
+/// reads an owned string.
+impl ReadRPC for Cow<'_, str> {
+    fn rpc_read_from<R: PbcRead>(reader: &mut R) -> Self {
+        Cow::Owned(String::rpc_read_from(reader))
+    }
+
+    fn try_rpc_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        String::try_rpc_read_from(reader).map(Cow::Owned)
+    }
+}
+
+/// Implementation of [`WriteRPC`] for [`Cow<str>`], serialized identically to [`String`].
+impl WriteRPC for Cow<'_, str> {
+    fn rpc_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+        let utf_bytes = self.as_bytes();
+        writer.write_u32_be(utf_bytes.len() as u32)?;
+        writer.write_bytes(utf_bytes)
+    }
+
+    fn rpc_serialized_size(&self) -> usize {
+        core::mem::size_of::<u32>() + self.len()
     }
 }
@@ -0,0 +1,248 @@
+use super::{skip_length, skip_values, ReadWriteState};
+use crate::canonical::try_read_keyed;
+use crate::decode_error::length_to_usize;
+use crate::io::{self, PbcRead, PbcWrite};
+use crate::read_int::ReadInt;
+use crate::write_int::WriteInt;
+use crate::DecodeError;
+use alloc::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::hash::{BuildHasher, Hash};
+
+/// Implementation of the [`ReadWriteState`] trait for [`BTreeMap<K, V>`] for any `K` and `V`
+/// that implement [`ReadWriteState`].
+///
+/// Serialized identically to a [`Vec`] of key-value pairs, sorted by key.
+///
+/// Fallible reads reject duplicate keys, and [strict readers](crate::io::Strict) also reject
+/// keys that are not sorted.
+impl<K: ReadWriteState + Ord, V: ReadWriteState> ReadWriteState for BTreeMap<K, V> {
+    /// The map is stored behind a pointer, so must be `false`.
+    const SERIALIZABLE_BY_COPY: bool = false;
+
+    fn state_read_from<R: PbcRead>(reader: &mut R) -> Self {
+        let len = reader.read_u32_le();
+        (0..len)
+            .map(|_| (K::state_read_from(reader), V::state_read_from(reader)))
+            .collect()
+    }
+
+    fn try_state_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        let len = length_to_usize(reader.try_read_u32_le()?)?;
+        let entries = try_read_keyed(
+            reader,
+            len,
+            |reader| {
+                Ok((
+                    K::try_state_read_from(reader)?,
+                    V::try_state_read_from(reader)?,
+                ))
+            },
+            |(key, _)| key,
+        )?;
+        Ok(entries.into_iter().collect())
+    }
+
+    fn state_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+        write_entries(self.len(), self.iter(), writer)
+    }
+
+    fn state_serialized_size(&self) -> usize {
+        entries_size(self.iter())
+    }
+
+    fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+        entries_skip::<K, V>(bytes)
+    }
+}
+
+/// Implementation of the [`ReadWriteState`] trait for [`BTreeSet<T>`] for any `T` that
+/// implements [`ReadWriteState`].
+///
+/// Serialized identically to a sorted [`Vec`] of the elements.
+///
+/// Fallible reads reject duplicate elements, and [strict readers](crate::io::Strict) also
+/// reject elements that are not sorted.
+impl<T: ReadWriteState + Ord> ReadWriteState for BTreeSet<T> {
+    /// The set is stored behind a pointer, so must be `false`.
+    const SERIALIZABLE_BY_COPY: bool = false;
+
+    fn state_read_from<R: PbcRead>(reader: &mut R) -> Self {
+        let len = reader.read_u32_le();
+        (0..len).map(|_| T::state_read_from(reader)).collect()
+    }
+
+    fn try_state_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        let len = length_to_usize(reader.try_read_u32_le()?)?;
+        let elements = try_read_keyed(reader, len, T::try_state_read_from, |element| element)?;
+        Ok(elements.into_iter().collect())
+    }
+
+    fn state_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+        write_elements(self.len(), self.iter(), writer)
+    }
+
+    fn state_serialized_size(&self) -> usize {
+        elements_size(self.iter())
+    }
+
+    fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+        elements_skip::<T>(bytes)
+    }
+}
+
+/// Implementation of the [`ReadWriteState`] trait for [`HashMap<K, V>`] for any `K` and `V`
+/// that implement [`ReadWriteState`].
+///
+/// Serialized identically to [`BTreeMap<K, V>`]; entries are sorted by key before writing, such
+/// that the serialization is independent of the iteration order of the map.
+#[cfg(feature = "std")]
+impl<K, V, S> ReadWriteState for HashMap<K, V, S>
+where
+    K: ReadWriteState + Ord + Hash,
+    V: ReadWriteState,
+    S: BuildHasher + Default,
+{
+    /// The map is stored behind a pointer, so must be `false`.
+    const SERIALIZABLE_BY_COPY: bool = false;
+
+    fn state_read_from<R: PbcRead>(reader: &mut R) -> Self {
+        let len = reader.read_u32_le();
+        (0..len)
+            .map(|_| (K::state_read_from(reader), V::state_read_from(reader)))
+            .collect()
+    }
+
+    fn try_state_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        let len = length_to_usize(reader.try_read_u32_le()?)?;
+        let entries = try_read_keyed(
+            reader,
+            len,
+            |reader| {
+                Ok((
+                    K::try_state_read_from(reader)?,
+                    V::try_state_read_from(reader)?,
+                ))
+            },
+            |(key, _)| key,
+        )?;
+        Ok(entries.into_iter().collect())
+    }
+
+    fn state_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+        let mut entries: Vec<(&K, &V)> = self.iter().collect();
+        entries.sort_unstable_by_key(|(key, _)| *key);
+        write_entries(entries.len(), entries.into_iter(), writer)
+    }
+
+    fn state_serialized_size(&self) -> usize {
+        entries_size(self.iter())
+    }
+
+    fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+        entries_skip::<K, V>(bytes)
+    }
+}
+
+/// Implementation of the [`ReadWriteState`] trait for [`HashSet<T>`] for any `T` that
+/// implements [`ReadWriteState`].
+///
+/// Serialized identically to [`BTreeSet<T>`]; elements are sorted before writing, such that the
+/// serialization is independent of the iteration order of the set.
+#[cfg(feature = "std")]
+impl<T, S> ReadWriteState for HashSet<T, S>
+where
+    T: ReadWriteState + Ord + Hash,
+    S: BuildHasher + Default,
+{
+    /// The set is stored behind a pointer, so must be `false`.
+    const SERIALIZABLE_BY_COPY: bool = false;
+
+    fn state_read_from<R: PbcRead>(reader: &mut R) -> Self {
+        let len = reader.read_u32_le();
+        (0..len).map(|_| T::state_read_from(reader)).collect()
+    }
+
+    fn try_state_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        let len = length_to_usize(reader.try_read_u32_le()?)?;
+        let elements = try_read_keyed(reader, len, T::try_state_read_from, |element| element)?;
+        Ok(elements.into_iter().collect())
+    }
+
+    fn state_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+        let mut elements: Vec<&T> = self.iter().collect();
+        elements.sort_unstable();
+        write_elements(elements.len(), elements.into_iter(), writer)
+    }
+
+    fn state_serialized_size(&self) -> usize {
+        elements_size(self.iter())
+    }
+
+    fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+        elements_skip::<T>(bytes)
+    }
+}
+
+/// Handles serialization of the given number of key-value pairs.
+fn write_entries<'a, W: PbcWrite, K: ReadWriteState + 'a, V: ReadWriteState + 'a>(
+    len: usize,
+    entries: impl Iterator<Item = (&'a K, &'a V)>,
+    writer: &mut W,
+) -> io::Result<()> {
+    writer.write_u32_le(len as u32)?;
+    for (key, value) in entries {
+        key.state_write_to(writer)?;
+        value.state_write_to(writer)?;
+    }
+    Ok(())
+}
+
+/// Handles serialization of the given number of elements.
+fn write_elements<'a, W: PbcWrite, T: ReadWriteState + 'a>(
+    len: usize,
+    elements: impl Iterator<Item = &'a T>,
+    writer: &mut W,
+) -> io::Result<()> {
+    writer.write_u32_le(len as u32)?;
+    for element in elements {
+        element.state_write_to(writer)?;
+    }
+    Ok(())
+}
+
+/// Size of the serialization of the given key-value pairs, including the length.
+fn entries_size<'a, K: ReadWriteState + 'a, V: ReadWriteState + 'a>(
+    entries: impl Iterator<Item = (&'a K, &'a V)>,
+) -> usize {
+    let content: usize = entries
+        .map(|(key, value)| key.state_serialized_size() + value.state_serialized_size())
+        .sum();
+    core::mem::size_of::<u32>() + content
+}
+
+/// Size of the serialization of the given elements, including the length.
+fn elements_size<'a, T: ReadWriteState + 'a>(elements: impl Iterator<Item = &'a T>) -> usize {
+    let content: usize = elements.map(ReadWriteState::state_serialized_size).sum();
+    core::mem::size_of::<u32>() + content
+}
+
+/// Skips the serialization of key-value pairs at the start of `bytes`, including the length.
+fn entries_skip<K: ReadWriteState, V: ReadWriteState>(bytes: &[u8]) -> Result<usize, DecodeError> {
+    let len = skip_length(bytes)?;
+    let mut offset = core::mem::size_of::<u32>();
+    for _ in 0..len {
+        offset += K::state_skip(&bytes[offset..])?;
+        offset += V::state_skip(&bytes[offset..])?;
+    }
+    Ok(offset)
+}
+
+/// Skips the serialization of elements at the start of `bytes`, including the length.
+fn elements_skip<T: ReadWriteState>(bytes: &[u8]) -> Result<usize, DecodeError> {
+    let len = skip_length(bytes)?;
+    let content = skip_values::<T>(&bytes[core::mem::size_of::<u32>()..], len)?;
+    Ok(core::mem::size_of::<u32>() + content)
+}
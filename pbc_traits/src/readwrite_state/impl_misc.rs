@@ -1,7 +1,15 @@
-use super::ReadWriteState;
+use super::{skip_bytes, skip_length, skip_values, ReadWriteState};
+use crate::canonical::{try_read_bool, try_read_option_marker};
+use crate::io::{self, PbcRead, PbcWrite};
 use crate::read_int::ReadInt;
 use crate::write_int::WriteInt;
-use std::io::{Read, Write};
+use crate::DecodeError;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+    NonZeroU32, NonZeroU64, NonZeroU8,
+};
 
 /// Implementation of the [`ReadWriteState`] trait for [`Option<T>`] for any `T` that
 /// implements [`ReadWriteState`].
@@ -9,7 +17,7 @@ impl<T: ReadWriteState> ReadWriteState for Option<T> {
     /// Not supported ATM, due to unknown memory layout. Might require ABI changes.
     const SERIALIZABLE_BY_COPY: bool = false;
 
-    fn state_read_from<R: Read>(reader: &mut R) -> Self {
+    fn state_read_from<R: PbcRead>(reader: &mut R) -> Self {
         let marker = reader.read_u8();
         match marker {
             0 => None,
@@ -17,7 +25,14 @@ impl<T: ReadWriteState> ReadWriteState for Option<T> {
         }
     }
 
-    fn state_write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+    fn try_state_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        match try_read_option_marker(reader)? {
+            false => Ok(None),
+            true => Ok(Some(T::try_state_read_from(reader)?)),
+        }
+    }
+
+    fn state_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
         match &self {
             None => writer.write_u8(0),
             Some(value) => {
@@ -26,6 +41,21 @@ impl<T: ReadWriteState> ReadWriteState for Option<T> {
             }
         }
     }
+
+    fn state_serialized_size(&self) -> usize {
+        match &self {
+            None => 1,
+            Some(value) => 1 + value.state_serialized_size(),
+        }
+    }
+
+    fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+        match bytes.first() {
+            None => Err(DecodeError::UnexpectedEof),
+            Some(0) => Ok(1),
+            Some(_) => Ok(1 + T::state_skip(&bytes[1..])?),
+        }
+    }
 }
 
 impl ReadWriteState for String {
@@ -36,45 +66,69 @@ impl ReadWriteState for String {
     /// the write method writes using slices of bytes and the read method reads vectors of bytes.
     ///
     /// The reason this asymmetry works is that a &\[u8] is the result of borrowing a Vec\<u8>.
-    fn state_read_from<T: Read>(reader: &mut T) -> Self {
+    fn state_read_from<T: PbcRead>(reader: &mut T) -> Self {
         // We can read this as an vector of bytes even though we wrote it as a slice,
         // since a byte slice &[u8] is simply a borrowed Vec<u8>.
         let vec: Vec<u8> = Vec::state_read_from(reader);
         String::from_utf8(vec).unwrap()
     }
 
-    fn state_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn try_state_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        let vec: Vec<u8> = Vec::try_state_read_from(reader)?;
+        String::from_utf8(vec).map_err(|_| DecodeError::InvalidUtf8)
+    }
+
+    fn state_write_to<T: PbcWrite>(&self, writer: &mut T) -> io::Result<()> {
         let utf_bytes = self.as_bytes();
         writer.write_u32_le(utf_bytes.len() as u32).unwrap();
-        writer.write_all(utf_bytes)
+        writer.write_bytes(utf_bytes)
+    }
+
+    fn state_serialized_size(&self) -> usize {
+        core::mem::size_of::<u32>() + self.len()
+    }
+
+    /// Skips the string without validating that it is UTF-8.
+    fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+        let len = skip_length(bytes)?;
+        let content = skip_bytes(&bytes[core::mem::size_of::<u32>()..], len)?;
+        Ok(core::mem::size_of::<u32>() + content)
     }
 }
 
 /// Implementation of the [`ReadWriteState`] trait for [`bool`].
 impl ReadWriteState for bool {
     const SERIALIZABLE_BY_COPY: bool = true;
-    fn state_read_from<T: Read>(reader: &mut T) -> Self {
+    fn state_read_from<T: PbcRead>(reader: &mut T) -> Self {
         reader.read_u8() != 0
     }
 
-    fn state_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn try_state_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        try_read_bool(reader)
+    }
+
+    fn state_write_to<T: PbcWrite>(&self, writer: &mut T) -> io::Result<()> {
         writer.write_u8(u8::from(*self))
     }
 }
 
 macro_rules! rw_int_copyable {
-    ($($type:ty, $read_method:ident, $write_method:ident)*) => {
+    ($($type:ty, $read_method:ident, $try_read_method:ident, $write_method:ident)*) => {
         $(
             #[doc = "Implementation of [`ReadWriteState`] trait for [`"]
             #[doc = stringify!($type)]
             #[doc = "`]."]
             impl ReadWriteState for $type {
                 const SERIALIZABLE_BY_COPY: bool = true;
-                fn state_read_from<T: Read>(reader: &mut T) -> Self {
+                fn state_read_from<T: PbcRead>(reader: &mut T) -> Self {
                     reader.$read_method()
                 }
 
-                fn state_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+                fn try_state_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+                    reader.$try_read_method()
+                }
+
+                fn state_write_to<T: PbcWrite>(&self, writer: &mut T) -> io::Result<()> {
                     writer.$write_method(*self)
                 }
             }
@@ -82,35 +136,136 @@ macro_rules! rw_int_copyable {
     }
 }
 
-rw_int_copyable!(u8, read_u8, write_u8);
-rw_int_copyable!(i8, read_i8, write_i8);
-rw_int_copyable!(u16, read_u16_le, write_u16_le);
-rw_int_copyable!(u32, read_u32_le, write_u32_le);
-rw_int_copyable!(u64, read_u64_le, write_u64_le);
-rw_int_copyable!(u128, read_u128_le, write_u128_le);
+rw_int_copyable!(u8, read_u8, try_read_u8, write_u8);
+rw_int_copyable!(i8, read_i8, try_read_i8, write_i8);
+rw_int_copyable!(u16, read_u16_le, try_read_u16_le, write_u16_le);
+rw_int_copyable!(u32, read_u32_le, try_read_u32_le, write_u32_le);
+rw_int_copyable!(u64, read_u64_le, try_read_u64_le, write_u64_le);
+rw_int_copyable!(u128, read_u128_le, try_read_u128_le, write_u128_le);
 
-rw_int_copyable!(i16, read_i16_le, write_i16_le);
-rw_int_copyable!(i32, read_i32_le, write_i32_le);
-rw_int_copyable!(i64, read_i64_le, write_i64_le);
-rw_int_copyable!(i128, read_i128_le, write_i128_le);
+rw_int_copyable!(i16, read_i16_le, try_read_i16_le, write_i16_le);
+rw_int_copyable!(i32, read_i32_le, try_read_i32_le, write_i32_le);
+rw_int_copyable!(i64, read_i64_le, try_read_i64_le, write_i64_le);
+rw_int_copyable!(i128, read_i128_le, try_read_i128_le, write_i128_le);
 
 /// Implementation of [`ReadWriteState`] for byte arrays of arbitrary sizes.
 impl<const LEN: usize, ElementT: ReadWriteState + Sized> ReadWriteState for [ElementT; LEN] {
     const SERIALIZABLE_BY_COPY: bool = <ElementT as ReadWriteState>::SERIALIZABLE_BY_COPY;
 
-    fn state_read_from<T: Read>(reader: &mut T) -> Self {
-        let mut data: [std::mem::MaybeUninit<ElementT>; LEN] =
-            unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    fn state_read_from<T: PbcRead>(reader: &mut T) -> Self {
+        let mut data: [core::mem::MaybeUninit<ElementT>; LEN] =
+            unsafe { core::mem::MaybeUninit::uninit().assume_init() };
         for element_addr in &mut data[..] {
             element_addr.write(<ElementT as ReadWriteState>::state_read_from(reader));
         }
         data.map(|x| unsafe { x.assume_init() })
     }
 
-    fn state_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn try_state_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        let mut elements = Vec::with_capacity(LEN);
+        for _ in 0..LEN {
+            elements.push(<ElementT as ReadWriteState>::try_state_read_from(reader)?);
+        }
+        match elements.try_into() {
+            Ok(array) => Ok(array),
+            Err(_) => unreachable!("Exactly {LEN} elements were read"),
+        }
+    }
+
+    fn state_write_to<T: PbcWrite>(&self, writer: &mut T) -> io::Result<()> {
         for elem in self {
             <ElementT as ReadWriteState>::state_write_to(elem, writer)?;
         }
         Ok(())
     }
+
+    fn state_serialized_size(&self) -> usize {
+        match Self::SERIALIZABLE_BY_COPY {
+            true => core::mem::size_of::<Self>(),
+            false => self.iter().map(ReadWriteState::state_serialized_size).sum(),
+        }
+    }
+
+    fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+        skip_values::<ElementT>(bytes, LEN)
+    }
 }
+
+/// Implementation of [`ReadWriteState`] for [`char`], serialized as its Unicode scalar value in a
+/// `u32`.
+impl ReadWriteState for char {
+    /// Not every `u32` is a valid `char`, so must be `false`.
+    const SERIALIZABLE_BY_COPY: bool = false;
+
+    fn state_read_from<T: PbcRead>(reader: &mut T) -> Self {
+        let value = reader.read_u32_le();
+        char::from_u32(value).unwrap_or_else(|| panic!("{value} is not a valid char"))
+    }
+
+    fn try_state_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        let value = reader.try_read_u32_le()?;
+        char::from_u32(value).ok_or(DecodeError::InvalidChar { value })
+    }
+
+    fn state_write_to<T: PbcWrite>(&self, writer: &mut T) -> io::Result<()> {
+        writer.write_u32_le(u32::from(*self))
+    }
+
+    fn state_serialized_size(&self) -> usize {
+        core::mem::size_of::<u32>()
+    }
+
+    fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+        skip_bytes(bytes, core::mem::size_of::<u32>())
+    }
+}
+
+/// Implements [`ReadWriteState`] for non-zero integers, serialized identically to the underlying
+/// integer. Reading zero fails.
+macro_rules! rw_non_zero {
+    ($($type:ident, $int:ty)*) => {
+        $(
+            #[doc = concat!("Implementation of [`ReadWriteState`] trait for [`", stringify!($type), "`].")]
+            impl ReadWriteState for $type {
+                /// Zero is not a valid value, so must be `false`.
+                const SERIALIZABLE_BY_COPY: bool = false;
+
+                fn state_read_from<T: PbcRead>(reader: &mut T) -> Self {
+                    <$type>::new(<$int>::state_read_from(reader))
+                        .unwrap_or_else(|| panic!(concat!("Zero is not a valid ", stringify!($type))))
+                }
+
+                fn try_state_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+                    <$type>::new(<$int>::try_state_read_from(reader)?).ok_or(DecodeError::InvalidNonZero {
+                        type_name: stringify!($type),
+                    })
+                }
+
+                fn state_write_to<T: PbcWrite>(&self, writer: &mut T) -> io::Result<()> {
+                    self.get().state_write_to(writer)
+                }
+
+                fn state_serialized_size(&self) -> usize {
+                    core::mem::size_of::<$int>()
+                }
+
+                fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+                    skip_bytes(bytes, core::mem::size_of::<$int>())
+                }
+            }
+        )*
+    };
+}
+
+rw_non_zero!(
+    NonZeroU8, u8
+    NonZeroU16, u16
+    NonZeroU32, u32
+    NonZeroU64, u64
+    NonZeroU128, u128
+    NonZeroI8, i8
+    NonZeroI16, i16
+    NonZeroI32, i32
+    NonZeroI64, i64
+    NonZeroI128, i128
+);
@@ -0,0 +1,73 @@
+use super::ReadWriteState;
+use crate::io::{self, PbcRead, PbcWrite};
+use crate::write_int::WriteInt;
+use crate::DecodeError;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::String;
+
+// Smart pointers are serialized identically to the value they point to, which allows recursive
+// types such as linked lists and trees to be stored in state.
+macro_rules! rw_pointer {
+    ($($pointer:ident)*) => {
+        $(
+            #[doc = concat!("Implementation of [`ReadWriteState`] for [`", stringify!($pointer), "<T>`], serialized identically to `T`.")]
+            impl<T: ReadWriteState> ReadWriteState for $pointer<T> {
+                /// The value is stored behind a pointer, so must be `false`.
+                const SERIALIZABLE_BY_COPY: bool = false;
+
+                fn state_read_from<R: PbcRead>(reader: &mut R) -> Self {
+                    $pointer::new(T::state_read_from(reader))
+                }
+
+                fn try_state_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+                    T::try_state_read_from(reader).map($pointer::new)
+                }
+
+                fn state_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+                    (**self).state_write_to(writer)
+                }
+
+                fn state_serialized_size(&self) -> usize {
+                    (**self).state_serialized_size()
+                }
+
+                fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+                    T::state_skip(bytes)
+                }
+            }
+        )*
+    };
+}
+
+rw_pointer!(Box Rc);
+
+/// Implementation of [`ReadWriteState`] for [`Cow<str>`], serialized identically to [`String`].
+/// Always reads an owned string.
+impl ReadWriteState for Cow<'_, str> {
+    /// Impossible due to internal pointers.
+    const SERIALIZABLE_BY_COPY: bool = false;
+
+    fn state_read_from<R: PbcRead>(reader: &mut R) -> Self {
+        Cow::Owned(String::state_read_from(reader))
+    }
+
+    fn try_state_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        String::try_state_read_from(reader).map(Cow::Owned)
+    }
+
+    fn state_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+        let utf_bytes = self.as_bytes();
+        writer.write_u32_le(utf_bytes.len() as u32)?;
+        writer.write_bytes(utf_bytes)
+    }
+
+    fn state_serialized_size(&self) -> usize {
+        core::mem::size_of::<u32>() + self.len()
+    }
+
+    fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+        String::state_skip(bytes)
+    }
+}
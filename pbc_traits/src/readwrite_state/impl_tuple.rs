@@ -0,0 +1,75 @@
+use super::ReadWriteState;
+use crate::io::{self, PbcRead, PbcWrite};
+use crate::DecodeError;
+
+// Tuple implementations of [`ReadWriteState`]; tuples are serialized as the concatenation of their
+// elements.
+macro_rules! rw_tuple {
+    ( $( $name:ident $index:tt )+ ) => {
+        #[doc = "Implementation of [`ReadWriteState`] for tuples of types that implement [`ReadWriteState`]."]
+        impl<$($name: ReadWriteState),+> ReadWriteState for ($($name,)+) {
+            /// The memory layout of tuples is unspecified, so must be `false`.
+            const SERIALIZABLE_BY_COPY: bool = false;
+
+            fn state_read_from<R: PbcRead>(reader: &mut R) -> Self {
+                ($($name::state_read_from(reader),)+)
+            }
+
+            fn try_state_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+                Ok(($($name::try_state_read_from(reader)?,)+))
+            }
+
+            fn state_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
+                $(self.$index.state_write_to(writer)?;)+
+                Ok(())
+            }
+
+            fn state_serialized_size(&self) -> usize {
+                0 $(+ self.$index.state_serialized_size())+
+            }
+
+            fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+                let mut offset = 0;
+                $(offset += $name::state_skip(&bytes[offset..])?;)+
+                Ok(offset)
+            }
+        }
+    };
+}
+
+/// Implementation of [`ReadWriteState`] for the unit type, which is serialized as no bytes.
+impl ReadWriteState for () {
+    /// The unit type has no bytes to copy, so must be `false`.
+    const SERIALIZABLE_BY_COPY: bool = false;
+
+    fn state_read_from<R: PbcRead>(_reader: &mut R) -> Self {}
+
+    fn try_state_read_from<R: PbcRead>(_reader: &mut R) -> Result<Self, DecodeError> {
+        Ok(())
+    }
+
+    fn state_write_to<W: PbcWrite>(&self, _writer: &mut W) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn state_serialized_size(&self) -> usize {
+        0
+    }
+
+    fn state_skip(_bytes: &[u8]) -> Result<usize, DecodeError> {
+        Ok(0)
+    }
+}
+
+rw_tuple! { A 0 }
+rw_tuple! { A 0 B 1 }
+rw_tuple! { A 0 B 1 C 2 }
+rw_tuple! { A 0 B 1 C 2 D 3 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10 }
+rw_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10 L 11 }
@@ -1,28 +1,50 @@
-use super::ReadWriteState;
+use super::{skip_length, skip_values, ReadWriteState};
+use crate::decode_error::length_to_usize;
+use crate::io::{self, PbcRead, PbcWrite};
 use crate::read_int::ReadInt;
 use crate::write_int::WriteInt;
-use std::collections::VecDeque;
-use std::io::{Read, Write};
-use std::mem::MaybeUninit;
+use crate::DecodeError;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
 
 /// Implementation of the [`ReadWriteState`] trait for [`Vec<T>`] for any `T` that implements [`ReadWriteState`].
 impl<T: ReadWriteState> ReadWriteState for Vec<T> {
     /// The vector buffer is stored behind a pointer, so must be `false`.
     const SERIALIZABLE_BY_COPY: bool = false;
 
-    fn state_read_from<R: Read>(reader: &mut R) -> Self {
+    fn state_read_from<R: PbcRead>(reader: &mut R) -> Self {
         match T::SERIALIZABLE_BY_COPY {
             true => static_sized_content_read_from(reader),
             false => dynamic_sized_content_read_from(reader),
         }
     }
 
-    fn state_write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+    /// Reads elements one at a time regardless of [`ReadWriteState::SERIALIZABLE_BY_COPY`], as
+    /// the length prefix cannot be trusted to allocate the entire buffer up front.
+    fn try_state_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        let len = length_to_usize(reader.try_read_u32_le()?)?;
+        let mut result = Vec::with_capacity(usize::min(len, 128usize));
+        for _ in 0..len {
+            result.push(T::try_state_read_from(reader)?)
+        }
+        Ok(result)
+    }
+
+    fn state_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
         match T::SERIALIZABLE_BY_COPY {
             true => static_sized_content_write_to([self], writer),
             false => dynamic_sized_content_write_to([self], writer),
         }
     }
+
+    fn state_serialized_size(&self) -> usize {
+        content_size([self])
+    }
+
+    fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+        content_skip::<T>(bytes)
+    }
 }
 
 /// Implementation of the [`ReadWriteState`] trait for [`VecDeque<T>`] for any `T` that implements [`ReadWriteState`].
@@ -30,13 +52,18 @@ impl<T: ReadWriteState> ReadWriteState for VecDeque<T> {
     /// The vector buffer is stored behind a pointer, so must be `false`.
     const SERIALIZABLE_BY_COPY: bool = false;
 
-    fn state_read_from<R: Read>(reader: &mut R) -> Self {
+    fn state_read_from<R: PbcRead>(reader: &mut R) -> Self {
         // Implementation reads as vec before converting to vecdeque
         let as_vec = Vec::<T>::state_read_from(reader);
         VecDeque::from(as_vec)
     }
 
-    fn state_write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+    fn try_state_read_from<R: PbcRead>(reader: &mut R) -> Result<Self, DecodeError> {
+        let as_vec = Vec::<T>::try_state_read_from(reader)?;
+        Ok(VecDeque::from(as_vec))
+    }
+
+    fn state_write_to<W: PbcWrite>(&self, writer: &mut W) -> io::Result<()> {
         let (slice_front, slice_back) = self.as_slices();
         let slices = [slice_front, slice_back];
         match T::SERIALIZABLE_BY_COPY {
@@ -44,6 +71,15 @@ impl<T: ReadWriteState> ReadWriteState for VecDeque<T> {
             false => dynamic_sized_content_write_to(slices, writer),
         }
     }
+
+    fn state_serialized_size(&self) -> usize {
+        let (slice_front, slice_back) = self.as_slices();
+        content_size([slice_front, slice_back])
+    }
+
+    fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+        content_skip::<T>(bytes)
+    }
 }
 
 const fn length_of_slices<T, const N: usize>(slices: [&[T]; N]) -> usize {
@@ -56,8 +92,28 @@ const fn length_of_slices<T, const N: usize>(slices: [&[T]; N]) -> usize {
     summed_length
 }
 
+/// Size of the serialization of the given slices as a single vec, including the length.
+fn content_size<T: ReadWriteState, const N: usize>(slices: [&[T]; N]) -> usize {
+    let content: usize = match T::SERIALIZABLE_BY_COPY {
+        true => length_of_slices(slices) * core::mem::size_of::<T>(),
+        false => slices
+            .iter()
+            .flat_map(|slice| slice.iter())
+            .map(ReadWriteState::state_serialized_size)
+            .sum(),
+    };
+    core::mem::size_of::<u32>() + content
+}
+
+/// Skips the serialization of a vec at the start of `bytes`, including the length.
+fn content_skip<T: ReadWriteState>(bytes: &[u8]) -> Result<usize, DecodeError> {
+    let len = skip_length(bytes)?;
+    let content = skip_values::<T>(&bytes[core::mem::size_of::<u32>()..], len)?;
+    Ok(core::mem::size_of::<u32>() + content)
+}
+
 /// Handles deserialization for vecs with dynamic-sized contents.
-fn dynamic_sized_content_read_from<R: Read, T: ReadWriteState>(reader: &mut R) -> Vec<T> {
+fn dynamic_sized_content_read_from<R: PbcRead, T: ReadWriteState>(reader: &mut R) -> Vec<T> {
     let len = reader.read_u32_le() as usize;
     let mut result = Vec::with_capacity(len);
     for _ in 0..len {
@@ -67,10 +123,10 @@ fn dynamic_sized_content_read_from<R: Read, T: ReadWriteState>(reader: &mut R) -
 }
 
 /// Handles serialization for vecs with dynamic-sized contents.
-fn dynamic_sized_content_write_to<W: Write, T: ReadWriteState, const N: usize>(
+fn dynamic_sized_content_write_to<W: PbcWrite, T: ReadWriteState, const N: usize>(
     slices: [&[T]; N],
     writer: &mut W,
-) -> std::io::Result<()> {
+) -> io::Result<()> {
     writer
         .write_u32_le(length_of_slices(slices) as u32)
         .unwrap();
@@ -84,41 +140,41 @@ fn dynamic_sized_content_write_to<W: Write, T: ReadWriteState, const N: usize>(
 }
 
 /// Handles deserialization for vecs with static-sized contents.
-fn static_sized_content_read_from<R: Read, T: ReadWriteState>(reader: &mut R) -> Vec<T> {
+fn static_sized_content_read_from<R: PbcRead, T: ReadWriteState>(reader: &mut R) -> Vec<T> {
     assert!(T::SERIALIZABLE_BY_COPY);
 
     let count = reader.read_u32_le() as usize;
     let mut result: Vec<MaybeUninit<T>> = Vec::with_capacity(count);
     unsafe {
         result.set_len(count);
-        if std::mem::size_of::<T>() > 0 {
+        if core::mem::size_of::<T>() > 0 {
             let (prefix, middle, suffix) = result.align_to_mut::<u8>();
             assert!(prefix.is_empty());
             assert!(suffix.is_empty());
-            reader.read_exact(middle).unwrap();
+            reader.read_bytes(middle).unwrap();
         }
 
-        std::mem::transmute::<_, Vec<T>>(result)
+        core::mem::transmute::<_, Vec<T>>(result)
     }
 }
 
 /// Handles serialization for vecs with static-sized contents.
-fn static_sized_content_write_to<W: Write, T: ReadWriteState, const N: usize>(
+fn static_sized_content_write_to<W: PbcWrite, T: ReadWriteState, const N: usize>(
     slices: [&[T]; N],
     writer: &mut W,
-) -> std::io::Result<()> {
+) -> io::Result<()> {
     assert!(T::SERIALIZABLE_BY_COPY);
 
     writer
         .write_u32_le(length_of_slices(slices) as u32)
         .unwrap();
-    if std::mem::size_of::<T>() > 0 {
+    if core::mem::size_of::<T>() > 0 {
         for slice in slices {
             unsafe {
                 let (prefix, middle, suffix) = slice.align_to::<u8>();
                 assert!(prefix.is_empty());
                 assert!(suffix.is_empty());
-                writer.write_all(middle)?;
+                writer.write_bytes(middle)?;
             }
         }
     }
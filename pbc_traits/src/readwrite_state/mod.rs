@@ -1,14 +1,23 @@
 //! Module containing definition of readwrite state and implementations for several different data
 //! structures.
-use std::io::{Read, Write};
+use crate::io::{self, PbcRead, PbcWrite};
 
-//// Sub-modules
+use crate::decode_error::length_to_usize;
+use crate::read_int::ReadInt;
+use crate::{ByteCounter, DecodeError};
+
+// Sub-modules
+mod impl_map;
 mod impl_misc;
+mod impl_pointer;
+mod impl_tuple;
 mod impl_vec;
 
-//// Re-exported trait implementations
+// Re-exported trait implementations
 
+#[allow(unused_imports)]
 pub use impl_misc::*;
+#[allow(unused_imports)]
 pub use impl_vec::*;
 
 /// Marks implementations of the [State serialization format](https://partisiablockchain.gitlab.io/documentation/smart-contracts/smart-contract-binary-formats.html).
@@ -47,8 +56,83 @@ pub trait ReadWriteState: Sized {
     const SERIALIZABLE_BY_COPY: bool;
 
     /// Deserialization method for state.
-    fn state_read_from<T: Read>(reader: &mut T) -> Self;
+    fn state_read_from<T: PbcRead>(reader: &mut T) -> Self;
+
+    /// Fallible deserialization method for state.
+    ///
+    /// Returns a [`DecodeError`] when the input is malformed, where
+    /// [`state_read_from`](Self::state_read_from) would panic. The default implementation
+    /// delegates to [`state_read_from`](Self::state_read_from), and thus still panics; all
+    /// implementations in the SDK, including the derived ones, override it.
+    fn try_state_read_from<T: PbcRead>(reader: &mut T) -> Result<Self, DecodeError> {
+        Ok(Self::state_read_from(reader))
+    }
 
     /// Serialization method for state.
-    fn state_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()>;
+    fn state_write_to<T: PbcWrite>(&self, writer: &mut T) -> io::Result<()>;
+
+    /// Number of bytes written by [`state_write_to`](Self::state_write_to).
+    ///
+    /// For types that are [`SERIALIZABLE_BY_COPY`](Self::SERIALIZABLE_BY_COPY) the size is
+    /// computed at compile time. The default implementation for other types counts the bytes
+    /// written by [`state_write_to`](Self::state_write_to); all implementations in the SDK,
+    /// including the derived ones, compute the size without serializing.
+    fn state_serialized_size(&self) -> usize {
+        if Self::SERIALIZABLE_BY_COPY {
+            core::mem::size_of::<Self>()
+        } else {
+            ByteCounter::count_written(|writer| self.state_write_to(writer))
+        }
+    }
+
+    /// Number of bytes occupied by the serialized value at the start of `bytes`, determined
+    /// without decoding the value where possible.
+    ///
+    /// Used by [`StateView`](crate::StateView) to locate fields without decoding the fields
+    /// before them. Returns a [`DecodeError`] if `bytes` is too short. The default implementation
+    /// uses the size of [`SERIALIZABLE_BY_COPY`](Self::SERIALIZABLE_BY_COPY) types, and decodes
+    /// other types; all implementations in the SDK, including the derived ones for structs, skip
+    /// the value without decoding it.
+    fn state_skip(bytes: &[u8]) -> Result<usize, DecodeError> {
+        if Self::SERIALIZABLE_BY_COPY {
+            skip_bytes(bytes, core::mem::size_of::<Self>())
+        } else {
+            let mut reader = bytes;
+            Self::try_state_read_from(&mut reader)?;
+            Ok(bytes.len() - reader.len())
+        }
+    }
+}
+
+/// Skips `count` bytes at the start of `bytes`, failing if there are fewer.
+pub(crate) fn skip_bytes(bytes: &[u8], count: usize) -> Result<usize, DecodeError> {
+    if bytes.len() < count {
+        Err(DecodeError::UnexpectedEof)
+    } else {
+        Ok(count)
+    }
+}
+
+/// Skips `count` consecutive values of type `T` at the start of `bytes`.
+pub(crate) fn skip_values<T: ReadWriteState>(
+    bytes: &[u8],
+    count: usize,
+) -> Result<usize, DecodeError> {
+    if T::SERIALIZABLE_BY_COPY {
+        let size = count
+            .checked_mul(core::mem::size_of::<T>())
+            .ok_or(DecodeError::UnexpectedEof)?;
+        return skip_bytes(bytes, size);
+    }
+    let mut offset = 0;
+    for _ in 0..count {
+        offset += T::state_skip(&bytes[offset..])?;
+    }
+    Ok(offset)
+}
+
+/// Reads the `u32` length prefix at the start of `bytes`.
+pub(crate) fn skip_length(bytes: &[u8]) -> Result<usize, DecodeError> {
+    let mut reader = bytes;
+    length_to_usize(reader.try_read_u32_le()?)
 }
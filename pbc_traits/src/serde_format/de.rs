@@ -0,0 +1,317 @@
+use std::io::Read;
+
+use serde::de::{DeserializeSeed, IntoDeserializer, Visitor};
+
+use super::{Error, Format, FormatMarker};
+use crate::decode_error::length_to_usize;
+use crate::DecodeError;
+
+/// [`serde::Deserializer`] reading the format `F` from a [`Read`].
+#[derive(Debug)]
+pub struct Deserializer<R, F> {
+    reader: R,
+    format: FormatMarker<F>,
+}
+
+impl<R: Read, F: Format> Deserializer<R, F> {
+    /// Creates a deserializer reading from the given reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            format: Default::default(),
+        }
+    }
+
+    /// Unwraps the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn read_array<const LEN: usize>(&mut self) -> Result<[u8; LEN], Error> {
+        let mut buf = [0; LEN];
+        self.reader
+            .read_exact(&mut buf)
+            .map_err(DecodeError::from)?;
+        Ok(buf)
+    }
+
+    fn read_length(&mut self) -> Result<usize, Error> {
+        let bytes = self.read_array()?;
+        let length = if F::BIG_ENDIAN {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        };
+        Ok(length_to_usize(length)?)
+    }
+
+    fn read_byte_buf(&mut self) -> Result<Vec<u8>, Error> {
+        let length = self.read_length()?;
+        let mut buf = Vec::with_capacity(usize::min(length, 1024));
+        let read = (&mut self.reader)
+            .take(length as u64)
+            .read_to_end(&mut buf)
+            .map_err(DecodeError::from)?;
+        if read < length {
+            return Err(DecodeError::UnexpectedEof.into());
+        }
+        Ok(buf)
+    }
+
+    fn read_string(&mut self) -> Result<String, Error> {
+        let bytes = self.read_byte_buf()?;
+        String::from_utf8(bytes).map_err(|_| DecodeError::InvalidUtf8.into())
+    }
+}
+
+/// Implements integer deserialization in the byte order of the format.
+macro_rules! deserialize_int {
+    ($($method:ident: $type:ty => $visit:ident),*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                let bytes = self.read_array()?;
+                if F::BIG_ENDIAN {
+                    visitor.$visit(<$type>::from_be_bytes(bytes))
+                } else {
+                    visitor.$visit(<$type>::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl<'de, R: Read, F: Format> serde::Deserializer<'de> for &mut Deserializer<R, F> {
+    type Error = Error;
+
+    deserialize_int!(
+        deserialize_i8: i8 => visit_i8,
+        deserialize_i16: i16 => visit_i16,
+        deserialize_i32: i32 => visit_i32,
+        deserialize_i64: i64 => visit_i64,
+        deserialize_i128: i128 => visit_i128,
+        deserialize_u8: u8 => visit_u8,
+        deserialize_u16: u16 => visit_u16,
+        deserialize_u32: u32 => visit_u32,
+        deserialize_u64: u64 => visit_u64,
+        deserialize_u128: u128 => visit_u128
+    );
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(Error::Unsupported("self-describing deserialization"))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let [byte] = self.read_array()?;
+        visitor.visit_bool(byte != 0)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(Error::Unsupported("f32"))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(Error::Unsupported("f64"))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let bytes = self.read_array()?;
+        let value = if F::BIG_ENDIAN {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        };
+        let value = char::from_u32(value).ok_or(DecodeError::InvalidChar { value })?;
+        visitor.visit_char(value)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_string(self.read_string()?)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_byte_buf(self.read_byte_buf()?)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let [marker] = self.read_array()?;
+        match marker {
+            0 => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let remaining = self.read_length()?;
+        visitor.visit_seq(Counted {
+            deserializer: self,
+            remaining,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(Counted {
+            deserializer: self,
+            remaining: len,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let remaining = self.read_length()?;
+        visitor.visit_map(Counted {
+            deserializer: self,
+            remaining,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(Error::Unsupported("identifiers"))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(Error::Unsupported("self-describing deserialization"))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Access to a known number of sequence elements or map entries.
+struct Counted<'a, R, F> {
+    deserializer: &'a mut Deserializer<R, F>,
+    remaining: usize,
+}
+
+impl<'de, R: Read, F: Format> serde::de::SeqAccess<'de> for Counted<'_, R, F> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(usize::min(self.remaining, 128))
+    }
+}
+
+impl<'de, R: Read, F: Format> serde::de::MapAccess<'de> for Counted<'_, R, F> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        seed.deserialize(&mut *self.deserializer)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(usize::min(self.remaining, 128))
+    }
+}
+
+impl<'de, R: Read, F: Format> serde::de::EnumAccess<'de> for &mut Deserializer<R, F> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        let [discriminant] = self.read_array()?;
+        let variant = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(u32::from(
+            discriminant,
+        )))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, R: Read, F: Format> serde::de::VariantAccess<'de> for &mut Deserializer<R, F> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        serde::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        serde::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
@@ -0,0 +1,157 @@
+//! [Serde](https://serde.rs) support for the RPC and State serialization formats.
+//!
+//! Allows off-chain code to produce and consume the exact bytes of [`WriteRPC`](crate::WriteRPC),
+//! [`ReadRPC`](crate::ReadRPC) and [`ReadWriteState`](crate::ReadWriteState) using its own serde
+//! models, without depending on the contract derives.
+//!
+//! The serde data model is mapped to the formats as follows:
+//!
+//! - Integers and `bool` are written as in the formats; big endian for [`Rpc`] and little endian
+//!   for [`State`]. A `char` is written as its Unicode scalar value in a `u32`.
+//! - Strings, byte buffers, sequences and maps are prefixed by their length as a `u32`. Maps are
+//!   written in iteration order; use `BTreeMap` to get the sorted order of the formats.
+//! - Options are prefixed by a marker byte, `0` for `None` and `1` for `Some`.
+//! - Structs, tuples and fixed size arrays are written as their fields in order, with no prefix.
+//! - Enum variants are prefixed by the index of the variant as a `u8`. This matches PBC enums
+//!   whose discriminants are `0, 1, 2, ...` in declaration order.
+//! - Floating point numbers have no representation, and fail with [`Error::Unsupported`].
+//!
+//! The formats are not self-describing, so `deserialize_any` is not supported.
+
+use std::fmt;
+use std::io::{Read, Write};
+use std::marker::PhantomData;
+
+use crate::DecodeError;
+
+pub use de::Deserializer;
+pub use ser::Serializer;
+
+mod de;
+mod ser;
+
+/// Byte order and naming of a serialization format.
+///
+/// Implemented by [`Rpc`] and [`State`].
+pub trait Format {
+    /// Whether integers are written as big endian.
+    const BIG_ENDIAN: bool;
+}
+
+/// The RPC serialization format, as produced by [`WriteRPC`](crate::WriteRPC).
+#[derive(Debug)]
+pub enum Rpc {}
+
+/// The State serialization format, as produced by [`ReadWriteState`](crate::ReadWriteState).
+#[derive(Debug)]
+pub enum State {}
+
+impl Format for Rpc {
+    const BIG_ENDIAN: bool = true;
+}
+
+impl Format for State {
+    const BIG_ENDIAN: bool = false;
+}
+
+/// Error produced by the serde [`Serializer`] and [`Deserializer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The underlying writer failed.
+    Write(std::io::ErrorKind),
+    /// The input could not be deserialized.
+    Decode(DecodeError),
+    /// The value uses a part of the serde data model that cannot be represented in the format.
+    Unsupported(&'static str),
+    /// Input remained after the value had been deserialized.
+    TrailingBytes,
+    /// Error raised by a [`serde::Serialize`] or [`serde::Deserialize`] implementation.
+    Custom(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Write(kind) => write!(f, "Could not write output: {kind}"),
+            Self::Decode(error) => error.fmt(f),
+            Self::Unsupported(what) => write!(f, "Serialization of {what} is not supported"),
+            Self::TrailingBytes => write!(f, "Input remained after decoding"),
+            Self::Custom(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<DecodeError> for Error {
+    fn from(error: DecodeError) -> Self {
+        Self::Decode(error)
+    }
+}
+
+impl serde::ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+/// Serializes the value to the writer, in the format `F`.
+pub fn to_writer<F: Format, W: Write, T: serde::Serialize + ?Sized>(
+    value: &T,
+    writer: &mut W,
+) -> Result<(), Error> {
+    value.serialize(&mut Serializer::<_, F>::new(writer))
+}
+
+/// Deserializes a value from the reader, in the format `F`.
+///
+/// The reader may contain more input after the value.
+pub fn from_reader<F: Format, R: Read, T: serde::de::DeserializeOwned>(
+    reader: &mut R,
+) -> Result<T, Error> {
+    T::deserialize(&mut Deserializer::<_, F>::new(reader))
+}
+
+/// Serializes the value to the bytes that [`WriteRPC`](crate::WriteRPC) would produce.
+pub fn to_rpc_bytes<T: serde::Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    to_writer::<Rpc, _, _>(value, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Serializes the value to the bytes that [`ReadWriteState`](crate::ReadWriteState) would
+/// produce.
+pub fn to_state_bytes<T: serde::Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    to_writer::<State, _, _>(value, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Deserializes a value from RPC bytes, failing if any input remains afterwards.
+pub fn from_rpc_bytes<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    from_bytes::<Rpc, T>(bytes)
+}
+
+/// Deserializes a value from State bytes, failing if any input remains afterwards.
+pub fn from_state_bytes<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    from_bytes::<State, T>(bytes)
+}
+
+fn from_bytes<F: Format, T: serde::de::DeserializeOwned>(mut bytes: &[u8]) -> Result<T, Error> {
+    let value = from_reader::<F, _, T>(&mut bytes)?;
+    if bytes.is_empty() {
+        Ok(value)
+    } else {
+        Err(Error::TrailingBytes)
+    }
+}
+
+/// Ties a serializer or deserializer to its format.
+type FormatMarker<F> = PhantomData<fn() -> F>;
@@ -0,0 +1,136 @@
+//! Borrowed views of State serialized values, decoded lazily.
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::io::Strict;
+use crate::{DecodeError, ReadWriteState};
+
+/// View of a value of type `T` in State serialization, borrowed from a byte buffer.
+///
+/// Nothing is decoded when the view is created; [`StateView::read`] decodes the value, and for
+/// types implementing [`StateViewFields`], [`StateView::fields`] gives views of the individual
+/// fields. Reading a single field of a large state thus only decodes that field, skipping the
+/// fields before it using [`ReadWriteState::state_skip`]. Values of types that are serializable
+/// by copy can also be borrowed in place using [`StateView::get`].
+///
+/// The buffer starts with the value, and may contain further bytes after it.
+///
+/// ```
+/// # use pbc_traits::{ReadWriteState, StateView};
+/// let mut bytes = Vec::new();
+/// (7u32, String::from("seven")).state_write_to(&mut bytes).unwrap();
+///
+/// let view = StateView::<(u32, String)>::new(&bytes);
+/// assert_eq!(view.read(), (7, String::from("seven")));
+/// ```
+pub struct StateView<'a, T> {
+    bytes: &'a [u8],
+    value_type: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: ReadWriteState> StateView<'a, T> {
+    /// Creates a view of the value at the start of `bytes`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            value_type: PhantomData,
+        }
+    }
+
+    /// The buffer of the view, starting with the value.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Decodes the value.
+    ///
+    /// Panics if the buffer does not start with a valid value.
+    pub fn read(&self) -> T {
+        let mut reader = self.bytes;
+        T::state_read_from(&mut reader)
+    }
+
+    /// Decodes the value, returning a [`DecodeError`] if the buffer does not start with a valid
+    /// value.
+    pub fn try_read(&self) -> Result<T, DecodeError> {
+        let mut reader = self.bytes;
+        T::try_state_read_from(&mut reader)
+    }
+
+    /// Borrows the value in place, without decoding or copying it.
+    ///
+    /// Only values of types that are [serializable by copy](ReadWriteState::SERIALIZABLE_BY_COPY)
+    /// can be borrowed, as their serialization is their memory representation. Returns `None`
+    /// if `T` is not serializable by copy, or if the value is not aligned for `T` in the buffer;
+    /// [`StateView::read`] decodes the value in either case. The bytes are checked to be a
+    /// canonical encoding, as by a [strict reader](crate::io::Strict), before being borrowed.
+    ///
+    /// Panics if the buffer does not start with a canonically encoded value.
+    ///
+    /// ```
+    /// # use pbc_traits::StateView;
+    /// let bytes = [7u8; 40];
+    /// let view = StateView::<[u8; 32]>::new(&bytes);
+    /// assert_eq!(view.get(), Some(&[7u8; 32]));
+    /// assert_eq!(StateView::<String>::new(&bytes).get(), None);
+    /// assert!(StateView::<bool>::new(&bytes).try_get().is_err());
+    /// ```
+    pub fn get(&self) -> Option<&'a T> {
+        self.try_get().unwrap()
+    }
+
+    /// Borrows the value in place like [`StateView::get`], returning a [`DecodeError`] if the
+    /// buffer does not start with a canonically encoded value.
+    pub fn try_get(&self) -> Result<Option<&'a T>, DecodeError> {
+        if !T::SERIALIZABLE_BY_COPY {
+            return Ok(None);
+        }
+        if self.bytes.len() < core::mem::size_of::<T>() {
+            return Err(DecodeError::UnexpectedEof);
+        }
+        let pointer = self.bytes.as_ptr();
+        if pointer.align_offset(core::mem::align_of::<T>()) != 0 {
+            return Ok(None);
+        }
+        T::try_state_read_from(&mut Strict::new(self.bytes))?;
+        // SAFETY: The buffer holds enough aligned bytes for a `T`, and those bytes are the
+        // memory representation of a valid `T`, as the type is serializable by copy and the
+        // bytes are its canonical encoding.
+        Ok(Some(unsafe { &*pointer.cast::<T>() }))
+    }
+}
+
+impl<'a, T: StateViewFields> StateView<'a, T> {
+    /// Views of the fields of the value.
+    pub fn fields(&self) -> T::Fields<'a> {
+        T::fields(self.bytes)
+    }
+}
+
+impl<T> Clone for StateView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for StateView<'_, T> {}
+
+impl<T> fmt::Debug for StateView<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateView")
+            .field("bytes", &self.bytes)
+            .finish()
+    }
+}
+
+/// Types whose fields can be viewed individually through [`StateView::fields`].
+///
+/// Derived using `#[derive(StateView)]` for structs also deriving [`ReadWriteState`].
+pub trait StateViewFields: ReadWriteState {
+    /// Views of the fields, borrowing the buffer of the value.
+    type Fields<'a>;
+
+    /// Creates views of the fields of the value at the start of `bytes`.
+    fn fields(bytes: &[u8]) -> Self::Fields<'_>;
+}
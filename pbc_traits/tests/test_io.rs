@@ -0,0 +1,79 @@
+use pbc_traits::io::{self, ErrorKind};
+use pbc_traits::{DecodeError, PbcRead, PbcWrite, ReadRPC, ReadWriteState, WriteRPC};
+
+/// Reader implemented only through [`PbcRead`], without [`std::io::Read`].
+struct ChunkReader {
+    chunks: Vec<Vec<u8>>,
+}
+
+impl PbcRead for ChunkReader {
+    fn read_bytes(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            let Some(chunk) = self.chunks.first_mut() else {
+                return Err(ErrorKind::UnexpectedEof.into());
+            };
+            let count = usize::min(chunk.len(), buf.len() - filled);
+            buf[filled..filled + count].copy_from_slice(&chunk[..count]);
+            chunk.drain(..count);
+            if chunk.is_empty() {
+                self.chunks.remove(0);
+            }
+            filled += count;
+        }
+        Ok(())
+    }
+}
+
+/// Writer implemented only through [`PbcWrite`], without [`std::io::Write`].
+#[derive(Default)]
+struct ChunkWriter {
+    chunks: Vec<Vec<u8>>,
+}
+
+impl PbcWrite for ChunkWriter {
+    fn write_bytes(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.chunks.push(buf.to_vec());
+        Ok(())
+    }
+}
+
+#[test]
+pub fn custom_readers_and_writers() {
+    let value = (0x0102u16, "ab".to_string(), vec![Some(3u8), None]);
+
+    let mut writer = ChunkWriter::default();
+    value.rpc_write_to(&mut writer).unwrap();
+    assert_eq!(
+        writer.chunks.concat(),
+        vec![1, 2, 0, 0, 0, 2, b'a', b'b', 0, 0, 0, 2, 1, 3, 0]
+    );
+
+    let mut reader = ChunkReader {
+        chunks: writer.chunks,
+    };
+    let read: (u16, String, Vec<Option<u8>>) = ReadRPC::rpc_read_from(&mut reader);
+    assert_eq!(read, value);
+    assert!(reader.chunks.is_empty());
+}
+
+#[test]
+pub fn custom_reader_reports_end_of_input() {
+    let mut reader = ChunkReader {
+        chunks: vec![vec![1, 0], vec![0]],
+    };
+    assert_eq!(
+        u32::try_state_read_from(&mut reader),
+        Err(DecodeError::UnexpectedEof)
+    );
+}
+
+#[test]
+pub fn std_readers_and_writers_are_adapted() {
+    let mut bytes: Vec<u8> = Vec::new();
+    0x01020304u32.state_write_to(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![4, 3, 2, 1]);
+
+    let mut cursor = std::io::Cursor::new(bytes);
+    assert_eq!(u32::state_read_from(&mut cursor), 0x01020304);
+}
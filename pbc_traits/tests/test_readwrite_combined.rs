@@ -1,7 +1,11 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Debug;
 use std::io::Cursor;
+use std::num::{NonZeroI32, NonZeroI64, NonZeroU128, NonZeroU16, NonZeroU8};
+use std::rc::Rc;
 
-use pbc_traits::{ReadRPC, ReadWriteState, WriteRPC};
+use pbc_traits::{ByteCounter, DecodeError, ReadRPC, ReadWriteState, WriteRPC};
 
 fn assert_read_write_raw<T: Eq + Debug>(
     expected: T,
@@ -17,15 +21,32 @@ fn assert_read_write_raw<T: Eq + Debug>(
 }
 
 fn assert_rpc<T: ReadRPC + WriteRPC + Eq + Debug + Clone>(expected: T) {
-    assert_read_write_raw(expected, WriteRPC::rpc_write_to, ReadRPC::rpc_read_from);
+    assert_eq!(expected.rpc_serialized_size(), rpc_bytes(&expected).len());
+    assert_read_write_raw(
+        expected.clone(),
+        WriteRPC::rpc_write_to,
+        ReadRPC::rpc_read_from,
+    );
+    assert_read_write_raw(expected, WriteRPC::rpc_write_to, |reader| {
+        T::try_rpc_read_from(reader).unwrap()
+    });
 }
 
 fn assert_state<T: ReadWriteState + Eq + Debug + Clone>(expected: T) {
+    let bytes = state_bytes(&expected);
+    assert_eq!(expected.state_serialized_size(), bytes.len());
+    assert_eq!(T::state_skip(&bytes), Ok(bytes.len()));
+    if let Some((_, truncated)) = bytes.split_last() {
+        assert_eq!(T::state_skip(truncated), Err(DecodeError::UnexpectedEof));
+    }
     assert_read_write_raw(
-        expected,
+        expected.clone(),
         ReadWriteState::state_write_to,
         ReadWriteState::state_read_from,
     );
+    assert_read_write_raw(expected, ReadWriteState::state_write_to, |reader| {
+        T::try_state_read_from(reader).unwrap()
+    });
 }
 
 fn assert_serializes<T: ReadRPC + WriteRPC + ReadWriteState + Eq + Debug + Clone>(expected: T) {
@@ -87,3 +108,165 @@ pub fn strings() {
     assert_serializes("This is a string".to_string());
     assert_serializes("Tæstång unícöde".to_string());
 }
+
+#[test]
+pub fn maps_and_sets() {
+    let btree_map = BTreeMap::from([(3u32, "c".to_string()), (1u32, "a".to_string())]);
+    assert_serializes(btree_map);
+    assert_serializes(BTreeMap::<u8, Vec<u64>>::new());
+    assert_serializes(BTreeSet::from([42u64, 1u64, 7u64]));
+    assert_serializes(BTreeSet::from(["b".to_string(), "a".to_string()]));
+    assert_serializes(HashMap::from([(1i32, Some(2u8)), (-1i32, None)]));
+    assert_serializes(HashSet::from([5u16, 3u16, 9u16]));
+}
+
+fn rpc_bytes<T: WriteRPC>(value: &T) -> Vec<u8> {
+    let mut out = Vec::new();
+    value.rpc_write_to(&mut out).unwrap();
+    out
+}
+
+fn state_bytes<T: ReadWriteState>(value: &T) -> Vec<u8> {
+    let mut out = Vec::new();
+    value.state_write_to(&mut out).unwrap();
+    out
+}
+
+#[test]
+pub fn maps_serialize_sorted_by_key() {
+    let entries = [(300u16, 1u8), (2u16, 2u8), (17u16, 3u8)];
+    let btree_map = BTreeMap::from(entries);
+    let hash_map: HashMap<u16, u8> = HashMap::from(entries);
+
+    let expected_rpc = vec![0, 0, 0, 3, 0, 2, 2, 0, 17, 3, 1, 44, 1];
+    assert_eq!(rpc_bytes(&btree_map), expected_rpc);
+    assert_eq!(rpc_bytes(&hash_map), expected_rpc);
+
+    let expected_state = vec![3, 0, 0, 0, 2, 0, 2, 17, 0, 3, 44, 1, 1];
+    assert_eq!(state_bytes(&btree_map), expected_state);
+    assert_eq!(state_bytes(&hash_map), expected_state);
+}
+
+#[test]
+pub fn sets_serialize_like_sorted_vectors() {
+    let elements = [9u32, 1u32, 4u32];
+    let mut sorted = elements.to_vec();
+    sorted.sort();
+
+    let btree_set = BTreeSet::from(elements);
+    let hash_set = HashSet::from(elements);
+    assert_eq!(rpc_bytes(&btree_set), rpc_bytes(&sorted));
+    assert_eq!(rpc_bytes(&hash_set), rpc_bytes(&sorted));
+    assert_eq!(state_bytes(&btree_set), state_bytes(&sorted));
+    assert_eq!(state_bytes(&hash_set), state_bytes(&sorted));
+}
+
+#[test]
+pub fn tuples() {
+    assert_serializes((42u8,));
+    assert_serializes((1u32, "a".to_string()));
+    assert_serializes((
+        1u8, 2u16, 3u32, 4u64, 5u128, 6i8, 7i16, 8i32, 9i64, 10i128, true, None::<u8>,
+    ));
+    assert_serializes(vec![(1u64, Some(2u8)), (3u64, None)]);
+    assert_serializes(((1u8, 2u8), vec![(3u8, (4u8, 5u8))]));
+}
+
+#[test]
+pub fn tuples_serialize_as_concatenated_elements() {
+    let value = (0x0102u16, true, vec![0x03u8]);
+    assert_eq!(rpc_bytes(&value), vec![0x01, 0x02, 0x01, 0, 0, 0, 1, 0x03]);
+    assert_eq!(
+        state_bytes(&value),
+        vec![0x02, 0x01, 0x01, 1, 0, 0, 0, 0x03]
+    );
+}
+
+#[test]
+pub fn arrays() {
+    assert_serializes([0xABu8; 256]);
+    assert_serializes([1u16, 2u16, 3u16]);
+    assert_serializes([Some(1u32), None]);
+    assert_serializes(["a".to_string(), "bc".to_string()]);
+    assert_serializes([[1u8, 2u8], [3u8, 4u8]]);
+    assert_serializes([(1u8, vec![2u64]), (3u8, vec![])]);
+}
+
+#[test]
+pub fn arrays_serialize_as_concatenated_elements() {
+    let value = [0x0102u16, 0x0304u16];
+    assert_eq!(rpc_bytes(&value), vec![0x01, 0x02, 0x03, 0x04]);
+    assert_eq!(state_bytes(&value), vec![0x02, 0x01, 0x04, 0x03]);
+    assert_eq!(
+        <[u16; 2]>::try_rpc_read_from(&mut [0x01, 0x02, 0x03].as_slice()),
+        Err(DecodeError::UnexpectedEof)
+    );
+}
+
+#[test]
+pub fn pointers_chars_non_zero_integers_and_unit() {
+    assert_serializes(Box::new(42u64));
+    assert_serializes(Rc::new(vec!["rc".to_string()]));
+    assert_serializes(Some(Box::new((1u8, Some(Box::new(2u8))))));
+    assert_serializes(Cow::<str>::Owned("owned".to_string()));
+    assert_serializes(Cow::Borrowed("borrowed"));
+    assert_serializes('a');
+    assert_serializes('\u{10FFFF}');
+    assert_serializes(NonZeroU8::new(1).unwrap());
+    assert_serializes(NonZeroU128::new(u128::MAX).unwrap());
+    assert_serializes(NonZeroI64::new(-1).unwrap());
+    assert_serializes(());
+    assert_serializes(vec![(), ()]);
+}
+
+#[test]
+pub fn wrappers_serialize_as_their_values() {
+    assert_eq!(rpc_bytes(&Box::new(0x0102u16)), rpc_bytes(&0x0102u16));
+    assert_eq!(state_bytes(&Rc::new(0x0102u16)), state_bytes(&0x0102u16));
+    assert_eq!(
+        rpc_bytes(&Cow::Borrowed("ab")),
+        rpc_bytes(&"ab".to_string())
+    );
+    assert_eq!(rpc_bytes(&'a'), vec![0, 0, 0, 0x61]);
+    assert_eq!(state_bytes(&'a'), vec![0x61, 0, 0, 0]);
+    assert_eq!(
+        state_bytes(&NonZeroI32::new(-2).unwrap()),
+        state_bytes(&-2i32)
+    );
+    assert_eq!(rpc_bytes(&()), Vec::<u8>::new());
+    assert_eq!(state_bytes(&(1u8, (), 2u8)), vec![1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "Zero is not a valid NonZeroU16")]
+pub fn reading_zero_non_zero_integer_panics() {
+    NonZeroU16::state_read_from(&mut [0u8, 0].as_slice());
+}
+
+#[test]
+pub fn serialized_sizes() {
+    assert_serializes(0u8);
+    assert_serializes(-1i128);
+    assert_serializes(false);
+    assert_serializes(String::new());
+    assert_serializes("sizes".to_string());
+    assert_serializes([1u8, 2u8, 3u8]);
+    assert_state([vec![1u16], vec![2u16, 3u16]]);
+    assert_state(std::collections::VecDeque::from([1u64, 2u64]));
+
+    assert_eq!((1u64, true).rpc_serialized_size(), 9);
+    assert_eq!(vec![1u32, 2u32].state_serialized_size(), 4 + 8);
+    assert_eq!(Some("ab".to_string()).rpc_serialized_size(), 1 + 4 + 2);
+    assert_eq!(BTreeMap::from([(1u8, 2u64)]).state_serialized_size(), 4 + 9);
+}
+
+#[test]
+pub fn byte_counter_counts_written_bytes() {
+    let size = ByteCounter::count_written(|counter| vec![1u64, 2u64].rpc_write_to(counter));
+    assert_eq!(size, 4 + 16);
+
+    let mut counter = ByteCounter::new();
+    42u32.state_write_to(&mut counter).unwrap();
+    "ab".to_string().state_write_to(&mut counter).unwrap();
+    assert_eq!(counter.count(), 4 + 4 + 2);
+}
@@ -0,0 +1,199 @@
+#![cfg(feature = "serde")]
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Debug;
+
+use pbc_traits::serde_format::{
+    from_rpc_bytes, from_state_bytes, to_rpc_bytes, to_state_bytes, Error,
+};
+use pbc_traits::{DecodeError, ReadRPC, ReadWriteState, WriteRPC};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+fn rpc_bytes<T: WriteRPC>(value: &T) -> Vec<u8> {
+    let mut out = Vec::new();
+    value.rpc_write_to(&mut out).unwrap();
+    out
+}
+
+fn state_bytes<T: ReadWriteState>(value: &T) -> Vec<u8> {
+    let mut out = Vec::new();
+    value.state_write_to(&mut out).unwrap();
+    out
+}
+
+/// Asserts that serde produces and consumes the same bytes as the PBC implementations.
+fn assert_conforms<T>(value: T)
+where
+    T: ReadRPC + WriteRPC + ReadWriteState + Serialize + DeserializeOwned + Eq + Debug,
+{
+    let rpc = rpc_bytes(&value);
+    assert_eq!(to_rpc_bytes(&value).unwrap(), rpc);
+    assert_eq!(from_rpc_bytes::<T>(&rpc).unwrap(), value);
+    assert_eq!(T::rpc_read_from(&mut rpc.as_slice()), value);
+
+    let state = state_bytes(&value);
+    assert_eq!(to_state_bytes(&value).unwrap(), state);
+    assert_eq!(from_state_bytes::<T>(&state).unwrap(), value);
+    assert_eq!(T::state_read_from(&mut state.as_slice()), value);
+}
+
+#[test]
+pub fn integers_and_bools() {
+    assert_conforms(0x12u8);
+    assert_conforms(-2i8);
+    assert_conforms(0x1234u16);
+    assert_conforms(-0x1234i16);
+    assert_conforms(0x12345678u32);
+    assert_conforms(-0x12345678i32);
+    assert_conforms(0x0102030405060708u64);
+    assert_conforms(i64::MIN);
+    assert_conforms(u128::MAX - 7);
+    assert_conforms(i128::MIN + 7);
+    assert_conforms(true);
+    assert_conforms(false);
+}
+
+#[test]
+pub fn chars_pointers_and_non_zero_integers() {
+    assert_conforms('x');
+    assert_conforms('\u{1F980}');
+    assert_conforms(Box::new(7u64));
+    assert_conforms(std::num::NonZeroU32::new(9).unwrap());
+    assert_conforms(());
+    assert_eq!(
+        from_rpc_bytes::<char>(&[0, 0, 0xD8, 0]),
+        Err(Error::Decode(DecodeError::InvalidChar { value: 0xD800 }))
+    );
+}
+
+#[test]
+pub fn strings_vectors_and_options() {
+    assert_conforms(String::new());
+    assert_conforms("hello".to_string());
+    assert_conforms(vec![1u64, 2u64, 3u64]);
+    assert_conforms(Vec::<u8>::new());
+    assert_conforms(vec!["a".to_string(), "bc".to_string()]);
+    assert_conforms(None::<u32>);
+    assert_conforms(Some(vec![Some(1u16), None]));
+}
+
+#[test]
+pub fn maps_sets_tuples_and_arrays() {
+    assert_conforms(BTreeMap::from([
+        (3u32, "c".to_string()),
+        (1u32, "a".to_string()),
+    ]));
+    assert_conforms(BTreeSet::from([9i64, -1i64, 4i64]));
+    assert_conforms((1u8, 0x0203u16, "x".to_string()));
+    assert_conforms(vec![(1u64, Some(2u8)), (3u64, None)]);
+    assert_conforms([0x11u8; 20]);
+    assert_conforms([1u8, 2u8, 3u8, 4u8]);
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct Transfer {
+    sender: [u8; 21],
+    amount: u128,
+    memo: Option<String>,
+    tags: BTreeSet<u16>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct Wrapped(u32);
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+enum Phase {
+    Bidding(u64),
+    Closed { winner: u8, price: u32 },
+    Cancelled,
+}
+
+#[test]
+pub fn structs_serialize_as_their_fields() {
+    let transfer = Transfer {
+        sender: [0x22; 21],
+        amount: 0x0102,
+        memo: Some("rent".to_string()),
+        tags: BTreeSet::from([7, 3]),
+    };
+    let fields = (
+        transfer.sender,
+        transfer.amount,
+        transfer.memo.clone(),
+        transfer.tags.clone(),
+    );
+
+    assert_eq!(to_rpc_bytes(&transfer).unwrap(), rpc_bytes(&fields));
+    assert_eq!(to_state_bytes(&transfer).unwrap(), state_bytes(&fields));
+    assert_eq!(
+        from_rpc_bytes::<Transfer>(&rpc_bytes(&fields)).unwrap(),
+        transfer
+    );
+    assert_eq!(
+        from_state_bytes::<Transfer>(&state_bytes(&fields)).unwrap(),
+        transfer
+    );
+
+    assert_eq!(to_rpc_bytes(&Wrapped(5)).unwrap(), rpc_bytes(&5u32));
+    assert_eq!(to_state_bytes(&Wrapped(5)).unwrap(), state_bytes(&5u32));
+}
+
+#[test]
+pub fn enums_are_prefixed_by_variant_index() {
+    let bidding = Phase::Bidding(0x0102);
+    assert_eq!(
+        to_rpc_bytes(&bidding).unwrap(),
+        rpc_bytes(&(0u8, 0x0102u64))
+    );
+    assert_eq!(
+        to_state_bytes(&bidding).unwrap(),
+        state_bytes(&(0u8, 0x0102u64))
+    );
+
+    let closed = Phase::Closed {
+        winner: 4,
+        price: 0x0506,
+    };
+    assert_eq!(
+        to_rpc_bytes(&closed).unwrap(),
+        rpc_bytes(&(1u8, 4u8, 0x0506u32))
+    );
+    assert_eq!(to_state_bytes(&Phase::Cancelled).unwrap(), vec![2]);
+
+    for phase in [bidding, closed, Phase::Cancelled] {
+        let bytes = to_state_bytes(&phase).unwrap();
+        assert_eq!(from_state_bytes::<Phase>(&bytes).unwrap(), phase);
+    }
+    assert!(matches!(
+        from_rpc_bytes::<Phase>(&[3]),
+        Err(Error::Custom(_))
+    ));
+}
+
+#[test]
+pub fn malformed_input_is_rejected() {
+    assert_eq!(
+        from_rpc_bytes::<u32>(&[0, 0, 1]),
+        Err(Error::Decode(DecodeError::UnexpectedEof))
+    );
+    assert_eq!(
+        from_rpc_bytes::<String>(&[0, 0, 0, 4, b'a']),
+        Err(Error::Decode(DecodeError::UnexpectedEof))
+    );
+    assert_eq!(
+        from_state_bytes::<String>(&[2, 0, 0, 0, 0xC3, 0x28]),
+        Err(Error::Decode(DecodeError::InvalidUtf8))
+    );
+    assert_eq!(from_rpc_bytes::<u8>(&[1, 2]), Err(Error::TrailingBytes));
+}
+
+#[test]
+pub fn unsupported_types_fail() {
+    assert_eq!(to_rpc_bytes(&1.5f64), Err(Error::Unsupported("f64")));
+    assert_eq!(to_state_bytes(&1.5f32), Err(Error::Unsupported("f32")));
+    assert_eq!(
+        from_rpc_bytes::<f32>(&[0, 0, 0, 0]),
+        Err(Error::Unsupported("f32"))
+    );
+}
@@ -0,0 +1,271 @@
+use std::num::{NonZeroI8, NonZeroU32};
+
+use pbc_traits::io::Strict;
+use pbc_traits::{DecodeError, ReadRPC, ReadWriteState};
+
+fn try_rpc<T: ReadRPC>(bytes: &[u8]) -> Result<T, DecodeError> {
+    let mut reader = bytes;
+    T::try_rpc_read_from(&mut reader)
+}
+
+fn try_state<T: ReadWriteState>(bytes: &[u8]) -> Result<T, DecodeError> {
+    let mut reader = bytes;
+    T::try_state_read_from(&mut reader)
+}
+
+#[test]
+pub fn truncated_integers() {
+    assert_eq!(try_rpc::<u8>(&[]), Err(DecodeError::UnexpectedEof));
+    assert_eq!(try_rpc::<u32>(&[0, 0, 0]), Err(DecodeError::UnexpectedEof));
+    assert_eq!(try_state::<i64>(&[0; 7]), Err(DecodeError::UnexpectedEof));
+    assert_eq!(try_state::<u128>(&[0; 15]), Err(DecodeError::UnexpectedEof));
+
+    assert_eq!(try_rpc::<u32>(&[0, 0, 1, 2]), Ok(0x0102));
+    assert_eq!(try_state::<u32>(&[2, 1, 0, 0]), Ok(0x0102));
+}
+
+#[test]
+pub fn truncated_collections() {
+    assert_eq!(
+        try_rpc::<Vec<u16>>(&[0, 0, 0, 2, 0, 1]),
+        Err(DecodeError::UnexpectedEof)
+    );
+    assert_eq!(
+        try_state::<Vec<u16>>(&[2, 0, 0, 0, 1, 0]),
+        Err(DecodeError::UnexpectedEof)
+    );
+    assert_eq!(
+        try_state::<[u8; 4]>(&[1, 2, 3]),
+        Err(DecodeError::UnexpectedEof)
+    );
+    assert_eq!(
+        try_rpc::<[u8; 4]>(&[1, 2, 3]),
+        Err(DecodeError::UnexpectedEof)
+    );
+    assert_eq!(try_rpc::<Option<u8>>(&[1]), Err(DecodeError::UnexpectedEof));
+}
+
+#[test]
+pub fn huge_length_prefix_does_not_allocate() {
+    assert_eq!(
+        try_rpc::<Vec<u64>>(&[0xFF, 0xFF, 0xFF, 0xFF, 0]),
+        Err(DecodeError::UnexpectedEof)
+    );
+    assert_eq!(
+        try_state::<Vec<u64>>(&[0xFF, 0xFF, 0xFF, 0xFF, 0]),
+        Err(DecodeError::UnexpectedEof)
+    );
+    assert_eq!(
+        try_state::<String>(&[0xFF, 0xFF, 0xFF, 0xFF, 0]),
+        Err(DecodeError::UnexpectedEof)
+    );
+}
+
+fn strict_rpc<T: ReadRPC>(bytes: &[u8]) -> Result<T, DecodeError> {
+    T::try_rpc_read_from(&mut Strict::new(bytes))
+}
+
+fn strict_state<T: ReadWriteState>(bytes: &[u8]) -> Result<T, DecodeError> {
+    T::try_state_read_from(&mut Strict::new(bytes))
+}
+
+#[test]
+pub fn lenient_readers_accept_non_canonical_encodings() {
+    assert_eq!(try_rpc::<bool>(&[2]), Ok(true));
+    assert_eq!(try_state::<bool>(&[0xFF]), Ok(true));
+    assert_eq!(try_rpc::<Option<u8>>(&[7, 1]), Ok(Some(1)));
+    assert_eq!(try_state::<Option<u8>>(&[7, 1]), Ok(Some(1)));
+    assert_eq!(
+        try_rpc::<Vec<u8>>(&[0x80, 0, 0, 0]),
+        Err(DecodeError::UnexpectedEof)
+    );
+}
+
+#[test]
+pub fn strict_readers_reject_non_canonical_encodings() {
+    assert_eq!(
+        strict_rpc::<(u16, bool)>(&[0, 1, 2]),
+        Err(DecodeError::InvalidBool { offset: 2, byte: 2 })
+    );
+    assert_eq!(
+        strict_state::<Vec<bool>>(&[2, 0, 0, 0, 1, 0xFF]),
+        Err(DecodeError::InvalidBool {
+            offset: 5,
+            byte: 0xFF
+        })
+    );
+    assert_eq!(
+        strict_rpc::<Option<u8>>(&[7, 1]),
+        Err(DecodeError::InvalidOptionMarker {
+            offset: 0,
+            marker: 7
+        })
+    );
+    assert_eq!(
+        strict_state::<(u8, Option<u8>)>(&[0, 2, 1]),
+        Err(DecodeError::InvalidOptionMarker {
+            offset: 1,
+            marker: 2
+        })
+    );
+    assert_eq!(
+        strict_rpc::<(u8, Vec<u8>)>(&[0, 0xFF, 0xFF, 0xFF, 0xFE]),
+        Err(DecodeError::NegativeLength {
+            offset: 1,
+            length: -2
+        })
+    );
+    assert_eq!(
+        strict_rpc::<String>(&[0x80, 0, 0, 0]),
+        Err(DecodeError::NegativeLength {
+            offset: 0,
+            length: i32::MIN
+        })
+    );
+}
+
+#[test]
+pub fn strict_readers_accept_canonical_encodings() {
+    assert_eq!(strict_rpc::<bool>(&[1]), Ok(true));
+    assert_eq!(strict_state::<bool>(&[0]), Ok(false));
+    assert_eq!(
+        strict_rpc::<Vec<Option<bool>>>(&[0, 0, 0, 2, 0, 1, 1]),
+        Ok(vec![None, Some(true)])
+    );
+    assert_eq!(
+        strict_state::<Option<String>>(&[1, 1, 0, 0, 0, b'a']),
+        Ok(Some("a".to_string()))
+    );
+
+    let mut reader = Strict::new([0u8, 0, 0, 1, 9].as_slice());
+    assert_eq!(Vec::<u8>::try_rpc_read_from(&mut reader), Ok(vec![9]));
+    assert_eq!(reader.position(), 5);
+}
+
+#[test]
+pub fn invalid_utf8() {
+    assert_eq!(
+        try_rpc::<String>(&[0, 0, 0, 2, 0xC3, 0x28]),
+        Err(DecodeError::InvalidUtf8)
+    );
+    assert_eq!(
+        try_state::<String>(&[2, 0, 0, 0, 0xC3, 0x28]),
+        Err(DecodeError::InvalidUtf8)
+    );
+    assert_eq!(
+        try_rpc::<String>(&[0, 0, 0, 2, b'h', b'i']),
+        Ok("hi".to_string())
+    );
+}
+
+#[test]
+pub fn invalid_chars_and_zero_non_zero_integers() {
+    assert_eq!(
+        try_rpc::<char>(&[0, 0x11, 0, 0]),
+        Err(DecodeError::InvalidChar { value: 0x110000 })
+    );
+    assert_eq!(
+        try_state::<char>(&[0, 0xD8, 0, 0]),
+        Err(DecodeError::InvalidChar { value: 0xD800 })
+    );
+    assert_eq!(try_state::<char>(&[0x61, 0, 0, 0]), Ok('a'));
+    assert_eq!(
+        try_rpc::<NonZeroU32>(&[0, 0, 0, 0]),
+        Err(DecodeError::InvalidNonZero {
+            type_name: "NonZeroU32"
+        })
+    );
+    assert_eq!(
+        try_state::<Option<NonZeroI8>>(&[1, 0]),
+        Err(DecodeError::InvalidNonZero {
+            type_name: "NonZeroI8"
+        })
+    );
+    assert_eq!(
+        try_rpc::<NonZeroU32>(&[0, 0, 0, 5]).map(NonZeroU32::get),
+        Ok(5)
+    );
+}
+
+#[test]
+pub fn error_messages() {
+    assert_eq!(
+        DecodeError::InvalidDiscriminant {
+            type_name: "MyEnum",
+            discriminant: 7
+        }
+        .to_string(),
+        "No known MyEnum value with discriminant 7"
+    );
+    assert_eq!(
+        DecodeError::UnexpectedEof.to_string(),
+        "Encountered end of stream while decoding"
+    );
+    assert_eq!(
+        DecodeError::InvalidBool { offset: 3, byte: 2 }.to_string(),
+        "Byte 2 at offset 3 is not a valid bool"
+    );
+    assert_eq!(
+        DecodeError::NegativeLength {
+            offset: 0,
+            length: -1
+        }
+        .to_string(),
+        "Length -1 at offset 0 is negative"
+    );
+    assert_eq!(
+        DecodeError::InvalidChar { value: 0xD800 }.to_string(),
+        "55296 is not a valid char"
+    );
+    assert_eq!(
+        DecodeError::InvalidNonZero {
+            type_name: "NonZeroU64"
+        }
+        .to_string(),
+        "Zero is not a valid NonZeroU64"
+    );
+}
+
+#[test]
+pub fn duplicate_keys() {
+    use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+    assert_eq!(
+        try_rpc::<BTreeMap<u8, u8>>(&[0, 0, 0, 3, 1, 10, 2, 20, 1, 30]),
+        Err(DecodeError::DuplicateKey)
+    );
+    assert_eq!(
+        try_state::<BTreeSet<u8>>(&[2, 0, 0, 0, 5, 5]),
+        Err(DecodeError::DuplicateKey)
+    );
+    assert_eq!(
+        try_state::<HashMap<u8, u8>>(&[2, 0, 0, 0, 1, 10, 1, 20]),
+        Err(DecodeError::DuplicateKey)
+    );
+    assert_eq!(
+        strict_rpc::<HashSet<u8>>(&[0, 0, 0, 2, 5, 5]),
+        Err(DecodeError::DuplicateKey)
+    );
+}
+
+#[test]
+pub fn unsorted_keys() {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    assert_eq!(
+        try_rpc::<BTreeMap<u8, u8>>(&[0, 0, 0, 2, 2, 20, 1, 10]),
+        Ok(BTreeMap::from([(1, 10), (2, 20)]))
+    );
+    assert_eq!(
+        strict_rpc::<BTreeMap<u8, u8>>(&[0, 0, 0, 2, 2, 20, 1, 10]),
+        Err(DecodeError::UnsortedKey { offset: 6 })
+    );
+    assert_eq!(
+        strict_state::<BTreeSet<u16>>(&[3, 0, 0, 0, 1, 0, 3, 0, 2, 0]),
+        Err(DecodeError::UnsortedKey { offset: 8 })
+    );
+    assert_eq!(
+        strict_state::<BTreeSet<u16>>(&[2, 0, 0, 0, 1, 0, 2, 0]),
+        Ok(BTreeSet::from([1, 2]))
+    );
+}
@@ -266,7 +266,7 @@ pub fn load_sbi<T: Secret + 'static>(variable_id: SecretVarId) -> T {
 /// ### Parameters:
 ///
 /// * `T`: Type of metadata. Must not be secret. This cannot be enforced in Rust 1.64, but the
-/// ZK-compiler can enforce it.
+///   ZK-compiler can enforce it.
 /// * `variable_id`: [`SecretVarId`], the id to retrieve metadata from.
 ///
 /// ### Returns:
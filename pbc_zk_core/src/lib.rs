@@ -3,7 +3,7 @@
 mod sbi;
 mod secret_binary;
 
-use std::io::{Read, Write};
+use pbc_traits::{PbcRead, PbcWrite};
 
 #[cfg(feature = "abi")]
 use pbc_traits::CreateTypeSpec;
@@ -12,6 +12,7 @@ pub use sbi::FromToBits;
 pub use sbi::Sbi;
 #[cfg(not(doc))]
 use sbi::Sbi;
+#[allow(unused_imports)]
 pub use secret_binary::*;
 
 /// A secret-shared [`bool`] value.
@@ -32,9 +33,9 @@ pub type Sbi128 = Sbi<i128>;
 /// [State serialization format](https://partisiablockchain.gitlab.io/documentation/smart-contracts/smart-contract-binary-formats.html#state-binary-format).
 pub trait SecretBinary {
     /// Deserialization method for a secret.
-    fn secret_read_from<T: Read>(reader: &mut T) -> Self;
+    fn secret_read_from<T: PbcRead>(reader: &mut T) -> Self;
     /// Serialization method for a secret.
-    fn secret_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()>;
+    fn secret_write_to<T: PbcWrite>(&self, writer: &mut T) -> pbc_traits::io::Result<()>;
 }
 pub use crate::SecretBinary as Secret;
 
@@ -51,11 +52,11 @@ macro_rules! impl_secretbinary_delegate_to_read_write_state {
             #[doc = stringify!($type)]
             #[doc = "`]."]
             impl SecretBinary for $type {
-                fn secret_read_from<ReadT: Read>(reader: &mut ReadT) -> Self {
+                fn secret_read_from<ReadT: PbcRead>(reader: &mut ReadT) -> Self {
                     <Self as pbc_traits::ReadWriteState>::state_read_from(reader)
                 }
 
-                fn secret_write_to<WriteT: Write>(&self, writer: &mut WriteT) -> std::io::Result<()> {
+                fn secret_write_to<WriteT: PbcWrite>(&self, writer: &mut WriteT) -> pbc_traits::io::Result<()> {
                     <Self as pbc_traits::ReadWriteState>::state_write_to(self, writer)
                 }
             }
@@ -121,7 +122,7 @@ impl_createtypespec_for_type!(
 
 /// Implementation of [`SecretBinary`] for arrays of arbitrary sizes and types.
 impl<const LEN: usize, ElementT: SecretBinary + Sized> SecretBinary for [ElementT; LEN] {
-    fn secret_read_from<T: Read>(reader: &mut T) -> Self {
+    fn secret_read_from<T: PbcRead>(reader: &mut T) -> Self {
         let mut data: [std::mem::MaybeUninit<ElementT>; LEN] =
             unsafe { std::mem::MaybeUninit::uninit().assume_init() };
         for element_addr in &mut data[..] {
@@ -130,7 +131,7 @@ impl<const LEN: usize, ElementT: SecretBinary + Sized> SecretBinary for [Element
         data.map(|x| unsafe { x.assume_init() })
     }
 
-    fn secret_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn secret_write_to<T: PbcWrite>(&self, writer: &mut T) -> pbc_traits::io::Result<()> {
         for elem in self {
             <ElementT as SecretBinary>::secret_write_to(elem, writer)?;
         }
@@ -1,9 +1,9 @@
 //! Contains implementation of [`Sbi`].
 
 use crate::{Sbi1, SecretBinary, SecretBinaryFixedSize};
+use pbc_traits::{PbcRead, PbcWrite};
 use pbc_traits::{ReadInt, WriteInt};
 use std::cmp::Ordering;
-use std::io::{Read, Write};
 use std::num::Wrapping;
 use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Shl, Shr, Sub};
 
@@ -216,11 +216,11 @@ impl SecretBinaryFixedSize for Sbi1 {
 
 #[doc = "Implementation of [`SecretBinary`] trait for [`Sbi1`]. Uses a full byte to present a single bit."]
 impl SecretBinary for Sbi1 {
-    fn secret_read_from<T: Read>(reader: &mut T) -> Self {
+    fn secret_read_from<T: PbcRead>(reader: &mut T) -> Self {
         reader.read_u8() != 0
     }
 
-    fn secret_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+    fn secret_write_to<T: PbcWrite>(&self, writer: &mut T) -> pbc_traits::io::Result<()> {
         writer.write_u8(u8::from(*self))
     }
 }
@@ -239,10 +239,10 @@ macro_rules! read_write_secret {
             #[doc = stringify!($inner_type)]
             #[doc = ">`]. Encoded as a little-endian integer."]
             impl SecretBinary for Sbi<$inner_type> {
-                fn secret_read_from<T: Read>(reader: &mut T) -> Self {
+                fn secret_read_from<T: PbcRead>(reader: &mut T) -> Self {
                     Self::from(reader.$read_method())
                 }
-                fn secret_write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+                fn secret_write_to<T: PbcWrite>(&self, writer: &mut T) -> pbc_traits::io::Result<()> {
                     writer.$write_method(self.secret)
                 }
             }
@@ -1,5 +1,4 @@
 use proc_macro::TokenStream;
-use quote::ToTokens;
 use syn::parse_quote;
 
 pub(crate) fn implement_secret(input: TokenStream) -> TokenStream {
@@ -9,18 +8,36 @@ pub(crate) fn implement_secret(input: TokenStream) -> TokenStream {
     let type_name = &ast.ident;
     let supported_kind = derive_commons::get_kind_data(&ast.data);
 
-    let (field_names, field_types): (Vec<_>, Vec<_>) = match supported_kind {
-        derive_commons::SupportedKind::StructWithNamedFields { fields, .. } => {
-            let field_names: Vec<_> = fields
+    let (field_members, field_types): (Vec<_>, Vec<_>) = match supported_kind {
+        derive_commons::SupportedKind::StructWithNamedFields { fields }
+        | derive_commons::SupportedKind::StructWithUnnamedFields { fields } => {
+            let field_members: Vec<_> = fields
                 .iter()
-                .map(|x| derive_commons::field_to_name(x).to_token_stream())
+                .enumerate()
+                .map(|(index, field)| derive_commons::field_to_member(index, field))
                 .collect();
             let field_types: Vec<_> = fields.iter().map(derive_commons::field_to_type).collect();
-            (field_names, field_types)
+            (field_members, field_types)
         }
         derive_commons::SupportedKind::DiscriminatedCstyleEnum { .. } => (vec![], vec![]),
-        derive_commons::SupportedKind::ItemStructEnum { .. } => unimplemented!("ItemStructEnum"),
+        derive_commons::SupportedKind::ItemStructEnum { .. } => {
+            return syn::Error::new_spanned(
+                type_name,
+                "SecretBinary cannot be derived for enums with fields, as secret values have no \
+                 discriminant; use a struct instead",
+            )
+            .to_compile_error()
+            .into();
+        }
     };
+    let field_bindings: Vec<_> = field_members
+        .iter()
+        .map(derive_commons::member_binding)
+        .collect();
+    let field_inits: Vec<_> = field_members
+        .iter()
+        .map(derive_commons::member_init)
+        .collect();
 
     let impl_fixed_size = {
         let trait_name: syn::Path = parse_quote! { ::pbc_zk::SecretBinaryFixedSize };
@@ -47,14 +64,14 @@ pub(crate) fn implement_secret(input: TokenStream) -> TokenStream {
 
         let read_block = quote! {
             #(
-                let #field_names = <#field_types as #trait_name>::secret_read_from(reader);
+                let #field_bindings = <#field_types as #trait_name>::secret_read_from(reader);
             )*
-            Self { #(#field_names),* }
+            Self { #(#field_inits),* }
         };
 
         let write_block = quote! {
             #(
-                <#field_types as #trait_name>::secret_write_to(&self.#field_names, writer)?;
+                <#field_types as #trait_name>::secret_write_to(&self.#field_members, writer)?;
             )*
             Ok(())
         };
@@ -62,10 +79,10 @@ pub(crate) fn implement_secret(input: TokenStream) -> TokenStream {
         quote! {
             #[automatically_derived]
             impl #impl_generics #trait_name for #type_name #ty_generics #where_clause {
-                fn secret_read_from<ReadT: std::io::Read>(reader: &mut ReadT) -> Self {
+                fn secret_read_from<ReadT: pbc_traits::PbcRead>(reader: &mut ReadT) -> Self {
                     #read_block
                 }
-                fn secret_write_to<WriteT: std::io::Write>(&self, writer: &mut WriteT) -> std::io::Result<()> {
+                fn secret_write_to<WriteT: pbc_traits::PbcWrite>(&self, writer: &mut WriteT) -> pbc_traits::io::Result<()> {
                     #write_block
                 }
             }
@@ -10,7 +10,7 @@ use proc_macro::TokenStream;
 use derive_commons::{impl_read_write, ReadWriteGenType};
 
 /// Implement `ReadRPC` for the annotated struct and enums.
-#[proc_macro_derive(ReadRPC, attributes(discriminant))]
+#[proc_macro_derive(ReadRPC, attributes(discriminant, pbc))]
 pub fn implement_read_rpc(input: TokenStream) -> TokenStream {
     // Parse the string representation
     let ast = syn::parse(input).unwrap();
@@ -21,6 +21,7 @@ pub fn implement_read_rpc(input: TokenStream) -> TokenStream {
         format_ident!("ReadRPC"),
         format_ident!("rpc_read_from"),
         format_ident!("rpc_write_to"),
+        format_ident!("rpc_serialized_size"),
         None,
         ReadWriteGenType::READ,
     );
@@ -30,7 +31,7 @@ pub fn implement_read_rpc(input: TokenStream) -> TokenStream {
 }
 
 /// Implement `WriteRPC` for the annotated struct and enums.
-#[proc_macro_derive(WriteRPC, attributes(discriminant))]
+#[proc_macro_derive(WriteRPC, attributes(discriminant, pbc))]
 pub fn implement_write_rpc(input: TokenStream) -> TokenStream {
     // Parse the string representation
     let ast = syn::parse(input).unwrap();
@@ -41,6 +42,7 @@ pub fn implement_write_rpc(input: TokenStream) -> TokenStream {
         format_ident!("WriteRPC"),
         format_ident!("rpc_read_from"),
         format_ident!("rpc_write_to"),
+        format_ident!("rpc_serialized_size"),
         None,
         ReadWriteGenType::WRITE,
     );
@@ -50,7 +52,7 @@ pub fn implement_write_rpc(input: TokenStream) -> TokenStream {
 }
 
 /// Implement `ReadRPC` and `WriteRPC` for the annotated struct and enums.
-#[proc_macro_derive(ReadWriteRPC, attributes(discriminant))]
+#[proc_macro_derive(ReadWriteRPC, attributes(discriminant, pbc))]
 pub fn implement_read_write_rpc(input: TokenStream) -> TokenStream {
     let mut gen: TokenStream = implement_read_rpc(input.clone());
     gen.extend(implement_write_rpc(input));
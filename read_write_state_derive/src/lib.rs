@@ -7,10 +7,12 @@ extern crate syn;
 
 use proc_macro::TokenStream;
 
-use derive_commons::{impl_read_write, ReadWriteGenType};
+use derive_commons::{impl_read_write, FieldOptions, ReadWriteGenType, SupportedKind};
+
+mod state_view;
 
 /// Implement `ReadWriteState` for the annotated struct and enums.
-#[proc_macro_derive(ReadWriteState, attributes(discriminant))]
+#[proc_macro_derive(ReadWriteState, attributes(discriminant, pbc))]
 pub fn read_write(input: TokenStream) -> TokenStream {
     // Parse the string representation
     let ast = syn::parse(input).unwrap();
@@ -21,7 +23,8 @@ pub fn read_write(input: TokenStream) -> TokenStream {
         format_ident!("ReadWriteState"),
         format_ident!("state_read_from"),
         format_ident!("state_write_to"),
-        Some(make_serialize_by_copy_constant),
+        format_ident!("state_serialized_size"),
+        Some(make_state_only_items),
         ReadWriteGenType::COMBINED,
     );
 
@@ -29,6 +32,135 @@ pub fn read_write(input: TokenStream) -> TokenStream {
     gen.into()
 }
 
+/// Implement `StateViewFields` for the annotated struct, giving lazy access to its fields through
+/// `StateView::fields`.
+///
+/// Generates a `{Name}StateFields<'a>` type with a method for each field, returning a `StateView`
+/// of the field. The struct must also implement `ReadWriteState`. Fields with `#[pbc(skip)]` or
+/// `#[pbc(with = "module")]` have no view method.
+#[proc_macro_derive(StateView, attributes(pbc))]
+pub fn state_view(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    state_view::impl_state_view(&ast).into()
+}
+
+/// Creator for the items only present in derived `ReadWriteState` implementations: the
+/// [`ReadWriteState::SERIALIZABLE_BY_COPY`] associated constant and the
+/// [`ReadWriteState::state_skip`] method.
+fn make_state_only_items(
+    type_name: &proc_macro2::Ident,
+    supported_kind: &SupportedKind,
+    trait_name: &proc_macro2::Ident,
+) -> proc_macro2::TokenStream {
+    let constant = make_serialize_by_copy_constant(supported_kind, trait_name);
+    let skip_method = make_state_skip_method(type_name, supported_kind);
+    quote! {
+        #constant
+        #skip_method
+    }
+}
+
+/// Creator for the derived [`ReadWriteState::state_skip`] method, skipping fields one at a time
+/// without decoding them.
+///
+/// C-style enums are serializable by copy, and use the default implementation.
+fn make_state_skip_method(
+    type_name: &proc_macro2::Ident,
+    supported_kind: &SupportedKind,
+) -> proc_macro2::TokenStream {
+    let skip_logic = match supported_kind {
+        SupportedKind::StructWithNamedFields { fields }
+        | SupportedKind::StructWithUnnamedFields { fields } => {
+            let field_skips = fields.iter().map(|field| {
+                make_field_skip(
+                    &derive_commons::field_to_type(field),
+                    &derive_commons::field_options(field),
+                )
+            });
+            quote! {
+                if <Self as pbc_traits::ReadWriteState>::SERIALIZABLE_BY_COPY {
+                    if bytes.len() < ::core::mem::size_of::<Self>() {
+                        return ::core::result::Result::Err(pbc_traits::DecodeError::UnexpectedEof);
+                    }
+                    return ::core::result::Result::Ok(::core::mem::size_of::<Self>());
+                }
+                let mut offset = 0usize;
+                #(#field_skips)*
+                ::core::result::Result::Ok(offset)
+            }
+        }
+        SupportedKind::DiscriminatedCstyleEnum { .. } => return quote! {},
+        SupportedKind::ItemStructEnum {
+            variant_discriminants,
+            variant_fields_types,
+            variant_fields_options,
+            ..
+        } => {
+            let type_name = type_name.to_string();
+            let variant_skips =
+                variant_fields_types
+                    .iter()
+                    .zip(variant_fields_options)
+                    .map(|(types, options)| {
+                        let field_skips = types
+                            .iter()
+                            .zip(options)
+                            .map(|(ty, options)| make_field_skip(ty, options));
+                        quote! { #(#field_skips)* }
+                    });
+            quote! {
+                let __discriminant = match bytes.first() {
+                    ::core::option::Option::Some(discriminant) => *discriminant,
+                    ::core::option::Option::None => {
+                        return ::core::result::Result::Err(pbc_traits::DecodeError::UnexpectedEof)
+                    }
+                };
+                let mut offset = 1usize;
+                match __discriminant {
+                    #(#variant_discriminants => { #variant_skips })*
+                    __unknown => {
+                        return ::core::result::Result::Err(pbc_traits::DecodeError::InvalidDiscriminant {
+                            type_name: #type_name,
+                            discriminant: __unknown,
+                        })
+                    }
+                }
+                ::core::result::Result::Ok(offset)
+            }
+        }
+    };
+    quote! {
+        fn state_skip(bytes: &[u8]) -> ::core::result::Result<usize, pbc_traits::DecodeError> {
+            #skip_logic
+        }
+    }
+}
+
+/// Statement advancing `offset` past the serialization of a field at `bytes[offset..]`.
+///
+/// Fields serialized by a `with` module are skipped by reading them, as the module gives no other
+/// way to determine their size.
+///
+/// * `ty` - Type of the field.
+/// * `options` - Serialization options of the field.
+fn make_field_skip(
+    ty: &proc_macro2::TokenStream,
+    options: &FieldOptions,
+) -> proc_macro2::TokenStream {
+    match (&options.with, options.skip) {
+        (_, true) => quote! {},
+        (Some(module), false) => quote! {
+            let mut reader = &bytes[offset..];
+            let available = reader.len();
+            #module::try_state_read_from(&mut reader)?;
+            offset += available - reader.len();
+        },
+        (None, false) => quote! {
+            offset += <#ty as pbc_traits::ReadWriteState>::state_skip(&bytes[offset..])?;
+        },
+    }
+}
+
 /// Creator for the derived [`ReadWriteState::SERIALIZABLE_BY_COPY`] associated constant.
 ///
 /// See documentation for [`ReadWriteState::SERIALIZABLE_BY_COPY`]; this derived constant is based
@@ -39,19 +171,29 @@ pub fn read_write(input: TokenStream) -> TokenStream {
 ///
 /// Other conditions may occur in the constant expression, depending upon the circumstances.
 fn make_serialize_by_copy_constant(
-    supported_kind: &derive_commons::SupportedKind,
+    supported_kind: &SupportedKind,
     trait_name: &proc_macro2::Ident,
 ) -> proc_macro2::TokenStream {
     let const_id = format_ident!("SERIALIZABLE_BY_COPY");
     let expression = match supported_kind {
-        derive_commons::SupportedKind::StructWithNamedFields { fields } => {
+        SupportedKind::StructWithNamedFields { fields }
+        | SupportedKind::StructWithUnnamedFields { fields }
+            if !fields
+                .iter()
+                .all(|field| derive_commons::field_options(field).is_verbatim()) =>
+        {
+            // Skipped and custom serialized fields are not represented identically in memory.
+            quote! { false }
+        }
+        SupportedKind::StructWithNamedFields { fields }
+        | SupportedKind::StructWithUnnamedFields { fields } => {
             let fieldtypes: Vec<_> = fields.iter().map(derive_commons::field_to_type).collect();
             make_serialize_by_copy_constant_struct(&fieldtypes, trait_name, &const_id)
         }
-        derive_commons::SupportedKind::DiscriminatedCstyleEnum { .. } => {
+        SupportedKind::DiscriminatedCstyleEnum { .. } => {
             quote! { true }
         }
-        derive_commons::SupportedKind::ItemStructEnum { .. } => {
+        SupportedKind::ItemStructEnum { .. } => {
             quote! { false }
         }
     };
@@ -82,8 +224,7 @@ fn make_serialize_by_copy_constant_struct(
     //
     // This is a temporary limitation, as we are currently not supporting SERIALIZABLE_BY_COPY in
     // padded structs.
-    let aligned_at_size =
-        quote! { (std::mem::size_of::<Self>().wrapping_rem(std::mem::align_of::<Self>()) == 0) };
+    let aligned_at_size = quote! { (::core::mem::size_of::<Self>().wrapping_rem(::core::mem::align_of::<Self>()) == 0) };
 
     // Construct padding check expression, of form:
     //
@@ -96,8 +237,8 @@ fn make_serialize_by_copy_constant_struct(
     //
     // This is a temporary limitation, as we are currently not supporting SERIALIZABLE_BY_COPY in
     // padded structs.
-    let bytes_used_by_fields = quote! { #( std::mem::size_of::<#fieldtypes>() +)* 0 };
-    let tightly_packed = quote! { (std::mem::size_of::<Self>() == #bytes_used_by_fields ) };
+    let bytes_used_by_fields = quote! { #( ::core::mem::size_of::<#fieldtypes>() +)* 0 };
+    let tightly_packed = quote! { (::core::mem::size_of::<Self>() == #bytes_used_by_fields ) };
 
     // Collect all expressions together.
     quote! { #fields_serializable_by_copy & #aligned_at_size & #tightly_packed }
@@ -0,0 +1,149 @@
+//! Derive of `StateViewFields`, generating lazy views of the fields of a struct.
+
+use derive_commons::{field_options, field_to_member, field_to_type, SupportedKind};
+use proc_macro2::TokenStream;
+use syn::{parse_quote, GenericParam, Lifetime, LifetimeDef};
+
+/// Implement `StateViewFields` for the given struct, together with its fields type.
+///
+/// May panic if the annotated type is not a struct.
+///
+/// * `ast` - Abstract Syntax Tree of the annotated struct.
+pub(crate) fn impl_state_view(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let fields_name = format_ident!("{}StateFields", name);
+
+    let fields = match derive_commons::get_kind_data(&ast.data) {
+        SupportedKind::StructWithNamedFields { fields }
+        | SupportedKind::StructWithUnnamedFields { fields } => fields,
+        _ => panic!("StateView can only be derived for structs"),
+    };
+
+    // Generics of the fields type; those of the struct, preceded by the lifetime of the buffer.
+    let view_lifetime: Lifetime = parse_quote! { '__view };
+    let mut view_generics = ast.generics.clone();
+    view_generics.params.insert(
+        0,
+        GenericParam::Lifetime(LifetimeDef::new(view_lifetime.clone())),
+    );
+    let mut bounded_view_generics = view_generics.clone();
+    derive_commons::extend_generic_bounds_with_trait(
+        &mut bounded_view_generics,
+        &parse_quote! { pbc_traits::ReadWriteState },
+    );
+    let (view_impl_generics, view_ty_generics, view_where_clause) =
+        bounded_view_generics.split_for_impl();
+
+    let mut generics = ast.generics.clone();
+    derive_commons::extend_generic_bounds_with_trait(
+        &mut generics,
+        &parse_quote! { pbc_traits::ReadWriteState },
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Offset of each field is found by skipping all the fields before it.
+    let mut offset_steps = vec![];
+    let mut accessors = vec![];
+    let mut viewed_steps = 0;
+    for (index, field) in fields.iter().enumerate() {
+        let ty = field_to_type(field);
+        let options = field_options(field);
+        offset_steps.push(quote! {
+            if field_index == #index {
+                return ::core::result::Result::Ok(offset);
+            }
+        });
+        offset_steps.push(crate::make_field_skip(&ty, &options));
+
+        if !options.is_verbatim() {
+            continue;
+        }
+        viewed_steps = offset_steps.len() - 1;
+        let field_vis = &field.vis;
+        let (accessor, description) = match field_to_member(index, field) {
+            syn::Member::Named(ident) => (ident.clone(), format!("View of the field `{ident}`.")),
+            syn::Member::Unnamed(_) => (
+                format_ident!("field_{}", index),
+                format!("View of the field at position {index}."),
+            ),
+        };
+        let try_accessor = format_ident!("try_{}", accessor);
+        let try_description = format!(
+            "{description}\n\nReturns a [`DecodeError`](pbc_traits::DecodeError) if the fields \
+             before it are malformed."
+        );
+        let panic_message = format!("Could not locate field {accessor} of {name}: {{}}");
+        accessors.push(quote! {
+            #[doc = #description]
+            ///
+            /// Panics if the fields before it are malformed.
+            #field_vis fn #accessor(&self) -> pbc_traits::StateView<#view_lifetime, #ty> {
+                self.#try_accessor()
+                    .unwrap_or_else(|error| panic!(#panic_message, error))
+            }
+
+            #[doc = #try_description]
+            #field_vis fn #try_accessor(&self) -> ::core::result::Result<pbc_traits::StateView<#view_lifetime, #ty>, pbc_traits::DecodeError> {
+                let offset = self.__field_offset(#index)?;
+                ::core::result::Result::Ok(pbc_traits::StateView::new(&self.bytes[offset..]))
+            }
+        });
+    }
+
+    // Fields after the last viewable field need not be skipped.
+    offset_steps.truncate(viewed_steps);
+    let offset_logic = if accessors.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            /// Offset in the buffer of the field with the given index.
+            #[allow(unused_mut, unused_variables)]
+            fn __field_offset(&self, field_index: usize) -> ::core::result::Result<usize, pbc_traits::DecodeError> {
+                let bytes = self.bytes;
+                let mut offset = 0usize;
+                #(#offset_steps)*
+                unreachable!("Field index {} out of bounds", field_index)
+            }
+        }
+    };
+
+    let (_, view_ty_generics_unbounded, _) = view_generics.split_for_impl();
+    let fields_doc = format!("Views of the fields of a State serialized [`{name}`].");
+    let (_, struct_ty_generics, _) = ast.generics.split_for_impl();
+    let (struct_generics, struct_where_clause) =
+        (&view_generics.params, &view_generics.where_clause);
+
+    quote! {
+        #[doc = #fields_doc]
+        #vis struct #fields_name<#struct_generics> #struct_where_clause {
+            bytes: &#view_lifetime [u8],
+            value_type: ::core::marker::PhantomData<fn() -> #name #struct_ty_generics>,
+        }
+
+        impl #view_impl_generics #fields_name #view_ty_generics #view_where_clause {
+            #(#accessors)*
+            #offset_logic
+        }
+
+        impl #view_impl_generics Clone for #fields_name #view_ty_generics #view_where_clause {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl #view_impl_generics Copy for #fields_name #view_ty_generics #view_where_clause {}
+
+        #[automatically_derived]
+        impl #impl_generics pbc_traits::StateViewFields for #name #ty_generics #where_clause {
+            type Fields<#view_lifetime> = #fields_name #view_ty_generics_unbounded;
+
+            fn fields(bytes: &[u8]) -> Self::Fields<'_> {
+                #fields_name {
+                    bytes,
+                    value_type: ::core::marker::PhantomData,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,334 @@
+#![cfg(feature = "abi")]
+#![allow(dead_code)]
+use create_type_spec_derive::CreateTypeSpec;
+use pbc_contract_codegen::{action, init, state};
+use pbc_contract_common::abi::generate::{generate_types, LookupTable};
+use pbc_contract_common::abi::{AbiFile, AbiHeader, AbiReadError, ContractAbi, NamedTypeSpec};
+use pbc_contract_common::abi::{FnAbi, KindInfo};
+use pbc_contract_common::address::Address;
+use pbc_contract_common::context::ContractContext;
+use pbc_contract_common::events::EventGroup;
+
+/// A bid placed in the auction.
+#[derive(CreateTypeSpec, read_write_rpc_derive::ReadWriteRPC)]
+pub struct Bid {
+    /// The account placing the bid.
+    bidder: Address,
+    amount: u128,
+}
+
+/// Phases of the auction.
+#[derive(CreateTypeSpec, read_write_state_derive::ReadWriteState)]
+pub enum Phase {
+    /// Bids are accepted.
+    #[discriminant(0)]
+    Open {},
+    #[discriminant(1)]
+    Closed {
+        /// The winning bid, if any bids were placed.
+        ///
+        /// Refunds are sent to all other bidders.
+        winner: Option<u128>,
+    },
+}
+
+/// State of the auction.
+#[state]
+pub struct AuctionState {
+    /// Owner of the auction, who may close it.
+    owner: Address,
+    /// The current phase.
+    phase: Phase,
+}
+
+#[init]
+pub fn initialize(context: ContractContext) -> AuctionState {
+    AuctionState {
+        owner: context.sender,
+        phase: Phase::Open {},
+    }
+}
+
+/// Places a bid in the auction.
+///
+/// ### Parameters:
+///
+/// * `bid` - The bid to place. The amount must
+///   exceed the highest bid.
+/// * `refund_to`: Account receiving the refund, should the bid be
+///   beaten.
+/// * `unknown` - Not a parameter.
+#[action]
+pub fn place_bid(
+    _context: ContractContext,
+    state: AuctionState,
+    bid: Bid,
+    refund_to: Address,
+    memo: String,
+) -> (AuctionState, Vec<EventGroup>) {
+    let _ = (bid, refund_to, memo);
+    (state, vec![])
+}
+
+const HEADER: AbiHeader = AbiHeader {
+    version_binder: [10, 2, 0],
+    version_client: [5, 6, 0],
+};
+
+fn contract_abi() -> ContractAbi {
+    let type_suppliers: Vec<LookupTable<Vec<NamedTypeSpec>>> = vec![
+        __abi_for_type_auctionstate,
+        __abi_for_type_phase,
+        __abi_for_type_bid,
+    ];
+    let (lut, types) = unsafe { generate_types(type_suppliers.iter()) };
+    let mut contract = ContractAbi::new(vec![0x00, 0x00]);
+    contract.actions(vec![__abi_fn_initialize(&lut), __abi_fn_place_bid(&lut)]);
+    contract.types(types);
+    contract
+}
+
+fn abi_bytes(contract: &ContractAbi) -> Vec<u8> {
+    let mut bytes = HEADER.bytes().to_vec();
+    contract
+        .serialize_abi_for_version(HEADER.version_client, &mut bytes)
+        .unwrap();
+    bytes
+}
+
+/// Removes all documentation from the contract, by reading it back from a client version without
+/// the documentation section.
+fn undocumented(contract: &ContractAbi) -> ContractAbi {
+    let header = AbiHeader {
+        version_client: [5, 5, 0],
+        ..HEADER
+    };
+    let mut bytes = header.bytes().to_vec();
+    contract
+        .serialize_abi_for_version(header.version_client, &mut bytes)
+        .unwrap();
+    AbiFile::parse(&bytes).unwrap().contract
+}
+
+#[test]
+fn doc_comments_of_types() {
+    let contract = contract_abi();
+    let docs: Vec<(&str, Option<&str>)> = contract
+        .named_types()
+        .iter()
+        .map(|t| (t.name.as_str(), t.doc.as_deref()))
+        .collect();
+    assert_eq!(
+        docs,
+        [
+            ("AuctionState", Some("State of the auction.")),
+            ("Phase", Some("Phases of the auction.")),
+            ("Open", Some("Bids are accepted.")),
+            ("Closed", None),
+            ("Bid", Some("A bid placed in the auction.")),
+        ]
+    );
+
+    let field_docs = |index: usize| -> Vec<Option<String>> {
+        let KindInfo::Struct { fields } = &contract.named_types()[index].kind_information else {
+            panic!("Not a struct");
+        };
+        fields.iter().map(|field| field.doc.clone()).collect()
+    };
+    assert_eq!(
+        field_docs(0),
+        [
+            Some("Owner of the auction, who may close it.".to_string()),
+            Some("The current phase.".to_string()),
+        ]
+    );
+    assert_eq!(
+        field_docs(3),
+        [Some(
+            "The winning bid, if any bids were placed.\n\nRefunds are sent to all other bidders."
+                .to_string()
+        )]
+    );
+    assert_eq!(
+        field_docs(4),
+        [Some("The account placing the bid.".to_string()), None]
+    );
+}
+
+#[test]
+fn doc_comments_of_functions() {
+    let contract = contract_abi();
+    let initialize = &contract.functions()[0];
+    assert_eq!(initialize.doc(), None);
+
+    let place_bid: &FnAbi = &contract.functions()[1];
+    assert!(place_bid
+        .doc()
+        .unwrap()
+        .starts_with("Places a bid in the auction.\n\n### Parameters:\n\n* `bid`"));
+    let argument_docs: Vec<(&str, Option<&str>)> = place_bid
+        .arguments()
+        .iter()
+        .map(|argument| (argument.name.as_str(), argument.doc.as_deref()))
+        .collect();
+    assert_eq!(
+        argument_docs,
+        [
+            (
+                "bid",
+                Some("The bid to place. The amount must exceed the highest bid.")
+            ),
+            (
+                "refund_to",
+                Some("Account receiving the refund, should the bid be beaten.")
+            ),
+            ("memo", None),
+        ]
+    );
+}
+
+#[test]
+fn documentation_section_follows_the_state() {
+    let contract = contract_abi();
+    let bytes = abi_bytes(&contract);
+    let undocumented_bytes = abi_bytes(&undocumented(&contract));
+
+    // The sections differ, where the undocumented one is empty.
+    let section_start = undocumented_bytes.len() - 4;
+    assert_eq!(undocumented_bytes[section_start..], [0, 0, 0, 0]);
+    assert_eq!(bytes[..section_start], undocumented_bytes[..section_start]);
+    let section = &bytes[section_start..];
+    let length = u32::from_be_bytes(section[..4].try_into().unwrap()) as usize;
+    assert_eq!(section.len(), 4 + length);
+    // Five named types, of which the first is documented.
+    assert_eq!(section[4..8], [0, 0, 0, 5]);
+    assert_eq!(section[8..12], [0, 0, 0, 21]);
+    assert_eq!(&section[12..33], b"State of the auction.");
+
+    let file = AbiFile::parse(&bytes).unwrap();
+    let mut serialized = vec![];
+    file.serialize_abi(&mut serialized).unwrap();
+    assert_eq!(serialized, bytes);
+}
+
+#[cfg(feature = "abi_json")]
+#[test]
+fn documentation_in_json() {
+    let contract = contract_abi();
+    let file = AbiFile::parse(&abi_bytes(&contract)).unwrap();
+    assert_eq!(file.contract.to_json(), contract.to_json());
+
+    let parsed_json: serde_json::Value = serde_json::from_str(&file.contract.to_json()).unwrap();
+    assert_eq!(parsed_json["types"][0]["doc"], "State of the auction.");
+    assert_eq!(
+        parsed_json["functions"][1]["arguments"][1]["doc"],
+        "Account receiving the refund, should the bid be beaten."
+    );
+    assert!(parsed_json["functions"][1]["arguments"][2]
+        .get("doc")
+        .is_none());
+    assert_eq!(
+        ContractAbi::from_json(&parsed_json.to_string()).unwrap(),
+        file.contract
+    );
+}
+
+#[test]
+fn undocumented_abi_has_an_empty_documentation_section() {
+    let contract = undocumented(&contract_abi());
+    assert!(contract.named_types().iter().all(|t| t.doc.is_none()));
+    let bytes = abi_bytes(&contract);
+    assert_eq!(bytes[bytes.len() - 6..], [0x00, 0x00, 0, 0, 0, 0]);
+    assert_eq!(AbiFile::parse(&bytes).unwrap().contract, contract);
+}
+
+#[test]
+fn undocumented_abi_keeps_the_earlier_version() {
+    let contract = contract_abi();
+    assert_eq!(contract.client_version(), HEADER.version_client);
+    let mut serialized = vec![];
+    contract.serialize_abi(&mut serialized).unwrap();
+    assert_eq!(serialized, abi_bytes(&contract)[12..]);
+
+    // Without documentation the file has no documentation section, as with 5.4.0 files.
+    let contract = undocumented(&contract);
+    assert_eq!(contract.client_version(), [5, 4, 0]);
+    let mut serialized = vec![];
+    contract.serialize_abi(&mut serialized).unwrap();
+    let mut old_bytes = vec![];
+    contract
+        .serialize_abi_for_version([5, 4, 0], &mut old_bytes)
+        .unwrap();
+    assert_eq!(serialized, old_bytes);
+    assert_eq!(serialized[serialized.len() - 2..], [0x00, 0x00]);
+}
+
+#[test]
+fn documentation_section_is_versioned() {
+    let contract = contract_abi();
+    let bytes = abi_bytes(&contract);
+    let section_start = abi_bytes(&undocumented(&contract)).len() - 4;
+
+    // Files of 5.5.0 end with the state type, so documentation is left out when writing them.
+    let mut old_bytes = AbiHeader {
+        version_client: [5, 5, 0],
+        ..HEADER
+    }
+    .bytes()
+    .to_vec();
+    contract
+        .serialize_abi_for_version([5, 5, 0], &mut old_bytes)
+        .unwrap();
+    assert_eq!(old_bytes[12..], bytes[12..section_start]);
+    let old_file = AbiFile::parse(&old_bytes).unwrap();
+    assert_eq!(old_file.contract, undocumented(&contract));
+
+    let mut documented_old_bytes = old_bytes.clone();
+    documented_old_bytes.extend(&bytes[section_start..]);
+    assert_eq!(
+        AbiFile::parse(&documented_old_bytes),
+        Err(AbiReadError::TrailingBytes)
+    );
+
+    // Files of 5.6.0 have the section, even when nothing is documented.
+    let mut missing = bytes[..section_start].to_vec();
+    assert_eq!(AbiFile::parse(&missing), Err(AbiReadError::UnexpectedEnd));
+    missing[9..12].copy_from_slice(&[5, 5, 0]);
+    assert!(AbiFile::parse(&missing).is_ok());
+}
+
+#[test]
+fn malformed_documentation_section_is_rejected() {
+    let contract = contract_abi();
+    let bytes = abi_bytes(&contract);
+    let section_start = abi_bytes(&undocumented(&contract)).len() - 4;
+
+    // Files of 5.6.0 end with the documentation section.
+    let mut extended = bytes.clone();
+    extended.push(0);
+    assert_eq!(AbiFile::parse(&extended), Err(AbiReadError::TrailingBytes));
+
+    assert_eq!(
+        AbiFile::parse(&bytes[..bytes.len() - 1]),
+        Err(AbiReadError::UnexpectedEnd)
+    );
+
+    // The section documents no named types or functions, while the contract has some.
+    let mut empty = bytes[..section_start].to_vec();
+    empty.extend([0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(
+        AbiFile::parse(&empty),
+        Err(AbiReadError::InvalidDocumentation)
+    );
+
+    // The section continues after the function documentation.
+    let mut longer = bytes[..section_start].to_vec();
+    let length = u32::from_be_bytes(bytes[section_start..section_start + 4].try_into().unwrap());
+    longer.extend((length + 1).to_be_bytes());
+    longer.extend(&bytes[section_start + 4..]);
+    longer.push(0);
+    assert_eq!(
+        AbiFile::parse(&longer),
+        Err(AbiReadError::InvalidDocumentation)
+    );
+}
@@ -0,0 +1,445 @@
+#![cfg(feature = "abi")]
+#![allow(dead_code)]
+use create_type_spec_derive::CreateTypeSpec;
+use pbc_contract_common::abi::abi_for_tuple;
+use pbc_contract_common::abi::generate::{generate_types, LookupTable};
+#[cfg(feature = "abi_json")]
+use pbc_contract_common::abi::AbiJsonError;
+use pbc_contract_common::abi::{
+    AbiFile, AbiHeader, AbiReadError, ContractAbi, EnumVariant, FnAbi, KindInfo, NamedTypeSpec,
+};
+use pbc_contract_common::address::{Address, Shortname};
+use pbc_contract_common::avl_tree_map::AvlTreeMap;
+use pbc_contract_common::FunctionKind;
+
+#[derive(CreateTypeSpec)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+
+#[derive(CreateTypeSpec)]
+enum Shape {
+    #[discriminant(0)]
+    Point { at: Position },
+    #[discriminant(4)]
+    Polygon { corners: Vec<Position> },
+}
+
+#[derive(CreateTypeSpec)]
+struct GameState {
+    owner: Address,
+    shapes: AvlTreeMap<u32, Shape>,
+    scores: Vec<(Address, Option<u64>)>,
+    board: [[u8; 3]; 200],
+}
+
+const HEADER: AbiHeader = AbiHeader {
+    version_binder: [11, 0, 0],
+    version_client: [5, 5, 0],
+};
+
+fn contract_abi() -> ContractAbi {
+    let type_suppliers: Vec<LookupTable<Vec<NamedTypeSpec>>> = vec![
+        __abi_for_type_gamestate,
+        __abi_for_type_shape,
+        __abi_for_type_position,
+        abi_for_tuple::<(Address, Option<u64>)>,
+    ];
+    let (lut, types) = unsafe { generate_types(type_suppliers.iter()) };
+
+    let mut init = FnAbi::new("initialize".to_string(), None, FunctionKind::Init);
+    init.argument::<Address>("owner".to_string(), &lut);
+    let mut add_shape = FnAbi::new(
+        "add_shape".to_string(),
+        Some(Shortname::from_u32(0x1234)),
+        FunctionKind::Action,
+    );
+    add_shape.argument::<u32>("id".to_string(), &lut);
+    add_shape.argument::<Shape>("shape".to_string(), &lut);
+    let callback = FnAbi::new("shape_added".to_string(), None, FunctionKind::Callback);
+    let mut secret = FnAbi::new(
+        "secret_move".to_string(),
+        None,
+        FunctionKind::ZkSecretInputWithExplicitType,
+    );
+    secret.argument::<Position>("public_position".to_string(), &lut);
+    secret.default_secret_argument(&lut);
+
+    let state_index = types.iter().position(|t| t.name == "GameState").unwrap() as u8;
+    let mut contract = ContractAbi::new(vec![0x00, state_index]);
+    contract.actions(vec![init, add_shape, callback, secret]);
+    contract.types(types);
+    contract
+}
+
+fn abi_bytes() -> Vec<u8> {
+    let mut bytes = HEADER.bytes().to_vec();
+    contract_abi()
+        .serialize_abi_for_version(HEADER.version_client, &mut bytes)
+        .unwrap();
+    bytes
+}
+
+#[test]
+fn header_bytes() {
+    assert_eq!(
+        HEADER.bytes(),
+        [b'P', b'B', b'C', b'A', b'B', b'I', 11, 0, 0, 5, 5, 0]
+    );
+}
+
+#[test]
+fn parse_generated_abi() {
+    let bytes = abi_bytes();
+    let file = AbiFile::parse(&bytes).unwrap();
+    assert_eq!(file.header, HEADER);
+
+    let contract = &file.contract;
+    let names: Vec<&str> = contract
+        .named_types()
+        .iter()
+        .map(|t| t.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "GameState",
+            "Shape",
+            "Point",
+            "Polygon",
+            "Position",
+            "(Address, Option<u64>)"
+        ]
+    );
+
+    let state = contract.state_type().unwrap();
+    assert_eq!(state.name, "GameState");
+    assert_eq!(contract.state_type_spec(), &[0x00, 0x00]);
+    let KindInfo::Struct { fields } = &state.kind_information else {
+        panic!("State is a struct");
+    };
+    let field_specs: Vec<(&str, &[u8])> = fields
+        .iter()
+        .map(|f| (f.name.as_str(), f.type_spec.as_slice()))
+        .collect();
+    assert_eq!(
+        field_specs,
+        [
+            ("owner", &[0x0d][..]),
+            ("shapes", &[0x19, 0x03, 0x00, 0x01]),
+            ("scores", &[0x0e, 0x00, 0x05]),
+            ("board", &[0x1a, 0x11, 0x03, 0, 0, 0, 200]),
+        ]
+    );
+    assert_eq!(fields[1].type_index, None);
+    assert_eq!(
+        contract.named_types()[1].kind_information,
+        KindInfo::Enum {
+            variants: vec![
+                EnumVariant::new(0, vec![0x00, 0x02]),
+                EnumVariant::new(4, vec![0x00, 0x03]),
+            ]
+        }
+    );
+
+    let functions = contract.functions();
+    let kinds: Vec<FunctionKind> = functions.iter().map(FnAbi::kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            FunctionKind::Init,
+            FunctionKind::Action,
+            FunctionKind::Callback,
+            FunctionKind::ZkSecretInputWithExplicitType
+        ]
+    );
+    let add_shape = &functions[1];
+    assert_eq!(add_shape.name().name(), "add_shape");
+    assert_eq!(add_shape.name().shortname(), &Shortname::from_u32(0x1234));
+    assert_eq!(add_shape.arguments()[1].type_spec, [0x00, 0x01]);
+    assert_eq!(add_shape.arguments()[1].type_index, Some(1));
+    assert_eq!(add_shape.secret_input(), None);
+
+    let secret_input = functions[3].secret_input().unwrap();
+    assert_eq!(secret_input.name, "secret_input");
+    assert_eq!(secret_input.type_spec, [0x08]);
+    assert_eq!(functions[3].arguments()[0].type_spec, [0x00, 0x04]);
+}
+
+#[test]
+fn parsed_abi_serializes_to_the_same_bytes() {
+    let bytes = abi_bytes();
+    let file = AbiFile::parse(&bytes).unwrap();
+    let mut serialized = vec![];
+    file.serialize_abi(&mut serialized).unwrap();
+    assert_eq!(serialized, bytes);
+    assert_eq!(AbiFile::parse(&serialized), Ok(file));
+}
+
+#[test]
+fn client_version_of_the_types_used() {
+    // The board of the state is an array with the 0x1a ordinal.
+    let contract = contract_abi();
+    assert_eq!(contract.client_version(), HEADER.version_client);
+    let mut serialized = vec![];
+    contract.serialize_abi(&mut serialized).unwrap();
+    assert_eq!(serialized, abi_bytes()[12..]);
+
+    // Lengths and indices equal to the ordinal are not arrays.
+    for state in [vec![0x11, 0x1a], vec![0x00, 0x1a], vec![0x0e, 0x00, 0x1a]] {
+        let contract = ContractAbi::new(state.clone());
+        assert_eq!(contract.client_version(), [5, 4, 0]);
+        let mut serialized = vec![];
+        contract.serialize_abi(&mut serialized).unwrap();
+        assert_eq!(
+            serialized,
+            [[0, 0, 0, 0, 0, 0, 0, 0].to_vec(), state].concat()
+        );
+    }
+    let contract = ContractAbi::new(vec![0x12, 0x1a, 0x03, 0, 0, 0, 0x1a]);
+    assert_eq!(contract.client_version(), [5, 5, 0]);
+}
+
+#[test]
+fn malformed_abis_are_rejected() {
+    let bytes = abi_bytes();
+    assert_eq!(
+        AbiFile::parse(&bytes[1..]),
+        Err(AbiReadError::InvalidHeader)
+    );
+    assert_eq!(AbiFile::parse(b"PBC"), Err(AbiReadError::InvalidHeader));
+    for length in 12..bytes.len() {
+        assert_eq!(
+            AbiFile::parse(&bytes[..length]),
+            Err(AbiReadError::UnexpectedEnd),
+            "{length}"
+        );
+    }
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(AbiFile::parse(&trailing), Err(AbiReadError::TrailingBytes));
+
+    let mut version = bytes.clone();
+    version[9] = 4;
+    assert_eq!(
+        AbiFile::parse(&version),
+        Err(AbiReadError::UnsupportedVersion {
+            version_client: [4, 5, 0]
+        })
+    );
+
+    // Later minor versions may contain encodings that cannot be read.
+    let [major, minor, patch] = AbiHeader::SUPPORTED_CLIENT_VERSION;
+    let mut newer = bytes.clone();
+    newer[9..12].copy_from_slice(&[major, minor + 1, patch]);
+    assert_eq!(
+        AbiFile::parse(&newer),
+        Err(AbiReadError::UnsupportedVersion {
+            version_client: [major, minor + 1, patch]
+        })
+    );
+
+    // The board is an array with the 0x1a ordinal, which was added in 5.5.0.
+    let mut older = bytes.clone();
+    older[10] = 4;
+    assert_eq!(
+        AbiFile::parse(&older),
+        Err(AbiReadError::UnknownTypeOrdinal(0x1a))
+    );
+
+    let state_spec = bytes.len() - 2;
+    let mut unknown_type = bytes.clone();
+    unknown_type[state_spec + 1] = 6;
+    assert_eq!(
+        AbiFile::parse(&unknown_type),
+        Err(AbiReadError::UnknownNamedType(6))
+    );
+
+    let mut unknown_ordinal = bytes.clone();
+    unknown_ordinal[state_spec] = 0x42;
+    assert_eq!(
+        AbiFile::parse(&unknown_ordinal),
+        Err(AbiReadError::UnknownTypeOrdinal(0x42))
+    );
+
+    let mut type_kind = bytes.clone();
+    type_kind[16] = 3;
+    assert_eq!(
+        AbiFile::parse(&type_kind),
+        Err(AbiReadError::UnknownTypeKind(3))
+    );
+    assert_eq!(
+        AbiReadError::UnknownTypeOrdinal(0x42).to_string(),
+        "unknown type ordinal 0x42"
+    );
+}
+
+#[test]
+fn minimal_abi() {
+    let mut bytes = HEADER.bytes().to_vec();
+    bytes.extend([0, 0, 0, 0, 0, 0, 0, 1]); // No types and one function.
+    bytes.extend([0x42, 0, 0, 0, 1, b'f', 0x01, 0, 0, 0, 0]); // Unknown kind, named f.
+    bytes.push(0x0c);
+    assert_eq!(
+        AbiFile::parse(&bytes),
+        Err(AbiReadError::UnknownFunctionKind(0x42))
+    );
+
+    bytes[20] = 0x02;
+    let file = AbiFile::parse(&bytes).unwrap();
+    assert_eq!(file.contract.state_type_spec(), &[0x0c]);
+    assert_eq!(file.contract.state_type(), None);
+    assert_eq!(
+        file.contract.functions(),
+        [FnAbi::new(
+            "f".to_string(),
+            Some(Shortname::from_u32(1)),
+            FunctionKind::Action
+        )]
+    );
+}
+
+#[cfg(feature = "abi_json")]
+#[test]
+fn json_rendering() {
+    let file = AbiFile::parse(&abi_bytes()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&file.to_json()).unwrap();
+    assert_eq!(json["version_binder"], "11.0.0");
+    assert_eq!(json["version_client"], "5.5.0");
+    assert_eq!(
+        json["state"],
+        serde_json::json!({ "type": "Named", "index": 0, "name": "GameState" })
+    );
+    assert_eq!(
+        json["types"][0]["fields"][1],
+        serde_json::json!({
+            "name": "shapes",
+            "type": {
+                "type": "AvlTreeMap",
+                "key": { "type": "u32" },
+                "value": { "type": "Named", "index": 1, "name": "Shape" },
+            },
+        })
+    );
+    assert_eq!(
+        json["types"][0]["fields"][3]["type"],
+        serde_json::json!({
+            "type": "Array",
+            "element": { "type": "ByteArray", "length": 3 },
+            "length": 200,
+        })
+    );
+    assert_eq!(
+        json["types"][1],
+        serde_json::json!({
+            "name": "Shape",
+            "kind": "enum",
+            "variants": [
+                { "discriminant": 0, "type": { "type": "Named", "index": 2, "name": "Point" } },
+                { "discriminant": 4, "type": { "type": "Named", "index": 3, "name": "Polygon" } },
+            ],
+        })
+    );
+    assert_eq!(
+        json["functions"][1],
+        serde_json::json!({
+            "kind": "action",
+            "name": "add_shape",
+            "shortname": "b424",
+            "arguments": [
+                { "name": "id", "type": { "type": "u32" } },
+                { "name": "shape", "type": { "type": "Named", "index": 1, "name": "Shape" } },
+            ],
+        })
+    );
+    assert_eq!(
+        json["functions"][3]["kind"],
+        "zk_on_secret_input_with_explicit_type"
+    );
+    assert_eq!(
+        json["functions"][3]["secret_argument"],
+        serde_json::json!({ "name": "secret_input", "type": { "type": "i32" } })
+    );
+}
+
+#[cfg(feature = "abi_json")]
+#[test]
+fn json_converts_back_to_binary() {
+    let bytes = abi_bytes();
+    let json = AbiFile::parse(&bytes).unwrap().to_json();
+    let reformatted = serde_json::from_str::<serde_json::Value>(&json)
+        .unwrap()
+        .to_string();
+
+    let file = AbiFile::from_json(&reformatted).unwrap();
+    let mut serialized = vec![];
+    file.serialize_abi(&mut serialized).unwrap();
+    assert_eq!(serialized, bytes);
+    assert_eq!(file.to_json(), json);
+}
+
+#[cfg(feature = "abi_json")]
+#[test]
+fn invalid_json_is_rejected() {
+    let json: serde_json::Value =
+        serde_json::from_str(&AbiFile::parse(&abi_bytes()).unwrap().to_json()).unwrap();
+    let error_at = |path: &str, expected: &'static str| {
+        Err(AbiJsonError {
+            path: path.to_string(),
+            expected,
+        })
+    };
+
+    let mut unknown_type = json.clone();
+    unknown_type["types"][0]["fields"][1]["type"]["value"]["index"] = 6.into();
+    assert_eq!(
+        AbiFile::from_json(&unknown_type.to_string()),
+        error_at(
+            "types[0].fields[1].type.value.index",
+            "the index of a named type"
+        )
+    );
+
+    let mut unknown_kind = json.clone();
+    unknown_kind["functions"][2]["kind"] = "view".into();
+    assert_eq!(
+        AbiFile::from_json(&unknown_kind.to_string()),
+        error_at("functions[2].kind", "a function kind")
+    );
+
+    let mut shortname = json.clone();
+    shortname["functions"][0]["shortname"] = "80".into();
+    assert_eq!(
+        AbiFile::from_json(&shortname.to_string()),
+        error_at("functions[0].shortname", "LEB128 bytes in hexadecimal")
+    );
+
+    let mut byte_array = json.clone();
+    byte_array["types"][0]["fields"][3]["type"]["element"]["length"] = 128.into();
+    assert_eq!(
+        AbiFile::from_json(&byte_array.to_string()),
+        error_at(
+            "types[0].fields[3].type.element.length",
+            "a length of at most 127"
+        )
+    );
+
+    let mut version = json.clone();
+    version["version_client"] = "5.5".into();
+    assert_eq!(
+        AbiFile::from_json(&version.to_string()),
+        error_at("version_client", "a version such as \"5.5.0\"")
+    );
+
+    let mut missing_state = json;
+    missing_state.as_object_mut().unwrap().remove("state");
+    let error = AbiFile::from_json(&missing_state.to_string()).unwrap_err();
+    assert_eq!(error.to_string(), "expected a value at state");
+
+    assert_eq!(
+        AbiFile::from_json(r#"{"types": [}"#),
+        error_at("line 1 column 12", "JSON")
+    );
+}
@@ -0,0 +1,276 @@
+#![cfg(feature = "abi")]
+#![allow(dead_code)]
+use create_type_spec_derive::CreateTypeSpec;
+use pbc_contract_codegen::{action, init, state};
+use pbc_contract_common::abi::abi_for_tuple;
+use pbc_contract_common::abi::generate::{generate_types, LookupTable};
+use pbc_contract_common::abi::{AbiFile, AbiHeader, AbiReadError, ContractAbi, NamedTypeSpec};
+use pbc_contract_common::address::Address;
+use pbc_contract_common::context::ContractContext;
+use pbc_contract_common::events::EventGroup;
+#[cfg(feature = "abi_json")]
+use serde_json::{json, Value};
+
+#[derive(CreateTypeSpec, read_write_rpc_derive::ReadWriteRPC)]
+pub struct Balance {
+    owner: Address,
+    amount: u128,
+}
+
+#[state]
+pub struct TokenState {
+    total_supply: u128,
+}
+
+#[init]
+pub fn initialize(_context: ContractContext) -> TokenState {
+    TokenState { total_supply: 0 }
+}
+
+#[action(returns = "u128")]
+pub fn total_supply(_context: ContractContext, state: TokenState) -> (TokenState, Vec<EventGroup>) {
+    let mut event_group = EventGroup::builder();
+    event_group.return_data(state.total_supply);
+    (state, vec![event_group.build()])
+}
+
+#[action(shortname = 0x10, returns = "Vec<Balance>")]
+pub fn balances(
+    _context: ContractContext,
+    state: TokenState,
+    owners: Vec<Address>,
+) -> (TokenState, Vec<EventGroup>) {
+    let _ = owners;
+    (state, vec![])
+}
+
+#[action(returns = "(u32, bool)")]
+pub fn status(_context: ContractContext, state: TokenState) -> (TokenState, Vec<EventGroup>) {
+    (state, vec![])
+}
+
+#[action]
+pub fn mint(
+    _context: ContractContext,
+    mut state: TokenState,
+    amount: u128,
+) -> (TokenState, Vec<EventGroup>) {
+    state.total_supply += amount;
+    (state, vec![])
+}
+
+const HEADER: AbiHeader = AbiHeader {
+    version_binder: [10, 2, 0],
+    version_client: [5, 7, 0],
+};
+
+fn contract_abi() -> ContractAbi {
+    let type_suppliers: Vec<LookupTable<Vec<NamedTypeSpec>>> = vec![
+        __abi_for_type_tokenstate,
+        __abi_for_type_balance,
+        abi_for_tuple::<(u32, bool)>,
+    ];
+    let (lut, types) = unsafe { generate_types(type_suppliers.iter()) };
+    let mut contract = ContractAbi::new(vec![0x00, 0x00]);
+    contract.actions(vec![
+        __abi_fn_initialize(&lut),
+        __abi_fn_total_supply(&lut),
+        __abi_fn_balances(&lut),
+        __abi_fn_status(&lut),
+        __abi_fn_mint(&lut),
+    ]);
+    contract.types(types);
+    contract
+}
+
+fn abi_bytes(contract: &ContractAbi) -> Vec<u8> {
+    let mut bytes = HEADER.bytes().to_vec();
+    contract
+        .serialize_abi_for_version(HEADER.version_client, &mut bytes)
+        .unwrap();
+    bytes
+}
+
+/// The return type section of the contract without return types.
+const PLAIN_SECTION: [u8; 13] = [0, 0, 0, 9, 0, 0, 0, 5, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+/// Removes all return types from the contract, by reading it back from a client version without
+/// the return type section.
+fn without_return_types(contract: &ContractAbi) -> ContractAbi {
+    let header = AbiHeader {
+        version_client: [5, 6, 0],
+        ..HEADER
+    };
+    let mut bytes = header.bytes().to_vec();
+    contract
+        .serialize_abi_for_version(header.version_client, &mut bytes)
+        .unwrap();
+    AbiFile::parse(&bytes).unwrap().contract
+}
+
+#[test]
+fn return_types_of_actions() {
+    let contract = contract_abi();
+    let return_types: Vec<(&str, Option<&[u8]>)> = contract
+        .functions()
+        .iter()
+        .map(|function| (function.name().name(), function.return_type_spec()))
+        .collect();
+    assert_eq!(
+        return_types,
+        [
+            ("initialize", None),
+            ("total_supply", Some(&[0x05][..])),
+            ("balances", Some(&[0x0e, 0x00, 0x01][..])),
+            ("status", Some(&[0x00, 0x02][..])),
+            ("mint", None),
+        ]
+    );
+}
+
+#[test]
+fn return_type_section_follows_the_documentation_section() {
+    let contract = contract_abi();
+    let bytes = abi_bytes(&contract);
+    let plain_bytes = abi_bytes(&without_return_types(&contract));
+
+    // The section lists every function, including those without a return type.
+    let section_start = plain_bytes.len() - PLAIN_SECTION.len();
+    assert_eq!(plain_bytes[section_start..], PLAIN_SECTION);
+    assert_eq!(bytes[..section_start], plain_bytes[..section_start]);
+    // Nothing is documented, so the documentation section preceding it is empty.
+    assert_eq!(bytes[section_start - 4..section_start], [0, 0, 0, 0]);
+    assert_eq!(
+        bytes[section_start..],
+        [
+            [0, 0, 0, 15].as_slice(),
+            &[0, 0, 0, 5],
+            &[0x00],
+            &[0x01, 0x05],
+            &[0x01, 0x0e, 0x00, 0x01],
+            &[0x01, 0x00, 0x02],
+            &[0x00],
+        ]
+        .concat()
+    );
+
+    let file = AbiFile::parse(&bytes).unwrap();
+    let mut serialized = vec![];
+    file.serialize_abi(&mut serialized).unwrap();
+    assert_eq!(serialized, bytes);
+}
+
+#[test]
+fn return_type_section_is_versioned() {
+    let contract = contract_abi();
+    let bytes = abi_bytes(&contract);
+    let section_start = abi_bytes(&without_return_types(&contract)).len() - PLAIN_SECTION.len();
+
+    // Files of 5.6.0 end with the documentation section, so return types are left out when
+    // writing them.
+    let mut old_bytes = AbiHeader {
+        version_client: [5, 6, 0],
+        ..HEADER
+    }
+    .bytes()
+    .to_vec();
+    contract
+        .serialize_abi_for_version([5, 6, 0], &mut old_bytes)
+        .unwrap();
+    assert_eq!(old_bytes[12..], bytes[12..section_start]);
+    let old_file = AbiFile::parse(&old_bytes).unwrap();
+    assert_eq!(old_file.contract, without_return_types(&contract));
+
+    let mut declared_old_bytes = old_bytes.clone();
+    declared_old_bytes.extend(&bytes[section_start..]);
+    assert_eq!(
+        AbiFile::parse(&declared_old_bytes),
+        Err(AbiReadError::TrailingBytes)
+    );
+
+    // Files of 5.7.0 have the section, even when no return types are declared.
+    let mut missing = bytes[..section_start].to_vec();
+    assert_eq!(AbiFile::parse(&missing), Err(AbiReadError::UnexpectedEnd));
+    missing[9..12].copy_from_slice(&[5, 6, 0]);
+    assert!(AbiFile::parse(&missing).is_ok());
+}
+
+#[test]
+fn abi_without_return_types_keeps_the_earlier_version() {
+    let contract = contract_abi();
+    assert_eq!(contract.client_version(), HEADER.version_client);
+    let mut serialized = vec![];
+    contract.serialize_abi(&mut serialized).unwrap();
+    assert_eq!(serialized, abi_bytes(&contract)[12..]);
+
+    // Without return types or documentation the file ends with the state, as with 5.4.0 files.
+    let contract = without_return_types(&contract);
+    assert_eq!(contract.client_version(), [5, 4, 0]);
+    let mut serialized = vec![];
+    contract.serialize_abi(&mut serialized).unwrap();
+    let mut old_bytes = vec![];
+    contract
+        .serialize_abi_for_version([5, 4, 0], &mut old_bytes)
+        .unwrap();
+    assert_eq!(serialized, old_bytes);
+    assert_eq!(serialized[serialized.len() - 2..], [0x00, 0x00]);
+}
+
+#[cfg(feature = "abi_json")]
+#[test]
+fn return_types_in_json() {
+    let contract = contract_abi();
+    let file = AbiFile::parse(&abi_bytes(&contract)).unwrap();
+    assert_eq!(file.contract.to_json(), contract.to_json());
+
+    let json: Value = serde_json::from_str(&contract.to_json()).unwrap();
+    assert_eq!(
+        json["functions"][1]["return_type"],
+        json!({ "type": "u128" })
+    );
+    assert_eq!(
+        json["functions"][2]["return_type"],
+        json!({ "type": "Vec", "element": { "type": "Named", "index": 1, "name": "Balance" } })
+    );
+    assert!(json["functions"][4].get("return_type").is_none());
+    assert_eq!(
+        ContractAbi::from_json(&json.to_string()).unwrap().to_json(),
+        contract_abi().to_json()
+    );
+}
+
+#[test]
+fn malformed_return_type_section_is_rejected() {
+    let contract = contract_abi();
+    let bytes = abi_bytes(&contract);
+    let section_start = abi_bytes(&without_return_types(&contract)).len() - PLAIN_SECTION.len();
+
+    let mut extended = bytes.clone();
+    extended.push(0);
+    assert_eq!(AbiFile::parse(&extended), Err(AbiReadError::TrailingBytes));
+
+    // The section declares the return types of fewer functions than the contract has.
+    let mut fewer = bytes[..section_start].to_vec();
+    fewer.extend([0, 0, 0, 5, 0, 0, 0, 1, 0x00]);
+    assert_eq!(
+        AbiFile::parse(&fewer),
+        Err(AbiReadError::InvalidReturnTypes)
+    );
+
+    // A function has neither 0x00 nor 0x01 as return type marker.
+    let mut marker = bytes.clone();
+    marker[section_start + 8] = 0x02;
+    assert_eq!(
+        AbiFile::parse(&marker),
+        Err(AbiReadError::InvalidReturnTypes)
+    );
+
+    // A return type references a named type that does not exist.
+    let mut unknown = bytes.clone();
+    let last_named = unknown.len() - 2;
+    unknown[last_named] = 7;
+    assert_eq!(
+        AbiFile::parse(&unknown),
+        Err(AbiReadError::UnknownNamedType(7))
+    );
+}
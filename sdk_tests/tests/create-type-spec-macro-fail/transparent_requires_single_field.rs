@@ -0,0 +1,7 @@
+#![no_main]
+use create_type_spec_derive::CreateTypeSpec;
+use std::marker::PhantomData;
+
+#[derive(CreateTypeSpec)]
+#[repr(transparent)]
+struct MyNewtype(u32, PhantomData<u8>);
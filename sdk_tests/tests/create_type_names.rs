@@ -1,7 +1,14 @@
 #![cfg(feature = "abi")]
-use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::num::{NonZeroI8, NonZeroU64};
+use std::rc::Rc;
 
+use create_type_spec_derive::create_type_spec_for_generic;
+use pbc_contract_common::abi::KindInfo;
+use pbc_contract_common::address::Address;
 use pbc_contract_common::sorted_vec_map::{SortedVec, SortedVecMap, SortedVecSet};
+use pbc_contract_common::{__abi_for_type_decimal, Decimal};
 use pbc_traits::CreateTypeSpec;
 
 #[test]
@@ -23,6 +30,9 @@ pub fn ty_names_simple_types() {
 #[test]
 pub fn ty_names_complex_types() {
     assert_eq!(<BTreeSet<String>>::__ty_name(), "BTreeSet<String>");
+    assert_eq!(<HashSet<String>>::__ty_name(), "HashSet<String>");
+    assert_eq!(<BTreeMap<u8, String>>::__ty_name(), "BTreeMap<u8, String>");
+    assert_eq!(<HashMap<u8, String>>::__ty_name(), "HashMap<u8, String>");
     assert_eq!(<Vec<String>>::__ty_name(), "Vec<String>");
     assert_eq!(<VecDeque<String>>::__ty_name(), "VecDeque<String>");
     assert_eq!(<VecDeque<String>>::__ty_identifier(), "VecDeque<String>");
@@ -70,6 +80,10 @@ pub fn ty_names_arrays() {
     assert_eq!(<[u8; 31]>::__ty_name(), "[u8; 31]");
     assert_eq!(<[u8; 32]>::__ty_name(), "[u8; 32]");
     assert_eq!(<[u8; 101]>::__ty_name(), "[u8; 101]");
+    assert_eq!(<[u8; 256]>::__ty_name(), "[u8; 256]");
+    assert_eq!(<[u16; 3]>::__ty_name(), "[u16; 3]");
+    assert_eq!(<[Option<u8>; 2]>::__ty_name(), "[Option<u8>; 2]");
+    assert_eq!(<[[u8; 2]; 3]>::__ty_identifier(), "[[u8; 2]; 3]");
 }
 
 #[track_caller]
@@ -137,6 +151,10 @@ pub fn ty_ordinals_complex_types() {
     assert_ty::<Option<Option<i128>>>(&[0x12, 0x12, 0x0a]);
 
     assert_ty::<BTreeSet<i128>>(&[0x10, 0x0a]);
+    assert_ty::<HashSet<i128>>(&[0x10, 0x0a]);
+    assert_ty::<BTreeMap<u8, String>>(&[0x0f, 0x01, 0x0b]);
+    assert_ty::<HashMap<u8, String>>(&[0x0f, 0x01, 0x0b]);
+    assert_ty::<BTreeMap<u8, BTreeSet<bool>>>(&[0x0f, 0x01, 0x10, 0x0c]);
 
     assert_ty::<BTreeSet<Vec<BTreeSet<Vec<Vec<String>>>>>>(&[0x10, 0x0e, 0x10, 0x0e, 0x0e, 0x0b]);
 }
@@ -176,6 +194,44 @@ pub fn ty_ordinals_arrays() {
     assert_ty::<[u8; 31]>(&[0x11, 0x1f]);
     assert_ty::<[u8; 32]>(&[0x11, 0x20]);
     assert_ty::<[u8; 101]>(&[0x11, 101]);
+    assert_ty::<[u8; 127]>(&[0x11, 0x7f]);
+}
+
+#[test]
+pub fn ty_ordinals_wide_arrays() {
+    assert_ty::<[u8; 128]>(&[0x1a, 0x01, 0x00, 0x00, 0x00, 0x80]);
+    assert_ty::<[u8; 256]>(&[0x1a, 0x01, 0x00, 0x00, 0x01, 0x00]);
+    assert_ty::<[u16; 3]>(&[0x1a, 0x02, 0x00, 0x00, 0x00, 0x03]);
+    assert_ty::<[Option<bool>; 2]>(&[0x1a, 0x12, 0x0c, 0x00, 0x00, 0x00, 0x02]);
+    assert_ty::<[[u8; 2]; 3]>(&[0x1a, 0x11, 0x02, 0x00, 0x00, 0x00, 0x03]);
+    assert_ty::<[Address; 3]>(&[0x1a, 0x0d, 0x00, 0x00, 0x00, 0x03]);
+}
+
+#[test]
+pub fn ty_names_pointers_chars_non_zero_integers_and_unit() {
+    assert_eq!(<Box<u32>>::__ty_name(), "Box<u32>");
+    assert_eq!(<Rc<Vec<String>>>::__ty_name(), "Rc<Vec<String>>");
+    assert_eq!(<Cow<str>>::__ty_name(), "Cow<str>");
+    assert_eq!(char::__ty_name(), "char");
+    assert_eq!(NonZeroU64::__ty_name(), "NonZeroU64");
+    assert_eq!(<()>::__ty_name(), "()");
+}
+
+#[test]
+pub fn ty_ordinals_pointers_chars_non_zero_integers_and_unit() {
+    assert_ty::<Box<u32>>(&[0x03]);
+    assert_ty::<Option<Box<Vec<u8>>>>(&[0x12, 0x0e, 0x01]);
+    assert_ty::<Rc<String>>(&[0x0b]);
+    assert_ty::<Cow<str>>(&[0x0b]);
+    assert_ty::<char>(&[0x03]);
+    assert_ty::<NonZeroU64>(&[0x04]);
+    assert_ty::<NonZeroI8>(&[0x06]);
+    assert_ty::<()>(&[0x00, 0xFF]);
+
+    let lut = BTreeMap::from([("()".to_string(), 2)]);
+    let mut vec = Vec::new();
+    <Vec<()>>::__ty_spec_write(&mut vec, &lut);
+    assert_eq!(vec, [0x0e, 0x00, 0x02]);
 }
 
 #[test]
@@ -231,3 +287,31 @@ pub fn sorted_vec_set() {
     assert_ty::<SortedVecSet<u32>>(&[0x10, 0x03]);
     assert_ty::<SortedVecSet<SortedVecSet<u32>>>(&[0x10, 0x10, 0x03]);
 }
+
+create_type_spec_for_generic! { Decimal<6> }
+
+#[test]
+pub fn decimals_are_named_after_their_scale() {
+    assert_eq!(<Decimal<18>>::__ty_name(), "Decimal<18>");
+    assert_eq!(<Decimal<0>>::__ty_identifier(), "Decimal<0>");
+
+    let mut lut = BTreeMap::new();
+    lut.insert(<Decimal<6>>::__ty_identifier(), 3);
+    let mut type_spec = Vec::new();
+    <Vec<Decimal<6>>>::__ty_spec_write(&mut type_spec, &lut);
+    assert_eq!(type_spec, [0x0e, 0x00, 3]);
+
+    let decimal = &__abi_for_type_decimal::<6>(&lut)[0];
+    assert_eq!(decimal.name, "Decimal<6>");
+    assert_eq!(decimal.type_spec, [0x00, 3]);
+    assert_eq!(
+        decimal.doc.as_deref(),
+        Some("A decimal number with 6 digits after the decimal point, stored in `raw` multiplied by 10^6.")
+    );
+    let KindInfo::Struct { fields } = &decimal.kind_information else {
+        panic!("Not a struct");
+    };
+    assert_eq!(fields.len(), 1);
+    assert_eq!(fields[0].name, "raw");
+    assert_eq!(fields[0].type_spec, [0x05]);
+}
@@ -6,6 +6,7 @@ use std::collections::BTreeSet;
 use create_type_spec_derive::create_type_spec_for_generic;
 
 use create_type_spec_derive::CreateTypeSpec;
+use pbc_contract_common::abi::abi_for_tuple;
 use pbc_contract_common::abi::generate::{generate_types, LookupTable};
 use pbc_contract_common::abi::AbiSerialize;
 use pbc_contract_common::abi::NamedTypeSpec;
@@ -77,6 +78,14 @@ struct WithArray {
     inner: [u8; 50],
 }
 
+#[derive(Clone, CreateTypeSpec)]
+struct WithWideArrays {
+    #[allow(dead_code)]
+    blob: [u8; 256],
+    #[allow(dead_code)]
+    signers: [Option<u32>; 3],
+}
+
 #[derive(Clone, PartialOrd, Ord, PartialEq, Eq, CreateTypeSpec)]
 enum MyRoleEnum<RankT: PartialOrd + PartialEq + Eq + Ord + Clone> {
     #[discriminant(1)]
@@ -339,6 +348,32 @@ fn serialize_struct_with_array() {
     assert_abi(&result, expected);
 }
 
+#[test]
+fn serialize_struct_with_wide_arrays() {
+    let mut lut: BTreeMap<String, u8> = BTreeMap::new();
+
+    lut.insert(WithWideArrays::__ty_identifier(), 1);
+
+    let result: NamedTypeSpec = __abi_for_type_withwidearrays(&lut)
+        .into_iter()
+        .next()
+        .unwrap();
+    let expected = vec![
+        0x01, // It's a struct
+        0x00, 0x00, 0x00, 14, // Length of name
+        b'W', b'i', b't', b'h', b'W', b'i', b'd', b'e', b'A', b'r', b'r', b'a', b'y',
+        b's', // Name
+        0, 0, 0, 2, // 2 fields
+        0, 0, 0, 4, // field name length
+        b'b', b'l', b'o', b'b', // Field name
+        0x1a, 0x01, 0, 0, 1, 0, // Array of u8 with length 256.
+        0, 0, 0, 7, // field name length
+        b's', b'i', b'g', b'n', b'e', b'r', b's', // Field name
+        0x1a, 0x12, 0x03, 0, 0, 0, 3, // Array of Option<u32> with length 3.
+    ];
+    assert_abi(&result, expected);
+}
+
 #[allow(dead_code)]
 #[derive(CreateTypeSpec)]
 enum EnumItemStruct {
@@ -436,7 +471,7 @@ fn serialize_nested_enum() {
     ];
     let (_, types) = unsafe { generate_types(functions.iter()) };
 
-    let nested_enum = types.get(0).unwrap();
+    let nested_enum = types.first().unwrap();
 
     let expected: Vec<u8> = vec![
         0x02, // It's an enum
@@ -463,6 +498,288 @@ fn serialize_nested_enum() {
     assert_eq!(types.get(2).unwrap().name, "EnumItemStruct");
 }
 
+#[allow(dead_code)]
+#[derive(CreateTypeSpec)]
+enum Phase {
+    #[discriminant(0)]
+    Bidding(u64, Inner),
+    #[discriminant(1)]
+    Closed { winner: u8 },
+    #[discriminant(2)]
+    Cancelled,
+}
+
+#[test]
+fn serialize_enum_with_tuple_and_unit_variants() {
+    let functions: Vec<LookupTable<Vec<NamedTypeSpec>>> =
+        vec![__abi_for_type_phase, __abi_for_type_inner];
+    let (_, types) = unsafe { generate_types(functions.iter()) };
+
+    let expected: Vec<u8> = vec![
+        0x02, // It's an enum
+        0, 0, 0, 5, // Length of name
+        b'P', b'h', b'a', b's', b'e', // Name
+        0, 0, 0, 3,    // 3 variants
+        0x00, // discriminant for Bidding
+        0x00, 0x01, // pointer to Bidding
+        0x01, // discriminant for Closed
+        0x00, 0x02, // pointer to Closed
+        0x02, // discriminant for Cancelled
+        0x00, 0x03, // pointer to Cancelled
+    ];
+    assert_abi(types.first().unwrap(), expected);
+
+    let bidding = types.get(1).unwrap();
+    let expected: Vec<u8> = vec![
+        0x01, // It's a struct
+        0, 0, 0, 7, // Length of name
+        b'B', b'i', b'd', b'd', b'i', b'n', b'g', // Name
+        0, 0, 0, 2, // 2 fields
+        0, 0, 0, 1,    // field name length
+        b'0', // Field name
+        0x04, // u64
+        0, 0, 0, 1,    // field name length
+        b'1', // Field name
+        0x00, 0x04, // Pointer to Inner
+    ];
+    assert_abi(bidding, expected);
+
+    let cancelled = types.get(3).unwrap();
+    let expected: Vec<u8> = vec![
+        0x01, // It's a struct
+        0, 0, 0, 9, // Length of name
+        b'C', b'a', b'n', b'c', b'e', b'l', b'l', b'e', b'd', // Name
+        0, 0, 0, 0, // No fields
+    ];
+    assert_abi(cancelled, expected);
+}
+
+/// Serializes an amount of seconds as a `u64`.
+mod seconds {
+    pub type AbiType = u64;
+}
+
+#[allow(dead_code)]
+#[derive(CreateTypeSpec)]
+struct WithFieldOptions {
+    #[pbc(rename = "ownerName")]
+    owner_name: String,
+    #[pbc(skip)]
+    cache: Vec<u8>,
+    #[pbc(with = "seconds")]
+    timeout: std::time::Duration,
+}
+
+#[allow(dead_code)]
+#[derive(CreateTypeSpec)]
+enum EnumWithFieldOptions {
+    #[discriminant(0)]
+    Single(#[pbc(skip)] u32, #[pbc(rename = "value")] u8),
+}
+
+#[test]
+fn field_options_in_abi() {
+    let lut: BTreeMap<String, u8> = BTreeMap::new();
+    let abi: NamedTypeSpec = __abi_for_type_withfieldoptions(&lut)
+        .into_iter()
+        .next()
+        .unwrap();
+
+    let expected: Vec<u8> = vec![
+        0x01, // It's a struct
+        0, 0, 0, 16, // Length of name
+        b'W', b'i', b't', b'h', b'F', b'i', b'e', b'l', b'd', b'O', b'p', b't', b'i', b'o', b'n',
+        b's', // Name
+        0, 0, 0, 2, // 2 fields
+        0, 0, 0, 9, // field name length
+        b'o', b'w', b'n', b'e', b'r', b'N', b'a', b'm', b'e', // Field name
+        0x0b, // String
+        0, 0, 0, 7, // field name length
+        b't', b'i', b'm', b'e', b'o', b'u', b't', // Field name
+        0x04, // u64
+    ];
+    assert_abi(&abi, expected);
+
+    let types = __abi_for_type_enumwithfieldoptions(&lut);
+    let single = types.get(1).unwrap();
+    let expected: Vec<u8> = vec![
+        0x01, // It's a struct
+        0, 0, 0, 6, // Length of name
+        b'S', b'i', b'n', b'g', b'l', b'e', // Name
+        0, 0, 0, 1, // 1 field
+        0, 0, 0, 5, // field name length
+        b'v', b'a', b'l', b'u', b'e', // Field name
+        0x01, // u8
+    ];
+    assert_abi(single, expected);
+}
+
+#[derive(CreateTypeSpec)]
+struct WithTuples {
+    pairs: Vec<(Inner, u8)>,
+    nested: (bool, (Inner, u8)),
+}
+
+#[test]
+fn tuple_type_spec() {
+    assert_eq!(<(Inner, u8)>::__ty_name(), "(Inner, u8)");
+    assert_eq!(<(u8,)>::__ty_name(), "(u8,)");
+    assert_eq!(
+        <(Inner, u8)>::__ty_identifier(),
+        format!("({}, u8)", Inner::__ty_identifier())
+    );
+
+    let mut lut: BTreeMap<String, u8> = BTreeMap::new();
+    let mut ordinal: Vec<u8> = Vec::new();
+    <(Inner, u8)>::__ty_spec_write(&mut ordinal, &lut);
+    assert_eq!(ordinal, vec![0x00, 0xFF]);
+
+    lut.insert(<(Inner, u8)>::__ty_identifier(), 7);
+    let mut ordinal: Vec<u8> = Vec::new();
+    <Vec<(Inner, u8)>>::__ty_spec_write(&mut ordinal, &lut);
+    assert_eq!(ordinal, vec![0x0e, 0x00, 7]);
+}
+
+#[test]
+fn tuples_are_anonymous_structs() {
+    let functions: Vec<LookupTable<Vec<NamedTypeSpec>>> = vec![
+        __abi_for_type_withtuples,
+        abi_for_tuple::<(Inner, u8)>,
+        abi_for_tuple::<(bool, (Inner, u8))>,
+        __abi_for_type_inner,
+    ];
+    let (lut, types) = unsafe { generate_types(functions.iter()) };
+    assert_eq!(lut.len(), 4);
+
+    let with_tuples = types.first().unwrap();
+    let expected: Vec<u8> = vec![
+        0x01, // It's a struct
+        0, 0, 0, 10, // Length of name
+        b'W', b'i', b't', b'h', b'T', b'u', b'p', b'l', b'e', b's', // Name
+        0, 0, 0, 2, // 2 fields
+        0, 0, 0, 5, // field name length
+        b'p', b'a', b'i', b'r', b's', // Field name
+        0x0e, 0x00, 0x01, // Vec of pointer to (Inner, u8)
+        0, 0, 0, 6, // field name length
+        b'n', b'e', b's', b't', b'e', b'd', // Field name
+        0x00, 0x02, // pointer to (bool, (Inner, u8))
+    ];
+    assert_abi(with_tuples, expected);
+
+    let pair = types.get(1).unwrap();
+    let expected: Vec<u8> = vec![
+        0x01, // It's a struct
+        0, 0, 0, 11, // Length of name
+        b'(', b'I', b'n', b'n', b'e', b'r', b',', b' ', b'u', b'8', b')', // Name
+        0, 0, 0, 2, // 2 fields
+        0, 0, 0, 1,    // field name length
+        b'0', // Field name
+        0x00, 0x03, // pointer to Inner
+        0, 0, 0, 1,    // field name length
+        b'1', // Field name
+        0x01, // u8
+    ];
+    assert_abi(pair, expected);
+
+    let nested = types.get(2).unwrap();
+    assert_eq!(nested.name, "(bool, (Inner, u8))");
+    assert_eq!(nested.type_spec, vec![0x00, 0x02]);
+}
+
+#[derive(CreateTypeSpec)]
+struct PositionalFields(Inner, u8);
+
+#[derive(CreateTypeSpec)]
+#[repr(transparent)]
+struct TransparentId(u64);
+
+#[derive(CreateTypeSpec)]
+#[repr(transparent)]
+struct TransparentInner(Inner);
+
+#[derive(CreateTypeSpec)]
+struct WithNewtypes {
+    id: TransparentId,
+    inner: TransparentInner,
+}
+
+#[test]
+fn tuple_structs_have_positional_field_names() {
+    assert_eq!(PositionalFields::__ty_name(), "PositionalFields");
+
+    let functions: Vec<LookupTable<Vec<NamedTypeSpec>>> =
+        vec![__abi_for_type_positionalfields, __abi_for_type_inner];
+    let (_, types) = unsafe { generate_types(functions.iter()) };
+
+    let expected: Vec<u8> = vec![
+        0x01, // It's a struct
+        0, 0, 0, 16, // Length of name
+        b'P', b'o', b's', b'i', b't', b'i', b'o', b'n', b'a', b'l', b'F', b'i', b'e', b'l', b'd',
+        b's', // Name
+        0, 0, 0, 2, // 2 fields
+        0, 0, 0, 1,    // field name length
+        b'0', // Field name
+        0x00, 0x01, // pointer to Inner
+        0, 0, 0, 1,    // field name length
+        b'1', // Field name
+        0x01, // u8
+    ];
+    assert_abi(types.first().unwrap(), expected);
+}
+
+#[test]
+fn transparent_newtypes_are_their_inner_type() {
+    assert_eq!(TransparentId::__ty_name(), "u64");
+    assert_eq!(TransparentInner::__ty_name(), "Inner");
+    assert_eq!(
+        TransparentInner::__ty_identifier(),
+        Inner::__ty_identifier()
+    );
+
+    let mut ordinal: Vec<u8> = Vec::new();
+    TransparentId::__ty_spec_write(&mut ordinal, &BTreeMap::new());
+    assert_eq!(ordinal, vec![0x04]);
+
+    let functions: Vec<LookupTable<Vec<NamedTypeSpec>>> = vec![
+        __abi_for_type_withnewtypes,
+        __abi_for_type_transparentid,
+        __abi_for_type_transparentinner,
+        __abi_for_type_inner,
+    ];
+    let (lut, types) = unsafe { generate_types(functions.iter()) };
+    assert_eq!(lut.len(), 2);
+    assert_eq!(types.len(), 2);
+
+    let expected: Vec<u8> = vec![
+        0x01, // It's a struct
+        0, 0, 0, 12, // Length of name
+        b'W', b'i', b't', b'h', b'N', b'e', b'w', b't', b'y', b'p', b'e', b's', // Name
+        0, 0, 0, 2, // 2 fields
+        0, 0, 0, 2, // field name length
+        b'i', b'd', // Field name
+        0x04, // u64
+        0, 0, 0, 5, // field name length
+        b'i', b'n', b'n', b'e', b'r', // Field name
+        0x00, 0x01, // pointer to Inner
+    ];
+    assert_abi(types.first().unwrap(), expected);
+    assert_eq!(types.get(1).unwrap().name, "Inner");
+}
+
+#[test]
+fn duplicate_types_are_generated_once() {
+    let functions: Vec<LookupTable<Vec<NamedTypeSpec>>> = vec![
+        abi_for_tuple::<(Inner, u8)>,
+        __abi_for_type_inner,
+        abi_for_tuple::<(Inner, u8)>,
+    ];
+    let (lut, types) = unsafe { generate_types(functions.iter()) };
+    assert_eq!(lut.len(), 2);
+    assert_eq!(types.len(), 2);
+    assert_eq!(types.first().unwrap().name, "(Inner, u8)");
+    assert_eq!(types.get(1).unwrap().name, "Inner");
+}
+
 fn assert_abi<T: AbiSerialize>(obj: &T, expected: Vec<u8>) {
     let mut actual = Vec::new();
     obj.serialize_abi(&mut actual).unwrap();
@@ -6,13 +6,13 @@ pub fn main() {}
 struct ZkState { }
 
 impl pbc_traits::ReadRPC for ZkState {
-    fn rpc_read_from<T: std::io::Read>(_reader: &mut T) -> Self {
+    fn rpc_read_from<T: pbc_traits::PbcRead>(_reader: &mut T) -> Self {
         unimplemented!()
     }
 }
 
 impl pbc_traits::WriteRPC for ZkState {
-    fn rpc_write_to<T: std::io::Write>(&self, _writer: &mut T) -> std::io::Result<()> {
+    fn rpc_write_to<T: pbc_traits::PbcWrite>(&self, _writer: &mut T) -> pbc_traits::io::Result<()> {
         unimplemented!()
     }
 }
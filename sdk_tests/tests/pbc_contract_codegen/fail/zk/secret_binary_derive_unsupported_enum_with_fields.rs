@@ -0,0 +1,12 @@
+use create_type_spec_derive::CreateTypeSpec;
+use pbc_zk::{Sbi32, SecretBinary};
+
+pub fn main() {}
+
+#[derive(CreateTypeSpec, SecretBinary)]
+enum SecretEnum {
+    #[discriminant(0)]
+    Some { data: Sbi32 },
+    #[discriminant(1)]
+    None {},
+}
@@ -10,8 +10,8 @@ struct ContractState { }
 
 impl pbc_traits::ReadWriteState for ContractState {
     const SERIALIZABLE_BY_COPY: bool = true;
-    fn state_read_from<T: std::io::Read>(_reader: &mut T) -> Self { Self {} }
-    fn state_write_to<T: std::io::Write>(&self, _writer: &mut T) -> std::io::Result<()> { std::io::Result::Ok(()) }
+    fn state_read_from<T: pbc_traits::PbcRead>(_reader: &mut T) -> Self { Self {} }
+    fn state_write_to<T: pbc_traits::PbcWrite>(&self, _writer: &mut T) -> pbc_traits::io::Result<()> { Ok(()) }
 }
 
 #[init(zk = true)]
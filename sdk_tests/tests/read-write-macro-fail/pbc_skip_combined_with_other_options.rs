@@ -2,7 +2,7 @@
 use read_write_state_derive::ReadWriteState;
 
 #[derive(ReadWriteState)]
-enum MyEnum {
-    #[discriminant(0)]
-    SomeTuple(u8, u8),
-}
\ No newline at end of file
+struct MyStruct {
+    #[pbc(skip, rename = "other")]
+    value: u8,
+}
@@ -2,8 +2,7 @@
 use read_write_state_derive::ReadWriteState;
 
 #[derive(ReadWriteState)]
-#[repr(C)]
-enum MyOptionU32 {
-    SomeU32(u32),
-    NoInt,
+struct MyStruct {
+    #[pbc(flatten)]
+    value: u8,
 }
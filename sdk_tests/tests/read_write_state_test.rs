@@ -7,10 +7,11 @@ use pbc_contract_common::address::{Address, AddressType};
 use pbc_contract_common::avl_tree_map::AvlTreeMap;
 use pbc_contract_common::signature::Signature;
 use pbc_contract_common::sorted_vec_map::{SortedVec, SortedVecMap, SortedVecSet};
-use pbc_traits::ReadWriteState;
+use pbc_traits::{DecodeError, ReadWriteState};
 use pbc_zk::SecretBinary;
-use read_write_state_derive::ReadWriteState;
-use std::collections::VecDeque;
+use read_write_state_derive::{ReadWriteState, StateView};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::time::Duration;
 
 use proptest::proptest;
 
@@ -20,10 +21,19 @@ fn read_write_state_roundtrip<T: ReadWriteState>(struct_1: &T, byte_repr: &[u8])
     let mut buf: Vec<u8> = Vec::new();
     struct_1.state_write_to(&mut buf).unwrap();
     assert_eq!(&buf, byte_repr);
+    assert_eq!(struct_1.state_serialized_size(), byte_repr.len());
+    assert_eq!(T::state_skip(byte_repr), Ok(byte_repr.len()));
 
     let mut buf: Vec<u8> = Vec::new();
     struct_1.state_write_to(&mut buf).unwrap();
 
+    // Fallible reading must agree with infallible reading
+    let mut try_reader = std::io::Cursor::new(buf.clone());
+    let try_read = T::try_state_read_from(&mut try_reader).unwrap();
+    let mut try_buf: Vec<u8> = Vec::new();
+    try_read.state_write_to(&mut try_buf).unwrap();
+    assert_eq!(&try_buf, byte_repr);
+
     let mut ctx_reader = std::io::Cursor::new(buf);
     T::state_read_from(&mut ctx_reader)
 }
@@ -112,6 +122,35 @@ struct VecDequeStructWithPadding {
     ls: VecDeque<StructWithPadding>,
 }
 
+#[derive(Eq, PartialEq, ReadWriteState, Debug)]
+struct StructWithTuples {
+    pair: (u8, u16),
+    entries: Vec<(u8, Option<u8>)>,
+}
+
+#[derive(Eq, PartialEq, ReadWriteState, Debug)]
+struct LinkedNode {
+    value: u32,
+    next: Option<Box<LinkedNode>>,
+}
+
+#[derive(Eq, PartialEq, ReadWriteState, Debug)]
+struct TupleStruct(u8, u16);
+
+#[derive(Eq, PartialEq, ReadWriteState, Debug)]
+#[repr(transparent)]
+struct Newtype(u32);
+
+#[derive(Eq, PartialEq, ReadWriteState, Debug)]
+struct TupleStructWithVec(Newtype, Vec<u8>);
+
+#[derive(Eq, PartialEq, ReadWriteState, Debug)]
+struct StructWithMaps {
+    balances: BTreeMap<u8, u32>,
+    members: BTreeSet<u16>,
+    lookup: HashMap<u8, bool>,
+}
+
 #[derive(Eq, PartialEq, ReadWriteState, SecretBinary, CreateTypeSpec, Debug)]
 #[repr(C, align(4))]
 struct StructWithAlignment {
@@ -299,6 +338,83 @@ enum EnumItemStruct {
     C { a: SimpleStruct },
 }
 
+#[derive(ReadWriteState, Debug, Eq, PartialEq)]
+enum Phase {
+    #[discriminant(0)]
+    Bidding(u64),
+    #[discriminant(1)]
+    Closed { winner: Address },
+    #[discriminant(2)]
+    Cancelled,
+}
+
+#[derive(ReadWriteState, Debug, Eq, PartialEq)]
+struct Auction {
+    phase: Phase,
+    history: Vec<Phase>,
+}
+
+/// Serializes a [`Duration`] as its number of milliseconds.
+mod duration_as_millis {
+    use pbc_traits::{io, DecodeError, PbcRead, PbcWrite, ReadWriteState};
+    use std::time::Duration;
+
+    pub fn state_read_from<R: PbcRead>(reader: &mut R) -> Duration {
+        Duration::from_millis(u64::state_read_from(reader))
+    }
+
+    pub fn try_state_read_from<R: PbcRead>(reader: &mut R) -> Result<Duration, DecodeError> {
+        u64::try_state_read_from(reader).map(Duration::from_millis)
+    }
+
+    pub fn state_write_to<W: PbcWrite>(value: &Duration, writer: &mut W) -> io::Result<()> {
+        (value.as_millis() as u64).state_write_to(writer)
+    }
+}
+
+#[derive(ReadWriteState, Debug, Eq, PartialEq)]
+struct StructWithFieldOptions {
+    count: u32,
+    #[pbc(skip)]
+    cached_total: u64,
+    #[pbc(with = "duration_as_millis")]
+    timeout: Duration,
+}
+
+#[derive(ReadWriteState, Debug, Eq, PartialEq)]
+struct StructWithSkippedByte {
+    a: u8,
+    #[pbc(skip)]
+    b: u8,
+}
+
+#[derive(ReadWriteState, Debug, Eq, PartialEq)]
+enum EnumWithFieldOptions {
+    #[discriminant(0)]
+    Waiting(#[pbc(skip)] u32, u8),
+    #[discriminant(1)]
+    Timed {
+        #[pbc(with = "duration_as_millis")]
+        timeout: Duration,
+    },
+}
+
+#[derive(ReadWriteState, StateView, Debug, Eq, PartialEq)]
+struct LedgerState {
+    owner: Address,
+    names: Vec<String>,
+    #[pbc(skip)]
+    cached_total: u64,
+    #[pbc(with = "duration_as_millis")]
+    timeout: Duration,
+    balances: BTreeMap<u8, u64>,
+    settings: LedgerSettings,
+    counter: u32,
+}
+
+#[derive(ReadWriteState, StateView, Debug, Eq, PartialEq)]
+struct LedgerSettings(Option<String>, Phase);
+
 #[derive(ReadWriteState, Debug, CreateTypeSpec)]
 struct StateWithAvlMap {
     map: AvlTreeMap<u32, String>,
@@ -641,6 +757,73 @@ pub fn serialize_state_with_vec_deque() {
     read_write_state_roundtrip_with_eq(&state, &buffer);
 }
 
+#[test]
+pub fn serialize_state_with_tuples() {
+    let state = StructWithTuples {
+        pair: (0x01, 0x0203),
+        entries: vec![(0x04, None), (0x05, Some(0x06))],
+    };
+    let buffer = [
+        0x01, 0x03, 0x02, // pair
+        0x02, 0x00, 0x00, 0x00, // entries length
+        0x04, 0x00, // entries[0]
+        0x05, 0x01, 0x06, // entries[1]
+    ];
+    read_write_state_roundtrip_with_eq(&state, &buffer);
+}
+
+#[test]
+pub fn serialize_recursive_struct() {
+    let state = LinkedNode {
+        value: 0x01,
+        next: Some(Box::new(LinkedNode {
+            value: 0x02,
+            next: None,
+        })),
+    };
+    let buffer = [
+        0x01, 0x00, 0x00, 0x00, // value
+        0x01, // next is Some
+        0x02, 0x00, 0x00, 0x00, // next.value
+        0x00, // next.next is None
+    ];
+    read_write_state_roundtrip_with_eq(&state, &buffer);
+}
+
+#[test]
+pub fn serialize_tuple_structs() {
+    read_write_state_roundtrip_with_eq(&TupleStruct(0x01, 0x0203), &[0x01, 0x03, 0x02]);
+    read_write_state_roundtrip_with_eq(&Newtype(0x01020304), &[0x04, 0x03, 0x02, 0x01]);
+    read_write_state_roundtrip_with_eq(
+        &TupleStructWithVec(Newtype(0x05), vec![0x06]),
+        &[
+            0x05, 0x00, 0x00, 0x00, // Newtype
+            0x01, 0x00, 0x00, 0x00, 0x06, // Vec
+        ],
+    );
+}
+
+#[test]
+pub fn serialize_state_with_maps() {
+    let state = StructWithMaps {
+        balances: BTreeMap::from([(2, 0x0102), (1, 0x03)]),
+        members: BTreeSet::from([0x0405, 0x0001]),
+        lookup: HashMap::from([(9, true), (3, false), (5, true)]),
+    };
+    let buffer = [
+        0x02, 0x00, 0x00, 0x00, // balances length
+        0x01, 0x03, 0x00, 0x00, 0x00, // balances[1]
+        0x02, 0x02, 0x01, 0x00, 0x00, // balances[2]
+        0x02, 0x00, 0x00, 0x00, // members length
+        0x01, 0x00, 0x05, 0x04, // members
+        0x03, 0x00, 0x00, 0x00, // lookup length
+        0x03, 0x00, 0x05, 0x01, 0x09, 0x01, // lookup, sorted by key
+    ];
+    // Debug output of HashMap is not deterministic, so only compare by equality
+    let state_2 = read_write_state_roundtrip(&state, &buffer);
+    assert_eq!(state, state_2);
+}
+
 #[test]
 pub fn serialize_vecdeque_with_addresses() {
     let state = VecDequeWithAddresses {
@@ -1058,6 +1241,243 @@ pub fn serialize_enum_item_struct() {
     read_write_state_roundtrip_with_eq(&my_enum_c, &[0x7D, 0x00]);
 }
 
+#[test]
+pub fn serialize_enum_with_data() {
+    read_write_state_roundtrip_with_eq(
+        &Phase::Bidding(0x0102),
+        &[0x00, 0x02, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+    read_write_state_roundtrip_with_eq(
+        &Phase::Closed {
+            winner: Address {
+                address_type: AddressType::Account,
+                identifier: [0x11; 20],
+            },
+        },
+        &[
+            0x01, // Closed
+            0x00, // Account
+            0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11,
+            0x11, 0x11, 0x11, 0x11, 0x11, 0x11,
+        ],
+    );
+    read_write_state_roundtrip_with_eq(&Phase::Cancelled, &[0x02]);
+    read_write_state_roundtrip_with_eq(
+        &Auction {
+            phase: Phase::Cancelled,
+            history: vec![Phase::Bidding(7), Phase::Cancelled],
+        },
+        &[
+            0x02, // phase
+            0x02, 0x00, 0x00, 0x00, // history length
+            0x00, 0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // history[0]
+            0x02, // history[1]
+        ],
+    );
+
+    assert_eq!(
+        Phase::try_state_read_from(&mut [0x00, 0x01].as_slice()),
+        Err(DecodeError::UnexpectedEof)
+    );
+    assert_eq!(
+        Phase::try_state_read_from(&mut [0x03].as_slice()),
+        Err(DecodeError::InvalidDiscriminant {
+            type_name: "Phase",
+            discriminant: 3
+        })
+    );
+}
+
+#[test]
+pub fn serialize_with_field_options() {
+    read_write_state_roundtrip_with_eq(
+        &StructWithFieldOptions {
+            count: 1,
+            cached_total: 0,
+            timeout: Duration::from_millis(0x0203),
+        },
+        &[
+            0x01, 0x00, 0x00, 0x00, // count
+            0x03, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // timeout
+        ],
+    );
+    read_write_state_roundtrip_with_eq(&StructWithSkippedByte { a: 1, b: 0 }, &[0x01]);
+    read_write_state_roundtrip_with_eq(&EnumWithFieldOptions::Waiting(0, 5), &[0x00, 0x05]);
+    read_write_state_roundtrip_with_eq(
+        &EnumWithFieldOptions::Timed {
+            timeout: Duration::from_millis(1),
+        },
+        &[0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+}
+
+#[test]
+pub fn skipped_fields_are_reconstructed_with_default() {
+    let value = StructWithFieldOptions {
+        count: 1,
+        cached_total: 99,
+        timeout: Duration::from_millis(2),
+    };
+    let mut buf: Vec<u8> = Vec::new();
+    value.state_write_to(&mut buf).unwrap();
+
+    let read = StructWithFieldOptions::state_read_from(&mut buf.as_slice());
+    assert_eq!(read.count, 1);
+    assert_eq!(read.cached_total, 0);
+    assert_eq!(read.timeout, Duration::from_millis(2));
+
+    let mut buf: Vec<u8> = Vec::new();
+    EnumWithFieldOptions::Waiting(7, 5)
+        .state_write_to(&mut buf)
+        .unwrap();
+    assert_eq!(
+        EnumWithFieldOptions::try_state_read_from(&mut buf.as_slice()),
+        Ok(EnumWithFieldOptions::Waiting(0, 5))
+    );
+}
+
+#[test]
+pub fn try_read_unknown_discriminant() {
+    assert_eq!(
+        StatusEnum::try_state_read_from(&mut [0x03].as_slice()),
+        Err(DecodeError::InvalidDiscriminant {
+            type_name: "StatusEnum",
+            discriminant: 3
+        })
+    );
+    assert_eq!(
+        EnumItemStruct::try_state_read_from(&mut [0x01, 0x00].as_slice()),
+        Err(DecodeError::InvalidDiscriminant {
+            type_name: "EnumItemStruct",
+            discriminant: 1
+        })
+    );
+}
+
+#[test]
+pub fn try_read_truncated() {
+    assert_eq!(
+        EnumItemStruct::try_state_read_from(&mut [0x03, 0x02].as_slice()),
+        Err(DecodeError::UnexpectedEof)
+    );
+    assert_eq!(
+        VecWithStatusEnums::try_state_read_from(&mut [0x02, 0x00, 0x00, 0x00, 0x01].as_slice()),
+        Err(DecodeError::UnexpectedEof)
+    );
+}
+
+fn ledger_state() -> LedgerState {
+    LedgerState {
+        owner: Address {
+            address_type: AddressType::PublicContract,
+            identifier: [0x22; 20],
+        },
+        names: vec!["alice".to_string(), "bob".to_string()],
+        cached_total: 0,
+        timeout: Duration::from_millis(500),
+        balances: BTreeMap::from([(1, 100), (2, 200)]),
+        settings: LedgerSettings(Some("fee".to_string()), Phase::Bidding(3)),
+        counter: 7,
+    }
+}
+
+#[test]
+pub fn skip_derived_values() {
+    let mut bytes: Vec<u8> = Vec::new();
+    ledger_state().state_write_to(&mut bytes).unwrap();
+    let len = bytes.len();
+    bytes.extend_from_slice(&[0xFF, 0xFF]);
+
+    assert_eq!(LedgerState::state_skip(&bytes), Ok(len));
+    assert_eq!(
+        LedgerState::state_skip(&bytes[..len - 1]),
+        Err(DecodeError::UnexpectedEof)
+    );
+    assert_eq!(
+        Phase::state_skip(&[0x03]),
+        Err(DecodeError::InvalidDiscriminant {
+            type_name: "Phase",
+            discriminant: 3
+        })
+    );
+    assert_eq!(Phase::state_skip(&[]), Err(DecodeError::UnexpectedEof));
+}
+
+#[test]
+pub fn state_view_reads_single_fields() {
+    let value = ledger_state();
+    let mut bytes: Vec<u8> = Vec::new();
+    value.state_write_to(&mut bytes).unwrap();
+
+    let view = pbc_traits::StateView::<LedgerState>::new(&bytes);
+    let fields = view.fields();
+    assert_eq!(fields.counter().read(), 7);
+    assert_eq!(fields.owner().read(), value.owner);
+    assert_eq!(fields.names().read(), value.names);
+    assert_eq!(fields.balances().read(), value.balances);
+    assert_eq!(view.read(), value);
+}
+
+#[test]
+pub fn state_view_of_nested_and_tuple_structs() {
+    let value = ledger_state();
+    let mut bytes: Vec<u8> = Vec::new();
+    value.state_write_to(&mut bytes).unwrap();
+
+    let settings = pbc_traits::StateView::<LedgerState>::new(&bytes)
+        .fields()
+        .settings();
+    assert_eq!(settings.fields().field_0().read(), Some("fee".to_string()));
+    assert_eq!(settings.fields().field_1().read(), Phase::Bidding(3));
+    assert_eq!(settings.try_read(), Ok(value.settings));
+}
+
+#[test]
+pub fn state_view_of_truncated_state() {
+    let mut bytes: Vec<u8> = Vec::new();
+    ledger_state().state_write_to(&mut bytes).unwrap();
+    let truncated = &bytes[..bytes.len() - 2];
+
+    let fields = pbc_traits::StateView::<LedgerState>::new(truncated).fields();
+    assert_eq!(fields.names().read().len(), 2);
+    assert_eq!(fields.counter().try_read(), Err(DecodeError::UnexpectedEof));
+}
+
+#[test]
+pub fn state_view_try_accessors_of_malformed_preceding_fields() {
+    let bytes = [0x00; 10];
+    let fields = pbc_traits::StateView::<LedgerState>::new(&bytes).fields();
+    assert!(fields.try_owner().is_ok());
+    assert_eq!(
+        fields.try_counter().map(|view| view.bytes().len()),
+        Err(DecodeError::UnexpectedEof)
+    );
+}
+
+#[test]
+pub fn state_view_borrows_values_serializable_by_copy() {
+    let value = ledger_state();
+    let mut bytes: Vec<u8> = Vec::new();
+    value.state_write_to(&mut bytes).unwrap();
+
+    let fields = pbc_traits::StateView::<LedgerState>::new(&bytes).fields();
+    assert_eq!(fields.owner().get(), Some(&value.owner));
+    assert_eq!(fields.names().get(), None);
+    assert_eq!(
+        pbc_traits::StateView::<Address>::new(&bytes[..3]).try_get(),
+        Err(DecodeError::UnexpectedEof)
+    );
+}
+
+#[test]
+#[should_panic]
+pub fn state_view_of_malformed_preceding_fields() {
+    let bytes = [0x00; 10];
+    pbc_traits::StateView::<LedgerState>::new(&bytes)
+        .fields()
+        .counter();
+}
+
 macro_rules! assert_serializable_by_copy{
     ($($type:ty)*) => {
         $(
@@ -1101,6 +1521,9 @@ assert_serializable_by_copy!(Tuple2<u8, Tuple2<u8, u8>>);
 assert_serializable_by_copy!(Tuple2<[u8; 3], Tuple2<[u8; 3], [u8; 2]>>);
 assert_serializable_by_copy!(Tuple2<u64, u64>);
 
+// Tuple structs
+assert_serializable_by_copy!(Newtype);
+
 // Map entry
 assert_serializable_by_copy!(pbc_contract_common::sorted_vec_map::entry::Entry<Address, u8>);
 
@@ -1124,3 +1547,61 @@ assert_serializable_by_copy_not!(VecWithAddresses);
 
 // EnumItemStruct
 assert_serializable_by_copy_not!(EnumItemStruct);
+assert_serializable_by_copy_not!(Phase);
+
+// Tuple structs
+assert_serializable_by_copy_not!(TupleStruct);
+assert_serializable_by_copy_not!(TupleStructWithVec);
+
+// Field options
+assert_serializable_by_copy_not!(StructWithFieldOptions);
+assert_serializable_by_copy_not!(StructWithSkippedByte);
+
+/// Derived code must not depend on the prelude names in scope of the deriving crate.
+mod shadowed_prelude {
+    use create_type_spec_derive::CreateTypeSpec;
+    use read_write_rpc_derive::ReadWriteRPC;
+    use read_write_state_derive::{ReadWriteState, StateView};
+
+    #[allow(clippy::enum_variant_names)]
+    pub enum Shadowed {
+        Ok,
+        Err,
+        Some,
+        None,
+    }
+    #[allow(unused_imports)]
+    pub use Shadowed::*;
+    pub type Result<T> = core::result::Result<T, Shadowed>;
+
+    #[derive(ReadWriteState, ReadWriteRPC, StateView, CreateTypeSpec, Debug, PartialEq)]
+    pub struct Record {
+        /// Identifier of the record.
+        pub id: u32,
+        pub names: Vec<String>,
+        pub kind: Kind,
+    }
+
+    #[derive(ReadWriteState, ReadWriteRPC, CreateTypeSpec, Debug, PartialEq)]
+    pub enum Kind {
+        #[discriminant(0)]
+        Plain {},
+        #[discriminant(1)]
+        Tagged { tag: u8 },
+    }
+}
+
+#[test]
+fn derives_with_shadowed_prelude() {
+    use shadowed_prelude::{Kind, Record};
+
+    let value = Record {
+        id: 3,
+        names: vec!["a".to_string()],
+        kind: Kind::Tagged { tag: 9 },
+    };
+    let bytes = [3, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, b'a', 1, 9];
+    assert_eq!(read_write_state_roundtrip(&value, &bytes), value);
+    let view = pbc_traits::StateView::<Record>::new(&bytes);
+    assert_eq!(view.fields().kind().read(), Kind::Tagged { tag: 9 });
+}
@@ -46,6 +46,30 @@ mod nonzk {
         (1, vec![])
     }
 
+    #[action]
+    pub fn action_with_address_array(
+        _ctx: ContractContext,
+        _state: u8,
+        signers: [Address; 3],
+        blob: [u8; 256],
+    ) -> (u8, Vec<EventGroup>) {
+        assert_eq!(signers.len(), 3);
+        assert_eq!(blob.len(), 256);
+        (1, vec![])
+    }
+
+    #[action]
+    pub fn action_with_tuples(
+        _ctx: ContractContext,
+        _state: u8,
+        transfer: (Address, u128),
+        amounts: Vec<(u8, u32)>,
+    ) -> (u8, Vec<EventGroup>) {
+        assert_eq!(transfer.1, 0);
+        assert_eq!(amounts.len(), 0);
+        (1, vec![])
+    }
+
     #[action]
     pub fn action_without_arg(_ctx: ContractContext, state: u8) -> (u8, Vec<EventGroup>) {
         (state, vec![])
@@ -88,5 +112,13 @@ mod nonzk {
     pub fn shortname_is_defined() {
         let _pointer = __abi_fn_action_with_arrays;
         let _pointer2 = __abi_fn_action_with_zero_shortname;
+        let _pointer3 = __abi_fn_action_with_address_array;
+    }
+
+    #[test]
+    #[cfg(feature = "abi")]
+    pub fn tuple_arguments_are_visible_to_abi_generator() {
+        let _pointer = __abi_type_as_fn_ptr_tuple_action_with_tuples_0;
+        let _pointer2 = __abi_type_as_fn_ptr_tuple_action_with_tuples_1;
     }
 }
@@ -1,3 +1,4 @@
+use pbc_traits::DecodeError;
 use pbc_traits::ReadRPC;
 use pbc_traits::WriteRPC;
 use read_write_rpc_derive::ReadRPC;
@@ -15,6 +16,13 @@ struct ComplexStruct {
     c: u32,
 }
 
+#[derive(ReadRPC, WriteRPC)]
+struct TupleStruct(u8, u32);
+
+#[derive(ReadRPC, WriteRPC)]
+#[repr(transparent)]
+struct Newtype(u16);
+
 #[derive(ReadRPC, WriteRPC)]
 enum AnEnum {
     #[discriminant(0)]
@@ -60,6 +68,108 @@ pub fn derive_for_complex_struct() {
     assert_eq!(complex2.c, 15432);
 }
 
+#[test]
+pub fn derive_for_tuple_struct() {
+    let tuple_struct = TupleStruct(42, 0x01020304);
+
+    let mut buf: Vec<u8> = Vec::new();
+    tuple_struct.rpc_write_to(&mut buf).unwrap();
+    assert_eq!(&buf, &[42, 1, 2, 3, 4]);
+
+    let read = TupleStruct::rpc_read_from(&mut buf.as_slice());
+    assert_eq!(read.0, 42);
+    assert_eq!(read.1, 0x01020304);
+
+    let mut reader: &[u8] = &[42, 1, 2];
+    assert!(matches!(
+        TupleStruct::try_rpc_read_from(&mut reader),
+        Err(DecodeError::UnexpectedEof)
+    ));
+}
+
+#[test]
+pub fn derive_for_newtype() {
+    let mut buf: Vec<u8> = Vec::new();
+    Newtype(0x0102).rpc_write_to(&mut buf).unwrap();
+    assert_eq!(&buf, &[1, 2]);
+
+    let read = Newtype::try_rpc_read_from(&mut buf.as_slice()).unwrap();
+    assert_eq!(read.0, 0x0102);
+}
+
+#[derive(ReadRPC, WriteRPC)]
+enum TupleVariantEnum {
+    #[discriminant(0)]
+    Pair(u8, u16),
+    #[discriminant(1)]
+    Empty,
+}
+
+#[test]
+pub fn derive_for_tuple_variant_enum() {
+    let mut buf: Vec<u8> = Vec::new();
+    TupleVariantEnum::Pair(1, 0x0203)
+        .rpc_write_to(&mut buf)
+        .unwrap();
+    assert_eq!(&buf, &[0, 1, 2, 3]);
+
+    let read = TupleVariantEnum::rpc_read_from(&mut buf.as_slice());
+    assert!(matches!(read, TupleVariantEnum::Pair(1, 0x0203)));
+
+    let mut buf: Vec<u8> = Vec::new();
+    TupleVariantEnum::Empty.rpc_write_to(&mut buf).unwrap();
+    assert_eq!(&buf, &[1]);
+
+    let read = TupleVariantEnum::try_rpc_read_from(&mut buf.as_slice()).unwrap();
+    assert!(matches!(read, TupleVariantEnum::Empty));
+}
+
+/// Serializes a [`bool`] as a `u32` with value 0 or 1.
+mod bool_as_u32 {
+    use pbc_traits::{io, DecodeError, PbcRead, PbcWrite, ReadRPC, WriteRPC};
+
+    pub fn rpc_read_from<R: PbcRead>(reader: &mut R) -> bool {
+        u32::rpc_read_from(reader) != 0
+    }
+
+    pub fn try_rpc_read_from<R: PbcRead>(reader: &mut R) -> Result<bool, DecodeError> {
+        u32::try_rpc_read_from(reader).map(|value| value != 0)
+    }
+
+    pub fn rpc_write_to<W: PbcWrite>(value: &bool, writer: &mut W) -> io::Result<()> {
+        u32::from(*value).rpc_write_to(writer)
+    }
+}
+
+#[derive(ReadRPC, WriteRPC)]
+struct StructWithFieldOptions {
+    #[pbc(with = "bool_as_u32")]
+    enabled: bool,
+    #[pbc(skip)]
+    note: String,
+    #[pbc(rename = "amount")]
+    value: u8,
+}
+
+#[test]
+pub fn derive_with_field_options() {
+    let value = StructWithFieldOptions {
+        enabled: true,
+        note: "not serialized".to_string(),
+        value: 42,
+    };
+
+    let mut buf: Vec<u8> = Vec::new();
+    value.rpc_write_to(&mut buf).unwrap();
+    assert_eq!(&buf, &[0, 0, 0, 1, 42]);
+    assert_eq!(value.rpc_serialized_size(), 5);
+
+    let read = StructWithFieldOptions::try_rpc_read_from(&mut buf.as_slice()).unwrap();
+    assert!(read.enabled);
+    assert_eq!(read.note, "");
+    assert_eq!(read.value, 42);
+}
+
 #[test]
 pub fn derive_for_simple_enum() {
     let simple_enum_1 = AnEnum::A { a: 1 };
@@ -84,3 +194,48 @@ pub fn derive_for_complex_enum() {
     complex_enum.rpc_write_to(&mut buf).unwrap();
     assert_eq!(&buf, &[125, 42]);
 }
+
+#[test]
+pub fn derived_rpc_serialized_size() {
+    let complex = ComplexStruct {
+        a: SimpleStruct { a: 42 },
+        b: SimpleStruct { a: 43 },
+        c: 15432,
+    };
+    assert_eq!(complex.rpc_serialized_size(), 6);
+    assert_eq!(TupleStruct(42, 0x01020304).rpc_serialized_size(), 5);
+    assert_eq!(Newtype(0x0102).rpc_serialized_size(), 2);
+    assert_eq!(TupleVariantEnum::Pair(1, 0x0203).rpc_serialized_size(), 4);
+    assert_eq!(AnEnum::B { a: 1, b: 42 }.rpc_serialized_size(), 3);
+}
+
+#[test]
+pub fn try_read_derived_struct() {
+    let mut reader: &[u8] = &[42, 43, 0, 0, 60, 72];
+    let complex = ComplexStruct::try_rpc_read_from(&mut reader).unwrap();
+    assert_eq!(complex.a.a, 42);
+    assert_eq!(complex.b.a, 43);
+    assert_eq!(complex.c, 15432);
+
+    let mut reader: &[u8] = &[42, 43, 0, 0];
+    assert!(matches!(
+        ComplexStruct::try_rpc_read_from(&mut reader),
+        Err(DecodeError::UnexpectedEof)
+    ));
+}
+
+#[test]
+pub fn try_read_derived_enum() {
+    let mut reader: &[u8] = &[3, 1, 42];
+    let read = AnEnum::try_rpc_read_from(&mut reader).unwrap();
+    assert!(matches!(read, AnEnum::B { a: 1, b: 42 }));
+
+    let mut reader: &[u8] = &[1, 1];
+    assert!(matches!(
+        AnEnum::try_rpc_read_from(&mut reader),
+        Err(DecodeError::InvalidDiscriminant {
+            type_name: "AnEnum",
+            discriminant: 1
+        })
+    ));
+}
@@ -39,6 +39,9 @@ struct StructWithSeveralBits {
     v5: Sbi1,
 }
 
+#[derive(SecretBinary)]
+struct TupleStruct(Sbi32, Sbi8);
+
 #[test]
 fn test() {
     assert_eq!(MyStruct1::BITS, 0);
@@ -48,4 +51,5 @@ fn test() {
     assert_eq!(MyStruct7::BITS, 8 * 128);
     assert_eq!(StructWithBit::BITS, 1);
     assert_eq!(StructWithSeveralBits::BITS, 5);
+    assert_eq!(TupleStruct::BITS, 40);
 }